@@ -19,8 +19,10 @@ const CMD_SETUP: &str = "setup";
 const CMD_TEARDOWN: &str = "teardown";
 const CMD_BACKUP: &str = "backup";
 const CMD_AUDIT: &str = "audit";
+const CMD_SESSION: &str = "session";
 const CMD_RETENTION: &str = "retention";
 const CMD_READ: &str = "read";
+const CMD_UNLOCK: &str = "unlock";
 
 const ARG_CONFIG: &str = "config";
 const ARG_CLIENT_ID: &str = "client-id";
@@ -135,6 +137,21 @@ async fn main() {
                                 .index(1),
                         ),
                 ]),
+            SubCommand::with_name(CMD_SESSION)
+                .help("Session")
+                .subcommands(vec![SubCommand::with_name(CMD_RETENTION)
+                    .about("Delete sessions which have expired")]),
+            SubCommand::with_name(CMD_USER)
+                .aliases(&["u"])
+                .help("User")
+                .subcommands(vec![SubCommand::with_name(CMD_UNLOCK)
+                    .about("Unlock user locked by failed logins")
+                    .arg(
+                        Arg::with_name(ARG_USER_EMAIL)
+                            .help("User email")
+                            .required(true)
+                            .index(1),
+                    )]),
         ])
         .get_matches();
 
@@ -204,6 +221,23 @@ async fn main() {
                 println!("{}", submatches.usage());
             }
         },
+        (CMD_SESSION, Some(submatches)) => match submatches.subcommand() {
+            (CMD_RETENTION, Some(_submatches)) => {
+                sso::cli::session_retention(&config).await;
+            }
+            _ => {
+                println!("{}", submatches.usage());
+            }
+        },
+        (CMD_USER, Some(submatches)) => match submatches.subcommand() {
+            (CMD_UNLOCK, Some(submatches)) => {
+                let user_email = submatches.value_of(ARG_USER_EMAIL).unwrap();
+                sso::cli::user_unlock(&config, user_email).await;
+            }
+            _ => {
+                println!("{}", submatches.usage());
+            }
+        },
         _ => {
             println!("{}", matches.usage());
         }
@@ -38,6 +38,14 @@ pub async fn generate_client(
         user_scope: oauth2::Scope::default().into_inner(),
         register_enable: true,
         register_scope: oauth2::Scope::default().into_inner(),
+        register_approval: false,
+        register_approval_email: Vec::new(),
+        register_approval_webhook_uri: None,
+        require_mfa: false,
+        require_email_verified: false,
+        magic_link_enable: false,
+        device_notify_enable: false,
+        password_max_age_days: None,
         ttl: ConfigOauth2ClientTtl::default(),
         templates: ConfigOauth2ClientTemplates::default(),
     };
@@ -4,7 +4,7 @@ use crate::internal::*;
 pub async fn generate_password(config: &Config) {
     let postgres = Postgres::from_config(config).await.unwrap();
     let password = postgres.secret_generate().await.unwrap();
-    let password_hash = postgres.password_hash(&password).await.unwrap();
+    let password_hash = password::hash(&config.password, &password).unwrap();
 
     println!("The password is:\r\n");
     println!("{}\r\n", password);
@@ -19,7 +19,7 @@ pub async fn generate_user(
 ) {
     let postgres = Postgres::from_config(config).await.unwrap();
     let password = postgres.secret_generate().await.unwrap();
-    let user_password = postgres.password_hash(&password).await.unwrap();
+    let user_password = password::hash(&config.password, &password).unwrap();
 
     let user_id = match user_id {
         Some(user_id) => user_id.to_string(),
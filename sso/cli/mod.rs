@@ -8,8 +8,11 @@ mod generate_secret;
 mod generate_user;
 mod postgres_setup;
 mod postgres_teardown;
+mod session_retention;
+mod user_unlock;
 
 pub use {
     audit_read::*, audit_retention::*, backup::*, generate_client::*, generate_password::*,
     generate_secret::*, generate_user::*, postgres_setup::*, postgres_teardown::*,
+    session_retention::*, user_unlock::*,
 };
@@ -0,0 +1,11 @@
+use crate::internal::*;
+
+/// Delete expired sessions and devices
+pub async fn session_retention(config: &Config) {
+    let postgres = Postgres::from_config(config).await.unwrap();
+
+    let deleted = postgres.session_retention().await.unwrap();
+    println!("Deleted {} sessions", deleted);
+    let deleted = postgres.user_device_retention().await.unwrap();
+    println!("Deleted {} devices", deleted);
+}
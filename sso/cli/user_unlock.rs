@@ -0,0 +1,10 @@
+use crate::internal::*;
+
+/// Unlock user email address locked by failed logins
+pub async fn user_unlock(config: &Config, email: &str) {
+    let postgres = Postgres::from_config(config).await.unwrap();
+
+    let audit = Audit::from_type("sso_cli_user_unlock");
+    postgres.user_unlock(audit, email).await.unwrap();
+    println!("Unlocked {}", email);
+}
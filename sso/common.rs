@@ -10,10 +10,26 @@ pub struct RequestOauth2Authorize {
     pub auth_type: String,
     #[validate(email)]
     pub email: Option<String>,
-    #[validate(custom = "validate::password")]
+    #[validate(custom = "validate::password_optional")]
     pub password: Option<String>,
     #[validate(custom = "validate::oauth2_provider")]
     pub oauth2_provider: Option<String>,
+    #[validate(custom = "validate::code")]
+    pub code: Option<String>,
+    #[validate(custom = "validate::totp")]
+    pub totp: Option<String>,
+    #[validate(custom = "validate::recovery_code")]
+    pub recovery_code: Option<String>,
+    #[validate(custom = "validate::webauthn")]
+    pub challenge: Option<String>,
+    #[validate(custom = "validate::webauthn")]
+    pub credential_id: Option<String>,
+    #[validate(custom = "validate::webauthn")]
+    pub client_data: Option<String>,
+    #[validate(custom = "validate::webauthn")]
+    pub authenticator_data: Option<String>,
+    #[validate(custom = "validate::webauthn")]
+    pub signature: Option<String>,
 }
 
 #[derive(Debug, Default, Clone, Validate, Serialize, Deserialize, Apiv2Schema)]
@@ -85,20 +101,98 @@ pub struct RequestAuthDelete {
     pub password: String,
 }
 
+#[derive(Debug, Default, Clone, Validate, Serialize, Deserialize, Apiv2Schema)]
+pub struct RequestAuthEmailUpdateQuery {
+    pub client_id: Option<Uuid>,
+    #[validate(url)]
+    pub redirect_uri: Option<String>,
+    #[validate(length(min = 1, max = 10))]
+    pub response_type: Option<String>,
+    #[validate(custom = "validate::code")]
+    pub code: Option<String>,
+}
+
 #[derive(Debug, Default, Clone, Validate, Serialize, Deserialize, Apiv2Schema)]
 pub struct RequestAuthEmailUpdate {
     #[serde(default)]
     #[validate(custom = "validate::csrf_token")]
     pub csrf_token: String,
-    #[serde(default)]
     #[validate(custom = "validate::password")]
-    pub password: String,
-    #[serde(default)]
+    pub password: Option<String>,
     #[validate(email)]
-    pub email_new: String,
-    #[serde(default)]
+    pub email_new: Option<String>,
     #[validate(email)]
-    pub email_confirm: String,
+    pub email_confirm: Option<String>,
+}
+
+#[derive(Debug, Default, Clone, Validate, Serialize, Deserialize, Apiv2Schema)]
+pub struct RequestAuthLink {
+    #[serde(default)]
+    #[validate(custom = "validate::csrf_token")]
+    pub csrf_token: String,
+    #[serde(default)]
+    #[validate(length(min = 1, max = 20))]
+    pub link_type: String,
+    #[serde(default)]
+    #[validate(custom = "validate::oauth2_provider")]
+    pub oauth2_provider: String,
+}
+
+#[derive(Debug, Default, Clone, Validate, Serialize, Deserialize, Apiv2Schema)]
+pub struct RequestAuthAccount {
+    #[serde(default)]
+    #[validate(custom = "validate::csrf_token")]
+    pub csrf_token: String,
+    #[serde(default)]
+    #[validate(length(min = 1, max = 20))]
+    pub account_type: String,
+    #[validate(custom = "validate::name")]
+    pub name: Option<String>,
+    #[validate(custom = "validate::locale")]
+    pub locale: Option<String>,
+    #[validate(custom = "validate::timezone")]
+    pub timezone: Option<String>,
+    pub session_id: Option<Uuid>,
+}
+
+#[derive(Debug, Default, Clone, Validate, Serialize, Deserialize, Apiv2Schema)]
+pub struct RequestAuthTotp {
+    #[serde(default)]
+    #[validate(custom = "validate::csrf_token")]
+    pub csrf_token: String,
+    #[serde(default)]
+    #[validate(length(min = 1, max = 20))]
+    pub totp_type: String,
+    #[serde(default)]
+    #[validate(custom = "validate::totp")]
+    pub totp: String,
+}
+
+#[derive(Debug, Default, Clone, Validate, Serialize, Deserialize, Apiv2Schema)]
+pub struct RequestAuthRecovery {
+    #[serde(default)]
+    #[validate(custom = "validate::csrf_token")]
+    pub csrf_token: String,
+}
+
+#[derive(Debug, Default, Clone, Validate, Serialize, Deserialize, Apiv2Schema)]
+pub struct RequestAuthWebauthn {
+    #[serde(default)]
+    #[validate(custom = "validate::csrf_token")]
+    pub csrf_token: String,
+    #[serde(default)]
+    #[validate(length(min = 1, max = 20))]
+    pub webauthn_type: String,
+    #[validate(length(max = 500))]
+    pub name: Option<String>,
+    #[validate(custom = "validate::webauthn")]
+    pub challenge: Option<String>,
+    #[validate(custom = "validate::webauthn")]
+    pub credential_id: Option<String>,
+    #[validate(custom = "validate::webauthn")]
+    pub client_data: Option<String>,
+    #[validate(custom = "validate::webauthn")]
+    pub attestation_object: Option<String>,
 }
 
 #[derive(Debug, Default, Clone, Validate, Serialize, Deserialize, Apiv2Schema)]
@@ -148,6 +242,27 @@ pub struct RequestAuthResponseQuery {
     pub code: String,
 }
 
+#[derive(Debug, Default, Clone, Validate, Serialize, Deserialize, Apiv2Schema)]
+pub struct RequestAuthCodeQuery {
+    #[serde(default)]
+    #[validate(custom = "validate::code")]
+    pub code: String,
+}
+
+#[derive(Debug, Default, Clone, Validate, Serialize, Deserialize, Apiv2Schema)]
+pub struct RequestAuthMagicLink {
+    #[serde(default)]
+    #[validate(custom = "validate::csrf_token")]
+    pub csrf_token: String,
+}
+
+#[derive(Debug, Default, Clone, Validate, Serialize, Deserialize, Apiv2Schema)]
+pub struct RequestAuthEmailVerify {
+    #[serde(default)]
+    #[validate(custom = "validate::csrf_token")]
+    pub csrf_token: String,
+}
+
 #[derive(Debug, Default, Clone, Validate, Serialize, Deserialize, Apiv2Schema)]
 pub struct RequestAuthRegister {
     #[serde(default)]
@@ -235,6 +350,7 @@ pub struct RequestUserUpdate {
     pub password: Option<RequestUserPasswordUpdate>,
     #[validate]
     pub access: Option<RequestUserAccessUpdate>,
+    pub factor_reset: Option<bool>,
 }
 
 #[derive(Debug, Default, Clone, Validate, Serialize, Deserialize, Apiv2Schema)]
@@ -255,6 +371,44 @@ pub struct RequestUserDelete {
     pub id: Uuid,
 }
 
+#[derive(Debug, Clone, Validate, Serialize, Deserialize, Apiv2Schema)]
+pub struct RequestUserRegisterApproval {
+    pub user_id: Uuid,
+}
+
+#[derive(Debug, Default, Clone, Validate, Serialize, Deserialize, Apiv2Schema)]
+pub struct RequestUserInvite {
+    /// User name
+    #[serde(default)]
+    #[validate(custom = "validate::name")]
+    pub name: String,
+    /// User email address
+    #[serde(default)]
+    #[validate(email)]
+    pub email: String,
+    /// User access scope
+    #[serde(default)]
+    #[validate(custom = "validate::scope")]
+    pub scope: String,
+}
+
+#[derive(Debug, Clone, Validate, Serialize, Deserialize, Apiv2Schema)]
+pub struct RequestUserUnlock {
+    #[validate(email)]
+    pub email: String,
+}
+
+#[derive(Debug, Clone, Validate, Serialize, Deserialize, Apiv2Schema)]
+pub struct RequestUserSessionRead {
+    pub user_id: Uuid,
+}
+
+#[derive(Debug, Clone, Validate, Serialize, Deserialize, Apiv2Schema)]
+pub struct RequestUserSessionDelete {
+    pub user_id: Uuid,
+    pub id: Option<Uuid>,
+}
+
 #[derive(Debug, Clone, Validate, Serialize, Deserialize, Apiv2Schema)]
 pub struct RequestUserAccessRead {
     pub user_id: Uuid,
@@ -267,14 +421,17 @@ pub struct ResponseUser {
     pub id: Uuid,
     pub name: String,
     pub email: String,
+    pub email_verified_at: Option<DateTime<Utc>>,
     pub locale: String,
     pub timezone: String,
     pub enable: bool,
+    pub approval_pending: bool,
     #[serde(rename = "static")]
     pub static_: bool,
     pub password: Option<ResponseUserPassword>,
     pub oauth2_provider: Vec<ResponseUserOauth2Provider>,
     pub oauth2_provider_count: i64,
+    pub factor_count: i64,
     pub access: Option<ResponseAccess>,
 }
 
@@ -304,6 +461,22 @@ pub struct ResponseUserOauth2Provider {
     pub static_: bool,
 }
 
+#[derive(Debug, Clone, Serialize, Deserialize, Apiv2Schema)]
+pub struct ResponseUserSession {
+    pub created_at: DateTime<Utc>,
+    pub id: Uuid,
+    pub user_id: Uuid,
+    pub user_agent: String,
+    pub remote_addr: String,
+    pub methods: Vec<String>,
+    pub last_seen_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, Apiv2Schema)]
+pub struct ResponseUserSessionMany {
+    pub data: Vec<ResponseUserSession>,
+}
+
 #[derive(Debug, Default, Clone, Validate, Serialize, Deserialize, Apiv2Schema)]
 pub struct RequestCsrf {
     #[serde(default)]
@@ -491,6 +664,11 @@ pub struct ResponseClient {
     pub user_scope: String,
     pub register_enable: bool,
     pub register_scope: String,
+    pub register_approval: bool,
+    pub require_mfa: bool,
+    pub require_email_verified: bool,
+    pub magic_link_enable: bool,
+    pub device_notify_enable: bool,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, Apiv2Schema)]
@@ -517,6 +695,14 @@ pub(crate) struct Client {
     pub user_scope: oauth2::Scope,
     pub register_enable: bool,
     pub register_scope: oauth2::Scope,
+    pub register_approval: bool,
+    pub register_approval_email: Vec<String>,
+    pub register_approval_webhook_uri: Option<Url>,
+    pub require_mfa: bool,
+    pub require_email_verified: bool,
+    pub magic_link_enable: bool,
+    pub device_notify_enable: bool,
+    pub password_max_age_days: Option<i64>,
     pub ttl: ConfigOauth2ClientTtl,
     pub templates: ConfigOauth2ClientTemplates,
 }
@@ -573,6 +759,11 @@ impl From<&Client> for ResponseClient {
             user_scope: x.user_scope.to_string(),
             register_enable: x.register_enable,
             register_scope: x.register_scope.to_string(),
+            register_approval: x.register_approval,
+            require_mfa: x.require_mfa,
+            require_email_verified: x.require_email_verified,
+            magic_link_enable: x.magic_link_enable,
+            device_notify_enable: x.device_notify_enable,
         }
     }
 }
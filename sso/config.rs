@@ -1,5 +1,6 @@
 //! # Configuration
 use crate::internal::*;
+use std::{net::IpAddr, path::PathBuf};
 
 /// Log Configuration
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -17,6 +18,14 @@ pub struct ConfigHttp {
     pub private: ConfigHttpIf,
     /// Cookie configuration
     pub cookie: ConfigHttpCookie,
+    /// Minimum number of milliseconds for login, password reset, register and magic link
+    /// form responses, so that response time does not reveal which users exist
+    #[serde(default = "default_as_500")]
+    pub auth_response_min_ms: u64,
+    /// IP addresses of trusted reverse proxies, client IP address of requests from these
+    /// addresses is read from the `X-Forwarded-For` header
+    #[serde(default)]
+    pub trusted_proxies: Vec<IpAddr>,
 }
 
 /// HTTP Interface Configuration
@@ -85,6 +94,28 @@ pub struct ConfigOauth2Client {
     /// Scope given to user on registration with client
     #[serde(default)]
     pub register_scope: Vec<String>,
+    /// Require registrations with client to be approved before users are enabled
+    #[serde(default)]
+    pub register_approval: bool,
+    /// Email addresses of approvers notified of registrations with client
+    #[serde(default)]
+    pub register_approval_email: Vec<String>,
+    /// Webhook URI notified of registrations with client using a JSON POST request
+    pub register_approval_webhook_uri: Option<Url>,
+    /// Require users to authenticate with a second factor
+    #[serde(default)]
+    pub require_mfa: bool,
+    /// Require users to verify their email address before login
+    #[serde(default)]
+    pub require_email_verified: bool,
+    /// Allow passwordless login using email links for client
+    #[serde(default)]
+    pub magic_link_enable: bool,
+    /// Send email to users on login from a device that is not known
+    #[serde(default)]
+    pub device_notify_enable: bool,
+    /// Maximum password age in days for users of client, overrides password configuration
+    pub password_max_age_days: Option<i64>,
     /// Token TTL configuration
     #[serde(default)]
     pub ttl: ConfigOauth2ClientTtl,
@@ -111,6 +142,12 @@ pub struct ConfigOauth2ClientTtl {
     /// Number of seconds after access token expiry that refresh token is valid for
     #[serde(default = "default_as_86400")]
     pub token_refresh_s: i64,
+    /// Number of seconds an email update can be reverted from the previous email address
+    #[serde(default = "default_as_604800")]
+    pub email_revert_s: i64,
+    /// Number of seconds a user invitation is valid for
+    #[serde(default = "default_as_604800")]
+    pub invite_s: i64,
 }
 
 /// OAuth2 Client Templates Configuration
@@ -174,6 +211,8 @@ pub struct ConfigOauth2Providers {
     pub sso: Option<ConfigOauth2Provider>,
     /// Microsoft provider
     pub microsoft: Option<ConfigOauth2Provider>,
+    /// GitHub provider
+    pub github: Option<ConfigOauth2Provider>,
 }
 
 /// OAuth2 Provider Configuration
@@ -183,14 +222,136 @@ pub struct ConfigOauth2Provider {
     pub client_id: String,
     /// Client secret
     pub client_secret: String,
-    /// Authorization URI
-    pub authorize_uri: Url,
-    /// Token URI
-    pub token_uri: Url,
+    /// OIDC issuer URI, if set provider is configured using discovery
+    /// and ID tokens are validated
+    pub issuer: Option<Url>,
+    /// Authorization URI (discovered from issuer if not set)
+    pub authorize_uri: Option<Url>,
+    /// Token URI (discovered from issuer if not set)
+    pub token_uri: Option<Url>,
     /// Introspection URI
     pub introspect_uri: Option<Url>,
     /// OIDC userinfo URI
     pub oidc_userinfo_uri: Option<Url>,
+    /// Additional scope requested from provider
+    #[serde(default)]
+    pub scope: Vec<String>,
+    /// Number of seconds before OIDC key set is refreshed
+    #[serde(default = "default_as_3600")]
+    pub jwks_refresh_s: i64,
+    /// API URI (GitHub only)
+    #[serde(default = "default_as_github_api_uri")]
+    pub api_uri: Url,
+    /// Only admit members of organizations if not empty (GitHub only)
+    #[serde(default)]
+    pub organizations: Vec<String>,
+    /// Create user on first login if provider returns verified email address, issuer is required
+    /// for providers other than GitHub, which returns the verified primary email address
+    #[serde(default)]
+    pub auto_provision: bool,
+    /// Email domains allowed for automatic provisioning (all if empty)
+    #[serde(default)]
+    pub auto_provision_domains: Vec<String>,
+    /// Email domains routed to provider at login, password login is disabled for these domains
+    #[serde(default)]
+    pub home_realm_domains: Vec<String>,
+}
+
+/// MFA Configuration
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ConfigMfa {
+    /// Key used to encrypt user factor secrets (up to 32 characters), TOTP and recovery codes
+    /// are unavailable if empty
+    #[serde(default)]
+    pub key: String,
+    /// Issuer name shown in authenticator apps
+    #[serde(default = "default_as_sso")]
+    pub totp_issuer: String,
+}
+
+/// Password Configuration
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ConfigPassword {
+    /// Argon2id memory cost in KiB
+    #[serde(default = "default_as_19456")]
+    pub argon2_memory_kib: u32,
+    /// Argon2id number of iterations
+    #[serde(default = "default_as_2")]
+    pub argon2_iterations: u32,
+    /// Argon2id degree of parallelism
+    #[serde(default = "default_as_1")]
+    pub argon2_parallelism: u32,
+    /// Breached password dataset directory in k-anonymity range format, check is disabled if not set
+    pub pwned_path: Option<PathBuf>,
+    /// Require users to update password at login if it appears in breached password dataset
+    #[serde(default)]
+    pub pwned_require_update: bool,
+    /// Minimum length of new passwords, must be within validation limits of 8-64 and not
+    /// greater than maximum length
+    #[serde(default = "default_as_8")]
+    pub length_min: usize,
+    /// Maximum length of new passwords, must be within validation limits of 8-64
+    #[serde(default = "default_as_64")]
+    pub length_max: usize,
+    /// Minimum zxcvbn strength score (0-4) of new passwords
+    #[serde(default)]
+    pub score_min: u8,
+    /// Words that new passwords must not contain, in addition to user email address and name
+    #[serde(default)]
+    pub banned_words: Vec<String>,
+    /// Number of most recent passwords, including the current password, that new passwords
+    /// must not match, history is disabled if zero
+    #[serde(default)]
+    pub history_count: usize,
+    /// Maximum password age in days, users must update password at login after this
+    /// time, expiry is disabled if zero
+    #[serde(default)]
+    pub max_age_days: i64,
+    /// Number of days before password expiry that users are sent a warning email at login
+    #[serde(default = "default_as_7")]
+    pub expiry_warning_days: i64,
+}
+
+/// Lockout Configuration
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ConfigLockout {
+    /// Number of failed logins for an email address before it is locked, disabled if zero
+    #[serde(default)]
+    pub email_failures: i32,
+    /// Number of failed logins from a client IP address before it is locked, disabled if zero
+    #[serde(default)]
+    pub address_failures: i32,
+    /// Number of seconds of first lockout, doubled for each further failed login
+    #[serde(default = "default_as_60")]
+    pub lockout_s: i64,
+    /// Maximum number of seconds of lockout
+    #[serde(default = "default_as_3600")]
+    pub lockout_max_s: i64,
+    /// Number of seconds without a failed login after which failures are reset
+    #[serde(default = "default_as_3600")]
+    pub reset_s: i64,
+}
+
+/// Session Configuration
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ConfigSession {
+    /// Number of seconds without a request after which sessions expire
+    #[serde(default = "default_as_604800")]
+    pub idle_s: i64,
+    /// Number of seconds after login after which sessions expire
+    #[serde(default = "default_as_2592000")]
+    pub lifetime_s: i64,
+    /// Number of seconds without a login after which remembered devices are forgotten
+    #[serde(default = "default_as_7776000")]
+    pub device_s: i64,
+}
+
+/// WebAuthn Configuration
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ConfigWebauthn {
+    /// Relying party name shown by authenticators, ID and origin are taken from OAuth2 domain
+    #[serde(default = "default_as_sso")]
+    pub rp_name: String,
 }
 
 /// Configuration
@@ -207,9 +368,28 @@ pub struct Config {
     /// Mailto configuration
     #[serde(default)]
     pub mailto: mailto::Config,
+    /// LDAP configuration
+    pub ldap: Option<ldap::Config>,
+    /// Password configuration
+    #[serde(default)]
+    pub password: ConfigPassword,
+    /// Lockout configuration
+    #[serde(default)]
+    pub lockout: ConfigLockout,
+    /// Session configuration
+    #[serde(default)]
+    pub session: ConfigSession,
+    /// MFA configuration
+    #[serde(default)]
+    pub mfa: ConfigMfa,
+    /// WebAuthn configuration, passkeys are unavailable if not set
+    pub webauthn: Option<ConfigWebauthn>,
     /// Metrics configuration
     #[serde(default)]
     pub metrics: metrics::Config,
+    /// Rate limit configuration
+    #[serde(default)]
+    pub rate_limit: rate_limit::Config,
 }
 
 /// Parse configuration from environment variables
@@ -260,7 +440,9 @@ impl Config {
 
         cfg.merge(config::Environment::with_prefix(util::NAME).separator("_"))?;
 
-        cfg.try_into().map_err(Error::ConfigError)
+        let config: Self = cfg.try_into().map_err(Error::ConfigError)?;
+        config.password.validate()?;
+        Ok(config)
     }
 
     /// Load configuration template files
@@ -306,6 +488,76 @@ impl ConfigHttpCookie {
     }
 }
 
+impl Default for ConfigMfa {
+    fn default() -> Self {
+        Self {
+            key: String::new(),
+            totp_issuer: default_as_sso(),
+        }
+    }
+}
+
+impl Default for ConfigPassword {
+    fn default() -> Self {
+        Self {
+            argon2_memory_kib: default_as_19456(),
+            argon2_iterations: default_as_2(),
+            argon2_parallelism: default_as_1(),
+            pwned_path: None,
+            pwned_require_update: false,
+            length_min: default_as_8(),
+            length_max: default_as_64(),
+            score_min: 0,
+            banned_words: Vec::new(),
+            history_count: 0,
+            max_age_days: 0,
+            expiry_warning_days: default_as_7(),
+        }
+    }
+}
+
+impl ConfigPassword {
+    /// Returns error if password configuration is outside of validation limits
+    pub fn validate(&self) -> Result<()> {
+        if self.length_min < validate::PASSWORD_MIN
+            || self.length_max > validate::PASSWORD_MAX
+            || self.length_min > self.length_max
+        {
+            return Err(Error::from(format!(
+                "password length_min and length_max must be within {}-{}",
+                validate::PASSWORD_MIN,
+                validate::PASSWORD_MAX
+            )));
+        }
+        if self.score_min > 4 {
+            return Err(Error::from("password score_min must be within 0-4"));
+        }
+        Ok(())
+    }
+}
+
+impl Default for ConfigLockout {
+    fn default() -> Self {
+        Self {
+            email_failures: 0,
+            address_failures: 0,
+            lockout_s: default_as_60(),
+            lockout_max_s: default_as_3600(),
+            reset_s: default_as_3600(),
+        }
+    }
+}
+
+impl Default for ConfigSession {
+    fn default() -> Self {
+        Self {
+            idle_s: default_as_604800(),
+            lifetime_s: default_as_2592000(),
+            device_s: default_as_7776000(),
+        }
+    }
+}
+
 impl Default for ConfigOauth2ClientTtl {
     fn default() -> Self {
         Self {
@@ -314,6 +566,31 @@ impl Default for ConfigOauth2ClientTtl {
             oauth2_code_s: 3600,
             token_access_s: 3600,
             token_refresh_s: 86400,
+            email_revert_s: 604800,
+            invite_s: 604800,
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_password_validate() {
+        let mut config = ConfigPassword::default();
+        assert!(config.validate().is_ok());
+
+        config.length_min = 16;
+        config.length_max = 12;
+        assert!(config.validate().is_err());
+
+        config.length_min = 4;
+        config.length_max = 64;
+        assert!(config.validate().is_err());
+
+        config.length_min = 8;
+        config.length_max = 128;
+        assert!(config.validate().is_err());
+    }
+}
@@ -0,0 +1,54 @@
+//! # Device
+//!
+//! Devices are identified by a fingerprint of the user agent and the network of the client
+//! IP address, so that a known device is still recognised when its address changes within
+//! the same network. Fingerprints are hashed before they are stored.
+use crate::internal::*;
+use std::net::IpAddr;
+
+/// Returns network of remote address, IPv4 addresses are truncated to /24 and IPv6
+/// addresses to /48, unknown addresses are returned unchanged
+pub fn network(remote: &str) -> String {
+    match IpAddr::from_str(&lockout::address(remote)) {
+        Ok(IpAddr::V4(addr)) => {
+            let o = addr.octets();
+            format!("{}.{}.{}.0/24", o[0], o[1], o[2])
+        }
+        Ok(IpAddr::V6(addr)) => {
+            let s = addr.segments();
+            format!("{:x}:{:x}:{:x}::/48", s[0], s[1], s[2])
+        }
+        Err(_e) => remote.to_string(),
+    }
+}
+
+/// Returns device fingerprint of user agent and remote address
+pub fn fingerprint(user_agent: &str, remote: &str) -> String {
+    format!("{} {}", network(remote), user_agent)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_network() {
+        assert_eq!(network("192.168.1.1:1234"), "192.168.1.0/24");
+        assert_eq!(network("192.168.1.254"), "192.168.1.0/24");
+        assert_eq!(network("[2001:db8:1:2::1]:1234"), "2001:db8:1::/48");
+        assert_eq!(network("2001:db8:1:ffff::1"), "2001:db8:1::/48");
+        assert_eq!(network("unknown"), "unknown");
+    }
+
+    #[test]
+    fn test_fingerprint() {
+        assert_eq!(
+            fingerprint("Mozilla/5.0", "10.0.0.1:80"),
+            fingerprint("Mozilla/5.0", "10.0.0.2:443")
+        );
+        assert_ne!(
+            fingerprint("Mozilla/5.0", "10.0.0.1:80"),
+            fingerprint("curl/7.68.0", "10.0.0.1:80")
+        );
+    }
+}
@@ -27,6 +27,10 @@ pub enum Error {
     LettreEmail(lettre_email::error::Error),
     /// validator::ValidationErrors
     Validation(validator::ValidationErrors),
+    /// jsonwebtoken::errors::Error
+    Jsonwebtoken(jsonwebtoken::errors::Error),
+    /// ldap3::LdapError
+    Ldap(ldap3::LdapError),
 }
 
 /// Result
@@ -47,6 +51,8 @@ impl std::string::ToString for Error {
             Self::Lettre(e) => e.to_string(),
             Self::LettreEmail(e) => e.to_string(),
             Self::Validation(e) => e.to_string(),
+            Self::Jsonwebtoken(e) => e.to_string(),
+            Self::Ldap(e) => e.to_string(),
         }
     }
 }
@@ -134,3 +140,15 @@ impl From<validator::ValidationErrors> for Error {
         Self::Validation(e)
     }
 }
+
+impl From<jsonwebtoken::errors::Error> for Error {
+    fn from(e: jsonwebtoken::errors::Error) -> Self {
+        Self::Jsonwebtoken(e)
+    }
+}
+
+impl From<ldap3::LdapError> for Error {
+    fn from(e: ldap3::LdapError) -> Self {
+        Self::Ldap(e)
+    }
+}
@@ -0,0 +1,212 @@
+//! # GitHub
+//!
+//! [API](https://docs.github.com/en/rest)
+use crate::internal::*;
+
+/// Number of results requested per page, this is the maximum supported by API
+const PER_PAGE: usize = 100;
+
+/// GitHub User
+#[derive(Debug, Clone)]
+pub struct User {
+    /// User ID
+    pub id: i64,
+    /// Login name
+    pub login: String,
+    /// Display name
+    pub name: Option<String>,
+    /// Primary email address if verified
+    pub email: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct ResponseUser {
+    id: i64,
+    login: String,
+    name: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct ResponseEmail {
+    email: String,
+    primary: bool,
+    verified: bool,
+}
+
+#[derive(Debug, Deserialize)]
+struct ResponseMembership {
+    state: String,
+}
+
+/// Returns API URI of path, path is appended to API URI so that GitHub Enterprise
+/// API URIs with a path prefix such as `https://github.example.com/api/v3` work
+pub fn api_uri(api_uri: &Url, path: &str) -> Url {
+    let mut uri = api_uri.clone();
+    let prefix = api_uri.path().trim_end_matches('/');
+    uri.set_path(&format!("{}{}", prefix, path));
+    uri
+}
+
+/// Read authenticated user and their verified primary email address
+pub async fn user(client: &reqwest::Client, api_uri: &Url, token: &str) -> Result<User> {
+    let user: ResponseUser = get(client, self::api_uri(api_uri, "/user"), token)
+        .await?
+        .json()
+        .await?;
+
+    // Emails are paginated, read pages until a page is not full
+    let mut email = None;
+    let mut page = 1;
+    while email.is_none() {
+        let mut uri = self::api_uri(api_uri, "/user/emails");
+        uri.query_pairs_mut()
+            .append_pair("per_page", &PER_PAGE.to_string())
+            .append_pair("page", &page.to_string());
+        let emails: Vec<ResponseEmail> = get(client, uri, token).await?.json().await?;
+
+        let last = emails.len() < PER_PAGE;
+        email = emails
+            .into_iter()
+            .find(|x| x.primary && x.verified)
+            .map(|x| x.email);
+        if last {
+            break;
+        }
+        page += 1;
+    }
+
+    Ok(User {
+        id: user.id,
+        login: user.login,
+        name: user.name,
+        email,
+    })
+}
+
+/// Returns true if authenticated user is an active member of organization
+pub async fn organization_member(
+    client: &reqwest::Client,
+    api_uri: &Url,
+    token: &str,
+    organization: &str,
+) -> Result<bool> {
+    let uri = self::api_uri(api_uri, &format!("/user/memberships/orgs/{}", organization));
+    let res = get(client, uri, token).await;
+
+    // API returns not found if user is not a member of organization
+    let res = match res {
+        Err(Error::ReqwestError(e)) if e.status() == Some(reqwest::StatusCode::NOT_FOUND) => {
+            return Ok(false)
+        }
+        res => res?,
+    };
+    let membership: ResponseMembership = res.json().await?;
+    Ok(membership.state == "active")
+}
+
+async fn get(client: &reqwest::Client, uri: Url, token: &str) -> Result<reqwest::Response> {
+    let authorisation = format!("token {}", token);
+    let res = client
+        .get(uri)
+        .header(http::header::AUTHORIZATION, authorisation)
+        .header(http::header::ACCEPT, "application/vnd.github.v3+json")
+        .send()
+        .await?
+        .error_for_status()?;
+    Ok(res)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use actix_web::{web, App, HttpResponse};
+
+    #[derive(Debug, Deserialize)]
+    struct Page {
+        page: usize,
+        per_page: usize,
+    }
+
+    async fn mock_user() -> HttpResponse {
+        HttpResponse::Ok().json(json!({ "id": 1, "login": "octocat", "name": null }))
+    }
+
+    async fn mock_emails(page: web::Query<Page>) -> HttpResponse {
+        // Primary email is on second page
+        let emails: Vec<Value> = (0..page.per_page)
+            .map(|i| {
+                json!({
+                    "email": format!("{}-{}@example.com", page.page, i),
+                    "primary": page.page == 2 && i == 0,
+                    "verified": true,
+                })
+            })
+            .collect();
+        HttpResponse::Ok().json(emails)
+    }
+
+    async fn mock_membership(org: web::Path<String>) -> HttpResponse {
+        match org.as_str() {
+            "active" => HttpResponse::Ok().json(json!({ "state": "active" })),
+            "pending" => HttpResponse::Ok().json(json!({ "state": "pending" })),
+            _ => HttpResponse::NotFound().json(json!({ "message": "Not Found" })),
+        }
+    }
+
+    fn mock_server() -> actix_web::test::TestServer {
+        actix_web::test::start(|| {
+            App::new().service(
+                web::scope("/api/v3")
+                    .route("/user", web::get().to(mock_user))
+                    .route("/user/emails", web::get().to(mock_emails))
+                    .route(
+                        "/user/memberships/orgs/{org}",
+                        web::get().to(mock_membership),
+                    ),
+            )
+        })
+    }
+
+    #[test]
+    fn test_api_uri() {
+        let uri = Url::parse("https://api.github.com").unwrap();
+        assert_eq!(
+            api_uri(&uri, "/user").as_str(),
+            "https://api.github.com/user"
+        );
+        let uri = Url::parse("https://github.example.com/api/v3/").unwrap();
+        assert_eq!(
+            api_uri(&uri, "/user/emails").as_str(),
+            "https://github.example.com/api/v3/user/emails"
+        );
+    }
+
+    #[actix_rt::test]
+    async fn test_user_emails_paginated() {
+        let server = mock_server();
+        let client = reqwest::Client::new();
+        let uri = Url::parse(&server.url("/api/v3")).unwrap();
+
+        let user = user(&client, &uri, "token").await.unwrap();
+        assert_eq!(user.id, 1);
+        assert_eq!(user.login, "octocat");
+        assert_eq!(user.email.as_deref(), Some("2-0@example.com"));
+    }
+
+    #[actix_rt::test]
+    async fn test_organization_member() {
+        let server = mock_server();
+        let client = reqwest::Client::new();
+        let uri = Url::parse(&server.url("/api/v3")).unwrap();
+
+        assert!(organization_member(&client, &uri, "token", "active")
+            .await
+            .unwrap());
+        assert!(!organization_member(&client, &uri, "token", "pending")
+            .await
+            .unwrap());
+        assert!(!organization_member(&client, &uri, "token", "other")
+            .await
+            .unwrap());
+    }
+}
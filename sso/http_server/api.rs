@@ -50,6 +50,8 @@ impl ServerApi {
 
     fn public_v2_auth() -> web::Scope {
         web::scope("/auth")
+            .route("/account", web::get().to(route_auth::account::get))
+            .route("/account", web::post().to(route_auth::account::post))
             .route(
                 "/password-reset",
                 web::get().to(route_auth::password_reset::get),
@@ -74,11 +76,29 @@ impl ServerApi {
                 "/email-update",
                 web::post().to(route_auth::email_update::post),
             )
+            .route(
+                "/email-verify",
+                web::get().to(route_auth::email_verify::get),
+            )
+            .route(
+                "/email-verify",
+                web::post().to(route_auth::email_verify::post),
+            )
+            .route("/link", web::get().to(route_auth::link::get))
+            .route("/link", web::post().to(route_auth::link::post))
             .route("/logout", web::get().to(route_auth::logout::get))
+            .route("/magic-link", web::get().to(route_auth::magic_link::get))
+            .route("/magic-link", web::post().to(route_auth::magic_link::post))
             .route("/register", web::get().to(route_auth::register::get))
             .route("/register", web::post().to(route_auth::register::post))
             .route("/delete", web::get().to(route_auth::delete::get))
             .route("/delete", web::post().to(route_auth::delete::post))
+            .route("/totp", web::get().to(route_auth::totp::get))
+            .route("/totp", web::post().to(route_auth::totp::post))
+            .route("/recovery", web::get().to(route_auth::recovery::get))
+            .route("/recovery", web::post().to(route_auth::recovery::post))
+            .route("/webauthn", web::get().to(route_auth::webauthn::get))
+            .route("/webauthn", web::post().to(route_auth::webauthn::post))
     }
 
     fn public_v2_audit() -> web::Scope {
@@ -110,6 +130,8 @@ impl ServerApi {
             .route("/read", web::post().to(route_user::read::post))
             .route("/update", web::post().to(route_user::update::post))
             .route("/delete", web::post().to(route_user::delete::post))
+            .route("/unlock", web::post().to(route_user::unlock::post))
+            .route("/invite", web::post().to(route_user::invite::post))
             .service(
                 web::scope("/access")
                     .route("/read", web::post().to(route_user_access::read::post))
@@ -124,6 +146,19 @@ impl ServerApi {
                     .route("/delete", web::post().to(route_user_api_key::delete::post))
                     .route("/verify", web::post().to(route_user_api_key::verify::post)),
             )
+            .service(
+                web::scope("/register")
+                    .route(
+                        "/approve",
+                        web::post().to(route_user_register::approve::post),
+                    )
+                    .route("/deny", web::post().to(route_user_register::deny::post)),
+            )
+            .service(
+                web::scope("/session")
+                    .route("/read", web::post().to(route_user_session::read::post))
+                    .route("/delete", web::post().to(route_user_session::delete::post)),
+            )
     }
 
     fn public_v2() -> web::Scope {
@@ -143,6 +178,7 @@ impl ServerApi {
                 "/password_strength.js",
                 web::get().to(route_script::password_strength_get),
             )
+            .route("/webauthn.js", web::get().to(route_script::webauthn_get))
     }
 
     fn public_well_known() -> web::Scope {
@@ -7,7 +7,13 @@ pub(crate) use paperclip::actix::{
     web::{Data, Form, HttpRequest, HttpResponse, Json, Query},
 };
 
-use std::time::SystemTime;
+use std::{future::Future, time::SystemTime};
+
+/// Error returned by password login for unknown email, disabled user and incorrect password
+const LOGIN_ERROR: &str = "email or password is incorrect";
+
+/// Number of audit logs shown on account page
+const ACCOUNT_AUDIT_LIMIT: i64 = 20;
 
 #[derive(Debug, Clone)]
 pub(crate) struct UserLoginArgs {
@@ -15,6 +21,13 @@ pub(crate) struct UserLoginArgs {
     pub password: String,
 }
 
+#[derive(Debug, Clone)]
+pub(crate) struct UserAccountUpdateArgs {
+    pub name: String,
+    pub locale: String,
+    pub timezone: String,
+}
+
 #[derive(Debug, Clone)]
 pub(crate) struct UserRegisterAcceptArgs {
     pub name: String,
@@ -28,6 +41,23 @@ pub(crate) struct UserRegisterPasswordArgs {
     pub password_confirm: String,
 }
 
+#[derive(Debug, Clone)]
+pub(crate) struct UserWebauthnRegisterArgs {
+    pub name: String,
+    pub challenge: String,
+    pub client_data: String,
+    pub attestation_object: String,
+}
+
+#[derive(Debug, Clone)]
+pub(crate) struct UserWebauthnLoginArgs {
+    pub challenge: String,
+    pub credential_id: String,
+    pub client_data: String,
+    pub authenticator_data: String,
+    pub signature: String,
+}
+
 #[derive(Debug, Clone)]
 pub(crate) struct ServerRequest {
     pub time: SystemTime,
@@ -168,26 +198,149 @@ impl HttpServer {
             .unwrap()
     }
 
-    pub(crate) async fn request_identity_required(
+    /// Returns session of request identity, identity is forgotten if session does not exist,
+    /// has been revoked or user is disabled
+    pub(crate) async fn request_session(
         &self,
-        audit: &mut Audit,
         req: &actix_web::HttpRequest,
-    ) -> oauth2::Result<Uuid> {
+    ) -> oauth2::Result<Option<PostgresSession>> {
         let ident = self.request_identity(req).await;
-        match ident.identity() {
-            Some(id) => match Uuid::parse_str(&id) {
-                Ok(id) => {
-                    audit.set_user_id(id);
-                    Ok(id)
-                }
-                Err(_e) => Err(oauth2::ErrorResponse::invalid_request("user_id is invalid")),
-            },
+        let value = match ident.identity() {
+            Some(value) => value,
+            None => return Ok(None),
+        };
+
+        let session = self
+            .postgres
+            .session_verify(&value)
+            .await
+            .map_err(oauth2::ErrorResponse::server_error)?;
+        if session.is_none() {
+            ident.forget();
+        }
+        Ok(session)
+    }
+
+    pub(crate) async fn request_session_required(
+        &self,
+        audit: &mut Audit,
+        req: &actix_web::HttpRequest,
+    ) -> oauth2::Result<PostgresSession> {
+        match self.request_session(req).await? {
+            Some(session) => {
+                audit.set_user_id(session.user_id);
+                Ok(session)
+            }
             None => Err(oauth2::ErrorResponse::invalid_request(
                 "authentication required",
             )),
         }
     }
 
+    pub(crate) async fn request_identity_required(
+        &self,
+        audit: &mut Audit,
+        req: &actix_web::HttpRequest,
+    ) -> oauth2::Result<Uuid> {
+        self.request_session_required(audit, req)
+            .await
+            .map(|x| x.user_id)
+    }
+
+    /// Create session for user and remember it in request identity, existing session of
+    /// request is deleted
+    pub(crate) async fn session_login(
+        &self,
+        audit: &mut Audit,
+        req: &actix_web::HttpRequest,
+        user_id: Uuid,
+        methods: Vec<String>,
+    ) -> oauth2::Result<()> {
+        self.session_logout(req).await?;
+
+        let value = self
+            .postgres
+            .session_create(audit, user_id, &methods)
+            .await
+            .map_err(oauth2::ErrorResponse::server_error)?;
+        audit.set_data("session_methods", json!(methods));
+
+        let ident = self.request_identity(req).await;
+        ident.remember(value);
+        Ok(())
+    }
+
+    /// Remember device of user login, user is sent an email if device is not known and
+    /// client has new device notifications enabled, mail errors are audited and do not
+    /// prevent login
+    pub(crate) async fn user_device_login(
+        &self,
+        audit: &mut Audit,
+        client: &Client,
+        user_id: Uuid,
+    ) -> oauth2::Result<()> {
+        let address = lockout::client_address(
+            audit.remote_addr(),
+            audit.forwarded_for(),
+            &self.config.http.trusted_proxies,
+        );
+        let fingerprint = device::fingerprint(
+            audit.user_agent().unwrap_or_default(),
+            address.as_deref().unwrap_or_default(),
+        );
+        let new = self
+            .postgres
+            .user_device_login(user_id, &fingerprint)
+            .await
+            .map_err(oauth2::ErrorResponse::server_error)?;
+        if !new {
+            return Ok(());
+        }
+        audit.set_data("device_new", json!(true));
+        if !client.device_notify_enable {
+            return Ok(());
+        }
+
+        let user = self.user_read_id(client, user_id).await?;
+        let text = self.mail_template(
+            client,
+            TEMPLATE_MAIL_DEVICE_NEW,
+            &json!({
+                "user_email": user.email,
+                "uri": self.uri_auth_account(client),
+                "client_name": client.client_name,
+                "client_uri": client.client_uri,
+                "audit": audit.template,
+            }),
+        )?;
+        let res = self
+            .mailto
+            .send(self.mailto.build(&user.email, "New Device Login", &text))
+            .await;
+        match res {
+            Ok(_) => audit.set_data("device_notified", json!(true)),
+            Err(e) => {
+                warn!("new device notification failed: {}", e.to_string());
+                audit.set_data("device_notify_error", json!(e.to_string()));
+            }
+        }
+        Ok(())
+    }
+
+    /// Delete session of request and forget request identity
+    pub(crate) async fn session_logout(&self, req: &actix_web::HttpRequest) -> oauth2::Result<()> {
+        if let Some(session) = self.request_session(req).await? {
+            self.postgres
+                .session_delete(session.user_id, Some(session.id))
+                .await
+                .map_err(oauth2::ErrorResponse::server_error)?;
+        }
+
+        let ident = self.request_identity(req).await;
+        ident.forget();
+        Ok(())
+    }
+
     /// Redirect using URI
     pub(crate) fn response_redirect(&self, uri: Url) -> actix_web::HttpResponse {
         actix_web::HttpResponse::Found()
@@ -259,12 +412,13 @@ impl HttpServer {
     }
 
     pub(crate) fn template_context(&self, client: &Client) -> Context {
-        Context::build(&self.config.oauth2.providers, client)
+        Context::build(&self.config.oauth2.providers, &self.config.password, client)
     }
 
     pub(crate) async fn template_csrf_context(&self, client: &Client) -> oauth2::Result<Context> {
         self.csrf_token(client).await.map(|token| {
-            let mut context = Context::build(&self.config.oauth2.providers, client);
+            let mut context =
+                Context::build(&self.config.oauth2.providers, &self.config.password, client);
             context.csrf_token(token);
             context
         })
@@ -276,7 +430,8 @@ impl HttpServer {
         message: Option<String>,
     ) -> oauth2::Result<Context> {
         self.csrf_token(client).await.map(|token| {
-            let mut context = Context::build(&self.config.oauth2.providers, client);
+            let mut context =
+                Context::build(&self.config.oauth2.providers, &self.config.password, client);
             context.csrf_token(token);
             if let Some(message) = message {
                 context.message(message);
@@ -291,19 +446,255 @@ impl HttpServer {
         e: (i64, oauth2::ErrorResponse),
     ) -> oauth2::Result<Context> {
         self.csrf_token(client).await.map(|token| {
-            let mut context = Context::build(&self.config.oauth2.providers, client);
+            let mut context =
+                Context::build(&self.config.oauth2.providers, &self.config.password, client);
             context.csrf_token(token);
             context.error(e);
             context
         })
     }
 
+    pub(crate) async fn template_link_context(
+        &self,
+        client: &Client,
+        user_id: Uuid,
+    ) -> oauth2::Result<Context> {
+        let linked = self
+            .postgres
+            .user_oauth2_provider_read(user_id)
+            .await
+            .map_err(oauth2::ErrorResponse::server_error)?;
+
+        let mut context = self.template_csrf_context(client).await?;
+        context.oauth2_providers_linked(&linked);
+        Ok(context)
+    }
+
+    pub(crate) async fn template_account_context(
+        &self,
+        client: &Client,
+        session: &PostgresSession,
+    ) -> oauth2::Result<Context> {
+        let user_id = session.user_id;
+        let user = self.user_read_id(client, user_id).await?;
+        let linked = self
+            .postgres
+            .user_oauth2_provider_read(user_id)
+            .await
+            .map_err(oauth2::ErrorResponse::server_error)?;
+        let sessions = self
+            .postgres
+            .session_read(RequestUserSessionRead { user_id })
+            .await
+            .map_err(oauth2::ErrorResponse::server_error)?;
+        let access = self
+            .postgres
+            .user_access_read_many(user_id)
+            .await
+            .map_err(oauth2::ErrorResponse::server_error)?;
+        let audit = self
+            .postgres
+            .audit_read_user(user_id, ACCOUNT_AUDIT_LIMIT)
+            .await
+            .map_err(oauth2::ErrorResponse::server_error)?;
+
+        let mut account = ContextAccount::new(
+            &user,
+            self.uri_auth_link(client),
+            self.uri_auth_password_update(client, None),
+        );
+        account.sessions(&sessions.data, session.id);
+        for x in access.into_iter().filter(|x| x.enable) {
+            // Access to clients removed from configuration is not shown
+            if let Some(client) = self.config.oauth2.clients.get(&x.client_id) {
+                account.access(client.name.clone(), x.scope);
+            }
+        }
+        account.audit(&audit);
+
+        let mut context = self.template_csrf_context(client).await?;
+        context.oauth2_providers_linked(&linked);
+        context.account(account);
+        Ok(context)
+    }
+
+    pub(crate) async fn template_totp_context(
+        &self,
+        client: &Client,
+        user_id: Uuid,
+    ) -> oauth2::Result<Context> {
+        let totp = self.user_totp_enrol(user_id).await?;
+
+        let mut context = self.template_csrf_context(client).await?;
+        self.context_totp(&mut context, client, user_id, totp)
+            .await?;
+        Ok(context)
+    }
+
+    pub(crate) async fn template_mfa_context(
+        &self,
+        client: &Client,
+        user_id: Uuid,
+        code: String,
+        action: Option<Url>,
+    ) -> oauth2::Result<Context> {
+        let credentials = self.user_webauthn_credentials(user_id).await?;
+        let totp = if self.config.mfa.key.is_empty() {
+            None
+        } else {
+            self.postgres
+                .user_totp_read(&self.config.mfa.key, user_id)
+                .await
+                .map_err(oauth2::ErrorResponse::server_error)?
+        };
+
+        let mut context = self.template_csrf_context(client).await?;
+        match totp {
+            Some(totp) if totp.enable => context.totp(true),
+            // Users without a factor are enrolled here when client requires MFA
+            _ if credentials.is_empty() => {
+                let totp = self.user_totp_enrol(user_id).await?;
+                self.context_totp(&mut context, client, user_id, totp)
+                    .await?;
+            }
+            _ => {}
+        }
+        if !credentials.is_empty() {
+            self.context_webauthn_login(&mut context, client, Some(user_id), &credentials)
+                .await?;
+        }
+        let recovery = self.user_recovery_count(user_id).await? > 0;
+        context.mfa(code, action, recovery);
+        Ok(context)
+    }
+
+    pub(crate) async fn template_recovery_context(
+        &self,
+        client: &Client,
+        user_id: Uuid,
+    ) -> oauth2::Result<Context> {
+        let count = self.user_recovery_count(user_id).await?;
+
+        let mut context = self.template_csrf_context(client).await?;
+        context.recovery_count(count);
+        Ok(context)
+    }
+
+    pub(crate) async fn template_auth_context(&self, client: &Client) -> oauth2::Result<Context> {
+        let mut context = self.template_csrf_context(client).await?;
+        if self.webauthn.is_some() {
+            self.context_webauthn_login(&mut context, client, None, &[])
+                .await?;
+        }
+        Ok(context)
+    }
+
+    pub(crate) async fn template_webauthn_context(
+        &self,
+        client: &Client,
+        user_id: Uuid,
+    ) -> oauth2::Result<Context> {
+        let rp = self.webauthn()?;
+        let user = self.user_read_id(client, user_id).await?;
+        let credentials = self.user_webauthn_credentials(user_id).await?;
+        let challenge = self
+            .postgres
+            .webauthn_challenge_insert(client, Some(user_id), PostgresWebauthnTarget::Register)
+            .await
+            .map_err(oauth2::ErrorResponse::server_error)?;
+
+        let mut context = self.template_csrf_context(client).await?;
+        context.webauthn(
+            json!({
+                "challenge": challenge,
+                "rp_id": rp.id(),
+                "rp_name": rp.name(),
+                "user_id": webauthn::base64url_encode(user.id.as_bytes()),
+                "user_name": user.email,
+                "user_display_name": user.name,
+                "credentials": credentials.iter().map(|x| &x.credential_id).collect::<Vec<_>>(),
+            }),
+            &credentials,
+        );
+        Ok(context)
+    }
+
+    async fn context_totp(
+        &self,
+        context: &mut Context,
+        client: &Client,
+        user_id: Uuid,
+        totp: PostgresUserTotp,
+    ) -> oauth2::Result<()> {
+        context.totp(totp.enable);
+        if !totp.enable {
+            let user = self.user_read_id(client, user_id).await?;
+
+            let uri = totp::uri(&self.config.mfa.totp_issuer, &user.email, &totp.secret);
+            let qr = totp::uri_qr_svg(&uri).map_err(oauth2::ErrorResponse::server_error)?;
+            context.totp_enrol(uri, totp.secret, qr);
+        }
+        Ok(())
+    }
+
+    async fn context_webauthn_login(
+        &self,
+        context: &mut Context,
+        client: &Client,
+        user_id: Option<Uuid>,
+        credentials: &[PostgresUserWebauthn],
+    ) -> oauth2::Result<()> {
+        let rp = self.webauthn()?;
+        let challenge = self
+            .postgres
+            .webauthn_challenge_insert(client, user_id, PostgresWebauthnTarget::Login)
+            .await
+            .map_err(oauth2::ErrorResponse::server_error)?;
+
+        // Passkey login without a known user relies on discoverable credentials,
+        // user verification is required so the passkey is not only a single factor
+        let user_verification = if user_id.is_some() {
+            "preferred"
+        } else {
+            "required"
+        };
+        context.webauthn(
+            json!({
+                "challenge": challenge,
+                "rp_id": rp.id(),
+                "credentials": credentials.iter().map(|x| &x.credential_id).collect::<Vec<_>>(),
+                "user_verification": user_verification,
+            }),
+            &[],
+        );
+        Ok(())
+    }
+
+    async fn user_read_id(&self, client: &Client, user_id: Uuid) -> oauth2::Result<ResponseUser> {
+        let mut user = self
+            .postgres
+            .user_read(
+                client,
+                RequestUserRead {
+                    id: Some(vec![user_id]),
+                    email: None,
+                },
+            )
+            .await
+            .map_err(oauth2::ErrorResponse::server_error)?;
+        if user.data.is_empty() {
+            return Err(oauth2::ErrorResponse::invalid_request("user not found"));
+        }
+        Ok(user.data.remove(0))
+    }
+
     pub(crate) fn template_error_context(
         &self,
         client: &Client,
         e: (i64, oauth2::ErrorResponse),
     ) -> Context {
-        let mut context = Context::build(&self.config.oauth2.providers, client);
+        let mut context =
+            Context::build(&self.config.oauth2.providers, &self.config.password, client);
         context.error(e);
         context
     }
@@ -380,6 +771,14 @@ impl HttpServer {
                 user_scope: oauth2::Scope::from_ref(&config.user_scope),
                 register_enable: config.register_enable,
                 register_scope: oauth2::Scope::from_ref(&config.register_scope),
+                register_approval: config.register_approval,
+                register_approval_email: config.register_approval_email.clone(),
+                register_approval_webhook_uri: config.register_approval_webhook_uri.clone(),
+                require_mfa: config.require_mfa,
+                require_email_verified: config.require_email_verified,
+                magic_link_enable: config.magic_link_enable,
+                device_notify_enable: config.device_notify_enable,
+                password_max_age_days: config.password_max_age_days,
                 ttl: config.ttl.clone(),
                 templates: config.templates.clone(),
             })
@@ -558,53 +957,1082 @@ impl HttpServer {
 pub enum LoginAction {
     Login,
     RequireUpdate,
+    RequireMfa(String),
+}
+
+/// Returns authentication methods of login using second factor, method of first factor is
+/// stored in state of MFA code
+fn login_mfa_methods(code: &PostgresCode, method: &str) -> Vec<String> {
+    let mut methods: Vec<String> = code.state.split_whitespace().map(String::from).collect();
+    methods.push(method.to_string());
+    methods
 }
 
 impl HttpServer {
     pub(crate) async fn user_password_login(
         &self,
-        _audit: &mut Audit,
+        audit: &mut Audit,
+        client: &Client,
         args: UserLoginArgs,
-    ) -> oauth2::Result<(String, LoginAction)> {
-        let check = self
+    ) -> oauth2::Result<(Uuid, LoginAction)> {
+        if self.oauth2_provider_home_realm(&args.email).is_some() {
+            return Err(oauth2::ErrorResponse::access_denied(
+                "password login is disabled for email domain",
+            ));
+        }
+
+        self.login_lockout_check(audit, &args.email).await?;
+
+        let mut ldap_unavailable = false;
+        if let Some(ldap) = &self.ldap {
+            let user = match ldap.authenticate(&args.email, &args.password).await {
+                Ok(ldap::Authenticate::User(user)) => Some(user),
+                Ok(ldap::Authenticate::NotFound) => None,
+                Ok(ldap::Authenticate::PasswordIncorrect) => {
+                    self.login_lockout_failure(audit, &args.email).await?;
+                    audit.set_data("login_error", json!("password is incorrect"));
+                    return Err(oauth2::ErrorResponse::access_denied(LOGIN_ERROR));
+                }
+                Err(e) => {
+                    // Directory errors fall through to local password check so that local
+                    // and static users can log in while the directory is unavailable
+                    warn!("ldap authenticate failed: {}", e.to_string());
+                    audit.set_data("ldap_error", json!(e.to_string()));
+                    ldap_unavailable = true;
+                    None
+                }
+            };
+
+            if let Some(user) = user {
+                // Mapped scope is limited to scope available to users of client
+                let scope = ldap.groups_scope(&user).map(|x| {
+                    oauth2::Scope::from(
+                        x.into_inner()
+                            .into_iter()
+                            .filter(|x| client.user_scope.as_ref().contains(x))
+                            .collect::<Vec<String>>(),
+                    )
+                });
+
+                let user_id = self
+                    .postgres
+                    .user_upsert_ldap(client, &user.email, &user.name, scope)
+                    .await
+                    .map_err(oauth2::ErrorResponse::access_denied)?;
+
+                audit.set_user_id(user_id);
+                audit.set_data("ldap", json!(user.dn));
+                let action = self
+                    .user_login_action(client, user_id, false, "password")
+                    .await?;
+                self.login_lockout_action(&args.email, &action).await?;
+                return Ok((user_id, action));
+            }
+        }
+
+        let check = match self
             .postgres
             .user_password_check(&args.email, &args.password)
             .await
-            .map_err(oauth2::ErrorResponse::access_denied)?;
+        {
+            Ok(check) => check,
+            Err(e) if ldap_unavailable => {
+                // User may exist in directory so failure is not counted for lockout
+                audit.set_data("login_error", json!(e.to_string()));
+                return Err(oauth2::ErrorResponse::server_error(
+                    "directory is unavailable, try again later",
+                ));
+            }
+            Err(e) => {
+                self.login_lockout_failure(audit, &args.email).await?;
+                // Unknown email, disabled user and incorrect password return the same error
+                // so that responses do not reveal which users exist, reason is audited
+                audit.set_data("login_error", json!(e.to_string()));
+                return Err(oauth2::ErrorResponse::access_denied(LOGIN_ERROR));
+            }
+        };
 
-        if check.require_update {
-            Ok((check.id.to_string(), LoginAction::RequireUpdate))
-        } else {
-            Ok((check.id.to_string(), LoginAction::Login))
+        let mut require_update = check.require_update;
+        if !require_update && !check.static_ {
+            if let Some(expires_at) = self.password_expires_at(client, check.hash_updated_at) {
+                let now = Utc::now();
+                let warn_at =
+                    expires_at - chrono::Duration::days(self.config.password.expiry_warning_days);
+                // Warning is sent once per password
+                let warned = check
+                    .expire_warned_at
+                    .map(|x| x >= check.hash_updated_at)
+                    .unwrap_or(false);
+
+                if now >= expires_at {
+                    audit.set_data("password_expired", json!(true));
+                    require_update = true;
+                } else if now >= warn_at && !warned {
+                    self.user_password_expiry_warn(
+                        audit,
+                        client,
+                        check.id,
+                        &args.email,
+                        expires_at,
+                    )
+                    .await?;
+                }
+            }
+        }
+        if !require_update && !check.static_ && self.config.password.pwned_require_update {
+            let pwned = self
+                .password_pwned(&args.password)
+                .await
+                .map_err(oauth2::ErrorResponse::server_error)?;
+            if pwned {
+                self.postgres
+                    .user_password_require_update_set(check.id)
+                    .await
+                    .map_err(oauth2::ErrorResponse::server_error)?;
+                audit.set_data("password_pwned", json!(true));
+                require_update = true;
+            }
         }
+
+        let action = self
+            .user_login_action(client, check.id, require_update, "password")
+            .await?;
+        self.login_lockout_action(&args.email, &action).await?;
+        Ok((check.id, action))
     }
 
-    pub(crate) async fn user_password_reset_request(
+    pub(crate) async fn user_totp_login(
         &self,
         audit: &mut Audit,
         client: &Client,
-        email: String,
-    ) -> oauth2::Result<()> {
+        code: String,
+        totp: String,
+    ) -> oauth2::Result<(Uuid, Vec<String>, LoginAction)> {
         let code = self
             .postgres
-            .code_insert_password_reset(client.client_id, client.ttl.code_s, &email)
+            .code_verify(client.client_id, &code, PostgresCodeTarget::Mfa)
             .await
-            .map_err(|e| oauth2::ErrorResponse::server_error(&e.to_string()))?;
+            .map_err(oauth2::ErrorResponse::access_denied)?;
+        let user_id = code
+            .user_id
+            .ok_or_else(|| oauth2::ErrorResponse::invalid_request("user_id is invalid"))?;
+        audit.set_user_id(user_id);
+
+        let email = self.login_lockout_mfa_check(audit, client, user_id).await?;
+        if let Err(e) = self.user_totp_verify(audit, user_id, &totp).await {
+            self.login_lockout_failure(audit, &email).await?;
+            return Err(e);
+        }
 
-        let user_email = &email;
-        let subject = "Password Reset Request";
+        let methods = login_mfa_methods(&code, "totp");
+        let action = self.user_mfa_login_action(user_id).await?;
+        self.login_lockout_success(&email).await?;
+        Ok((user_id, methods, action))
+    }
 
-        let uri = self.uri_auth_password_reset(&code);
-        let mut uri_accept = uri.clone();
-        uri_accept
-            .query_pairs_mut()
-            .append_pair("response_type", "accept");
-        let mut uri_reject = uri.clone();
-        uri_reject
-            .query_pairs_mut()
-            .append_pair("response_type", "reject");
+    /// Returns MFA code if user must verify a second factor before login
+    pub(crate) async fn user_mfa_code(
+        &self,
+        client: &Client,
+        user_id: Uuid,
+        method: &str,
+    ) -> oauth2::Result<Option<String>> {
+        let enabled = self
+            .postgres
+            .user_factor_enabled(user_id)
+            .await
+            .map_err(oauth2::ErrorResponse::server_error)?;
+        if !enabled {
+            if !client.require_mfa {
+                return Ok(None);
+            }
+            // User without a factor is enrolled with TOTP at second factor step, the key is
+            // not required for users with a passkey
+            if self.config.mfa.key.is_empty() {
+                return Err(oauth2::ErrorResponse::access_denied(
+                    "second factor is required, totp is not configured",
+                ));
+            }
+        }
 
-        let text = self
+        let code = self
+            .postgres
+            .code_insert_mfa(client.client_id, client.ttl.code_s, user_id, method)
+            .await
+            .map_err(oauth2::ErrorResponse::server_error)?;
+        Ok(Some(code))
+    }
+
+    /// Returns true if client requires MFA and user has not enrolled a factor
+    pub(crate) async fn user_mfa_missing(
+        &self,
+        client: &Client,
+        user_id: Uuid,
+    ) -> oauth2::Result<bool> {
+        if !client.require_mfa {
+            return Ok(false);
+        }
+        let enabled = self
+            .postgres
+            .user_factor_enabled(user_id)
+            .await
+            .map_err(oauth2::ErrorResponse::server_error)?;
+        Ok(!enabled)
+    }
+
+    /// Returns true if client requires a verified email address and user has not verified
+    pub(crate) async fn user_email_unverified(
+        &self,
+        client: &Client,
+        user_id: Uuid,
+    ) -> oauth2::Result<bool> {
+        if !client.require_email_verified {
+            return Ok(false);
+        }
+        let user = self.user_read_id(client, user_id).await?;
+        Ok(user.email_verified_at.is_none())
+    }
+
+    /// Returns error if client requires a verified email address and user has not verified,
+    /// checked by every login method before a session is created, a verification link is
+    /// sent to the user
+    pub(crate) async fn user_email_verified_check(
+        &self,
+        audit: &mut Audit,
+        client: &Client,
+        user_id: Uuid,
+    ) -> oauth2::Result<()> {
+        if !self.user_email_unverified(client, user_id).await? {
+            return Ok(());
+        }
+        audit.set_user_id(user_id);
+        self.user_email_verify_request(audit, client, user_id)
+            .await?;
+        Err(oauth2::ErrorResponse::access_denied(
+            "email address is not verified, a verification email has been sent",
+        ))
+    }
+
+    pub(crate) async fn user_recovery_login(
+        &self,
+        audit: &mut Audit,
+        client: &Client,
+        code: String,
+        recovery_code: String,
+    ) -> oauth2::Result<(Uuid, Vec<String>, LoginAction)> {
+        let key = self.mfa_key()?;
+        let code = self
+            .postgres
+            .code_verify(client.client_id, &code, PostgresCodeTarget::Mfa)
+            .await
+            .map_err(oauth2::ErrorResponse::access_denied)?;
+        let user_id = code
+            .user_id
+            .ok_or_else(|| oauth2::ErrorResponse::invalid_request("user_id is invalid"))?;
+        audit.set_user_id(user_id);
+
+        let email = self.login_lockout_mfa_check(audit, client, user_id).await?;
+        if let Err(e) = self
+            .postgres
+            .user_recovery_verify(key, user_id, &totp::recovery_code_normalise(&recovery_code))
+            .await
+        {
+            self.login_lockout_failure(audit, &email).await?;
+            return Err(oauth2::ErrorResponse::access_denied(e));
+        }
+
+        let count = self.user_recovery_count(user_id).await?;
+        audit.set_data("recovery", json!({ "remaining": count }));
+
+        let methods = login_mfa_methods(&code, "recovery");
+        let action = self.user_mfa_login_action(user_id).await?;
+        self.login_lockout_success(&email).await?;
+
+        self.user_recovery_notify(audit, client, user_id, count)
+            .await;
+        Ok((user_id, methods, action))
+    }
+
+    /// Notify user that a recovery code was used, recovery code is already consumed so
+    /// errors are logged and audited and do not fail login
+    async fn user_recovery_notify(
+        &self,
+        audit: &mut Audit,
+        client: &Client,
+        user_id: Uuid,
+        count: i64,
+    ) {
+        let res: oauth2::Result<()> = async {
+            let user = self.user_read_id(client, user_id).await?;
+            let text = self.mail_template(
+                client,
+                TEMPLATE_MAIL_RECOVERY,
+                &json!({
+                    "user_email": user.email,
+                    "recovery_count": count,
+                    "client_name": client.client_name,
+                    "client_uri": client.client_uri,
+                    "audit": audit.template,
+                }),
+            )?;
+            self.mailto
+                .send(self.mailto.build(&user.email, "Recovery Code Used", &text))
+                .await
+                .map_err(|e| oauth2::ErrorResponse::server_error(&e.to_string()))
+        }
+        .await;
+        if let Err(e) = res {
+            warn!("recovery code notification failed: {}", e.to_string());
+            audit.set_data("recovery_notify_error", json!(e.to_string()));
+        }
+    }
+
+    pub(crate) async fn user_webauthn_login(
+        &self,
+        audit: &mut Audit,
+        client: &Client,
+        code: Option<String>,
+        args: UserWebauthnLoginArgs,
+    ) -> oauth2::Result<(Uuid, Vec<String>, LoginAction)> {
+        let rp = self.webauthn()?;
+
+        // Second factor step is bound to user by code, otherwise user is found by credential
+        let (code_user_id, methods) = match code {
+            Some(code) => {
+                let code = self
+                    .postgres
+                    .code_verify(client.client_id, &code, PostgresCodeTarget::Mfa)
+                    .await
+                    .map_err(oauth2::ErrorResponse::access_denied)?;
+                let user_id = code
+                    .user_id
+                    .ok_or_else(|| oauth2::ErrorResponse::invalid_request("user_id is invalid"))?;
+                (Some(user_id), login_mfa_methods(&code, "webauthn"))
+            }
+            None => (None, vec!["webauthn".to_string()]),
+        };
+        let challenge_user_id = self
+            .postgres
+            .webauthn_challenge_verify(client, &args.challenge, PostgresWebauthnTarget::Login)
+            .await
+            .map_err(oauth2::ErrorResponse::access_denied)?;
+        if challenge_user_id != code_user_id {
+            return Err(oauth2::ErrorResponse::access_denied(
+                "webauthn challenge is invalid",
+            ));
+        }
+
+        let mut credentials = self
+            .postgres
+            .user_webauthn_read(code_user_id, Some(&args.credential_id))
+            .await
+            .map_err(oauth2::ErrorResponse::server_error)?;
+        if credentials.is_empty() {
+            return Err(oauth2::ErrorResponse::access_denied(
+                "webauthn credential not found",
+            ));
+        }
+        let credential = credentials.remove(0);
+        let user_id = credential.user_id;
+        audit.set_user_id(user_id);
+
+        // Passkey login and second factor step are both subject to login lockout
+        let user = self.user_read_id(client, user_id).await?;
+        self.login_lockout_check(audit, &user.email).await?;
+
+        let sign_count = match rp.login_verify(
+            &args.challenge,
+            &webauthn::Credential {
+                id: credential.credential_id.clone(),
+                public_key: credential.public_key.clone(),
+                sign_count: credential.sign_count as u32,
+            },
+            &args.client_data,
+            &args.authenticator_data,
+            &args.signature,
+            code_user_id.is_none(),
+        ) {
+            Ok(sign_count) => sign_count,
+            Err(e) => {
+                self.login_lockout_failure(audit, &user.email).await?;
+                return Err(oauth2::ErrorResponse::access_denied(e));
+            }
+        };
+
+        self.postgres
+            .user_webauthn_update(
+                user_id,
+                &credential.credential_id,
+                i64::from(sign_count),
+                credential.sign_count,
+            )
+            .await
+            .map_err(oauth2::ErrorResponse::access_denied)?;
+        audit.set_data("webauthn", json!("login"));
+
+        // Passkey login does not use a code so user enable is checked here
+        if code_user_id.is_none() && !user.enable {
+            return Err(oauth2::ErrorResponse::access_denied("user is disabled"));
+        }
+
+        let action = self.user_mfa_login_action(user_id).await?;
+        self.login_lockout_success(&user.email).await?;
+        Ok((user_id, methods, action))
+    }
+
+    pub(crate) async fn user_webauthn_register(
+        &self,
+        audit: &mut Audit,
+        client: &Client,
+        user_id: Uuid,
+        args: UserWebauthnRegisterArgs,
+    ) -> oauth2::Result<Option<Vec<String>>> {
+        let rp = self.webauthn()?;
+
+        let challenge_user_id = self
+            .postgres
+            .webauthn_challenge_verify(client, &args.challenge, PostgresWebauthnTarget::Register)
+            .await
+            .map_err(oauth2::ErrorResponse::access_denied)?;
+        if challenge_user_id != Some(user_id) {
+            return Err(oauth2::ErrorResponse::access_denied(
+                "webauthn challenge is invalid",
+            ));
+        }
+
+        let credential = rp
+            .register_verify(
+                &args.challenge,
+                &args.client_data,
+                &args.attestation_object,
+                false,
+            )
+            .map_err(oauth2::ErrorResponse::access_denied)?;
+
+        self.postgres
+            .user_webauthn_insert(user_id, &credential, &args.name)
+            .await
+            .map_err(oauth2::ErrorResponse::access_denied)?;
+
+        audit.set_data("webauthn", json!("register"));
+        self.user_recovery_enrol(audit, user_id).await
+    }
+
+    pub(crate) async fn user_webauthn_delete(
+        &self,
+        audit: &mut Audit,
+        user_id: Uuid,
+        credential_id: &str,
+    ) -> oauth2::Result<()> {
+        self.postgres
+            .user_webauthn_delete(user_id, credential_id)
+            .await
+            .map_err(oauth2::ErrorResponse::invalid_request)?;
+
+        audit.set_data("webauthn", json!("delete"));
+        self.user_recovery_cleanup(user_id).await
+    }
+
+    async fn user_webauthn_credentials(
+        &self,
+        user_id: Uuid,
+    ) -> oauth2::Result<Vec<PostgresUserWebauthn>> {
+        if self.webauthn.is_none() {
+            return Ok(Vec::new());
+        }
+        self.postgres
+            .user_webauthn_read(Some(user_id), None)
+            .await
+            .map_err(oauth2::ErrorResponse::server_error)
+    }
+
+    pub(crate) async fn user_totp_enable(
+        &self,
+        audit: &mut Audit,
+        user_id: Uuid,
+        totp: &str,
+    ) -> oauth2::Result<Option<Vec<String>>> {
+        self.user_totp_verify(audit, user_id, totp).await?;
+        self.user_recovery_enrol(audit, user_id).await
+    }
+
+    pub(crate) async fn user_totp_disable(
+        &self,
+        audit: &mut Audit,
+        user_id: Uuid,
+        totp: &str,
+    ) -> oauth2::Result<()> {
+        self.user_totp_verify(audit, user_id, totp).await?;
+
+        self.postgres
+            .user_factor_delete(user_id, Some(PostgresUserFactor::Totp))
+            .await
+            .map_err(oauth2::ErrorResponse::server_error)?;
+
+        audit.set_data("totp", json!("disable"));
+        self.user_recovery_cleanup(user_id).await
+    }
+
+    /// Returns new recovery codes if user has none, codes are generated when first factor is enrolled
+    async fn user_recovery_enrol(
+        &self,
+        audit: &mut Audit,
+        user_id: Uuid,
+    ) -> oauth2::Result<Option<Vec<String>>> {
+        if self.config.mfa.key.is_empty() || self.user_recovery_count(user_id).await? > 0 {
+            return Ok(None);
+        }
+        let codes = self.user_recovery_generate(audit, user_id).await?;
+        Ok(Some(codes))
+    }
+
+    pub(crate) async fn user_recovery_generate(
+        &self,
+        audit: &mut Audit,
+        user_id: Uuid,
+    ) -> oauth2::Result<Vec<String>> {
+        let key = self.mfa_key()?;
+        let enabled = self
+            .postgres
+            .user_factor_enabled(user_id)
+            .await
+            .map_err(oauth2::ErrorResponse::server_error)?;
+        if !enabled {
+            return Err(oauth2::ErrorResponse::invalid_request(
+                "recovery codes require an enabled factor",
+            ));
+        }
+
+        let codes = totp::recovery_codes_generate().map_err(oauth2::ErrorResponse::server_error)?;
+        let hashed = codes
+            .iter()
+            .map(|x| totp::recovery_code_normalise(x))
+            .collect::<Vec<_>>();
+        self.postgres
+            .user_recovery_insert(key, user_id, &hashed)
+            .await
+            .map_err(oauth2::ErrorResponse::server_error)?;
+
+        audit.set_data("recovery", json!("generate"));
+        Ok(codes)
+    }
+
+    async fn user_recovery_count(&self, user_id: Uuid) -> oauth2::Result<i64> {
+        if self.config.mfa.key.is_empty() {
+            return Ok(0);
+        }
+        self.postgres
+            .user_recovery_count(user_id)
+            .await
+            .map_err(oauth2::ErrorResponse::server_error)
+    }
+
+    /// Recovery codes are deleted when user has no remaining enabled factors
+    async fn user_recovery_cleanup(&self, user_id: Uuid) -> oauth2::Result<()> {
+        let enabled = self
+            .postgres
+            .user_factor_enabled(user_id)
+            .await
+            .map_err(oauth2::ErrorResponse::server_error)?;
+        if !enabled {
+            self.postgres
+                .user_recovery_delete(user_id)
+                .await
+                .map_err(oauth2::ErrorResponse::server_error)?;
+        }
+        Ok(())
+    }
+
+    async fn user_totp_verify(
+        &self,
+        audit: &mut Audit,
+        user_id: Uuid,
+        totp: &str,
+    ) -> oauth2::Result<()> {
+        let key = self.mfa_key()?;
+        let secret = self
+            .postgres
+            .user_totp_read(key, user_id)
+            .await
+            .map_err(oauth2::ErrorResponse::server_error)?
+            .ok_or_else(|| oauth2::ErrorResponse::access_denied("totp is not enrolled"))?;
+
+        let step = totp::verify(&secret.secret, totp, totp::timestamp(), secret.step)
+            .ok_or_else(|| oauth2::ErrorResponse::access_denied("totp code is incorrect"))?;
+
+        // Verifying a code enables a pending secret and prevents the code being replayed
+        self.postgres
+            .user_totp_update(user_id, step)
+            .await
+            .map_err(oauth2::ErrorResponse::access_denied)?;
+
+        if !secret.enable {
+            audit.set_data("totp", json!("enable"));
+        }
+        Ok(())
+    }
+
+    async fn user_mfa_login_action(&self, user_id: Uuid) -> oauth2::Result<LoginAction> {
+        let require_update = self
+            .postgres
+            .user_password_require_update(user_id)
+            .await
+            .map_err(oauth2::ErrorResponse::server_error)?;
+        if require_update {
+            Ok(LoginAction::RequireUpdate)
+        } else {
+            Ok(LoginAction::Login)
+        }
+    }
+
+    async fn user_login_action(
+        &self,
+        client: &Client,
+        user_id: Uuid,
+        require_update: bool,
+        method: &str,
+    ) -> oauth2::Result<LoginAction> {
+        if let Some(code) = self.user_mfa_code(client, user_id, method).await? {
+            Ok(LoginAction::RequireMfa(code))
+        } else if require_update {
+            Ok(LoginAction::RequireUpdate)
+        } else {
+            Ok(LoginAction::Login)
+        }
+    }
+
+    async fn user_totp_enrol(&self, user_id: Uuid) -> oauth2::Result<PostgresUserTotp> {
+        let key = self.mfa_key()?;
+        if let Some(totp) = self
+            .postgres
+            .user_totp_read(key, user_id)
+            .await
+            .map_err(oauth2::ErrorResponse::server_error)?
+        {
+            return Ok(totp);
+        }
+
+        let secret = totp::secret_generate().map_err(oauth2::ErrorResponse::server_error)?;
+        self.postgres
+            .user_totp_insert(key, user_id, &secret)
+            .await
+            .map_err(oauth2::ErrorResponse::server_error)?;
+        self.postgres
+            .user_totp_read(key, user_id)
+            .await
+            .map_err(oauth2::ErrorResponse::server_error)?
+            .ok_or_else(|| oauth2::ErrorResponse::server_error("totp secret not found"))
+    }
+
+    /// Returns login attempt targets for email and client IP address of request, with the
+    /// number of failures before each is locked
+    fn login_lockout_targets(
+        &self,
+        audit: &Audit,
+        email: &str,
+    ) -> Vec<(PostgresLoginAttemptTarget, String, i32)> {
+        let config = &self.config.lockout;
+        let mut targets = Vec::new();
+        if config.email_failures > 0 {
+            targets.push((
+                PostgresLoginAttemptTarget::Email,
+                lockout::email(email),
+                config.email_failures,
+            ));
+        }
+        if config.address_failures > 0 {
+            let addr = lockout::client_address(
+                audit.remote_addr(),
+                audit.forwarded_for(),
+                &self.config.http.trusted_proxies,
+            );
+            if let Some(addr) = addr {
+                targets.push((
+                    PostgresLoginAttemptTarget::Address,
+                    addr,
+                    config.address_failures,
+                ));
+            }
+        }
+        targets
+    }
+
+    /// Returns error if email or client IP address of request is locked, error does not
+    /// say which is locked
+    async fn login_lockout_check(&self, audit: &mut Audit, email: &str) -> oauth2::Result<()> {
+        for (target, value, _) in self.login_lockout_targets(audit, email) {
+            let locked_until = self
+                .postgres
+                .login_attempt_locked(target, &value)
+                .await
+                .map_err(oauth2::ErrorResponse::server_error)?;
+
+            if let Some(locked_until) = locked_until {
+                audit.set_data(
+                    "lockout",
+                    json!({
+                        "target": target.as_str(),
+                        "locked_until": locked_until.to_rfc3339(),
+                    }),
+                );
+                return Err(oauth2::ErrorResponse::access_denied(
+                    "too many failed login attempts, try again later",
+                ));
+            }
+        }
+        Ok(())
+    }
+
+    /// Returns email of user at second factor step if it is not locked, second factor
+    /// failures are counted for the email so that a known password does not allow unlimited
+    /// guesses of codes
+    async fn login_lockout_mfa_check(
+        &self,
+        audit: &mut Audit,
+        client: &Client,
+        user_id: Uuid,
+    ) -> oauth2::Result<String> {
+        let user = self.user_read_id(client, user_id).await?;
+        self.login_lockout_check(audit, &user.email).await?;
+        Ok(user.email)
+    }
+
+    /// Count failed login for email and client IP address of request, and lock either if
+    /// number of failures reaches configured threshold
+    async fn login_lockout_failure(&self, audit: &mut Audit, email: &str) -> oauth2::Result<()> {
+        let config = &self.config.lockout;
+        for (target, value, threshold) in self.login_lockout_targets(audit, email) {
+            let failures = self
+                .postgres
+                .login_attempt_failure(target, &value, config.reset_s)
+                .await
+                .map_err(oauth2::ErrorResponse::server_error)?;
+
+            if let Some(lockout_s) = lockout::duration_s(config, threshold, failures) {
+                self.postgres
+                    .login_attempt_lock(target, &value, lockout_s)
+                    .await
+                    .map_err(oauth2::ErrorResponse::server_error)?;
+
+                self.opentelemetry.login_lockout_count.add(1);
+                audit.set_data(
+                    "lockout",
+                    json!({
+                        "target": target.as_str(),
+                        "failures": failures,
+                        "lockout_s": lockout_s,
+                    }),
+                );
+            }
+        }
+        Ok(())
+    }
+
+    /// Reset failed logins for email once login is complete, a login that requires a second
+    /// factor is not complete until the second factor is verified
+    async fn login_lockout_action(&self, email: &str, action: &LoginAction) -> oauth2::Result<()> {
+        match action {
+            LoginAction::RequireMfa(_) => Ok(()),
+            LoginAction::Login | LoginAction::RequireUpdate => {
+                self.login_lockout_success(email).await
+            }
+        }
+    }
+
+    /// Reset failed logins for email after successful login, client IP address failures are
+    /// not reset so that they cannot be cleared by logging in to another account
+    async fn login_lockout_success(&self, email: &str) -> oauth2::Result<()> {
+        if self.config.lockout.email_failures > 0 {
+            self.postgres
+                .login_attempt_delete(PostgresLoginAttemptTarget::Email, &lockout::email(email))
+                .await
+                .map_err(oauth2::ErrorResponse::server_error)?;
+        }
+        Ok(())
+    }
+
+    /// Returns time at which password changed at updated_at expires, if client or password
+    /// configuration has a maximum password age
+    fn password_expires_at(
+        &self,
+        client: &Client,
+        updated_at: DateTime<Utc>,
+    ) -> Option<DateTime<Utc>> {
+        let max_age_days = client
+            .password_max_age_days
+            .unwrap_or(self.config.password.max_age_days);
+        if max_age_days > 0 {
+            Some(updated_at + chrono::Duration::days(max_age_days))
+        } else {
+            None
+        }
+    }
+
+    pub(crate) async fn user_password_expires_at(
+        &self,
+        client: &Client,
+        user_id: Uuid,
+    ) -> oauth2::Result<Option<DateTime<Utc>>> {
+        let updated_at = self
+            .postgres
+            .user_password_hash_updated_at(user_id)
+            .await
+            .map_err(oauth2::ErrorResponse::server_error)?;
+        Ok(updated_at.and_then(|x| self.password_expires_at(client, x)))
+    }
+
+    /// Send password expiry warning to user, mail errors are audited and do not prevent
+    /// login, warning is sent again on next login if mail is not sent
+    async fn user_password_expiry_warn(
+        &self,
+        audit: &mut Audit,
+        client: &Client,
+        user_id: Uuid,
+        user_email: &str,
+        expires_at: DateTime<Utc>,
+    ) -> oauth2::Result<()> {
+        let text = self.mail_template(
+            client,
+            TEMPLATE_MAIL_PASSWORD_EXPIRY,
+            &json!({
+                "user_email": user_email,
+                "expires_at": expires_at.format("%Y-%m-%d").to_string(),
+                "uri": self.uri_auth_password_update(client, None),
+                "client_name": client.client_name,
+                "client_uri": client.client_uri,
+                "audit": audit.template,
+            }),
+        )?;
+        let res = self
+            .mailto
+            .send(self.mailto.build(user_email, "Password Expiry", &text))
+            .await;
+        if let Err(e) = res {
+            warn!("password expiry warning failed: {}", e.to_string());
+            audit.set_data("password_expiry_warn_error", json!(e.to_string()));
+            return Ok(());
+        }
+
+        self.postgres
+            .user_password_expire_warned_set(user_id)
+            .await
+            .map_err(oauth2::ErrorResponse::server_error)?;
+        audit.set_data("password_expiry_warned", json!(true));
+        Ok(())
+    }
+
+    /// Returns output of future after at least the configured minimum authentication response
+    /// time, so that response time does not depend on whether a user exists
+    pub(crate) async fn response_pad<F: Future>(&self, f: F) -> F::Output {
+        let min = std::time::Duration::from_millis(self.config.http.auth_response_min_ms);
+        let start = std::time::Instant::now();
+        let output = f.await;
+        let elapsed = start.elapsed();
+        if elapsed < min {
+            actix_rt::time::delay_for(min - elapsed).await;
+        }
+        output
+    }
+
+    /// Returns true if password appears in breached password dataset
+    async fn password_pwned(&self, password: &str) -> Result<bool> {
+        match &self.config.password.pwned_path {
+            Some(path) => Ok(password::pwned_count(path, password).await? > 0),
+            None => Ok(false),
+        }
+    }
+
+    /// Returns error if new password does not meet policy or appears in breached password dataset
+    pub(crate) async fn password_policy_check(
+        &self,
+        password: &str,
+        user_inputs: &[&str],
+    ) -> Result<()> {
+        password::policy_check(&self.config.password, password, user_inputs)?;
+
+        if self.password_pwned(password).await? {
+            Err(Error::from(
+                "password has appeared in a data breach, choose a different password",
+            ))
+        } else {
+            Ok(())
+        }
+    }
+
+    /// Returns error if new password matches one of the most recent passwords of user
+    async fn password_history_check(&self, user_id: Uuid, password: &str) -> oauth2::Result<()> {
+        let used = self
+            .postgres
+            .user_password_history_check(user_id, password)
+            .await
+            .map_err(oauth2::ErrorResponse::server_error)?;
+        if used {
+            Err(oauth2::ErrorResponse::invalid_request(
+                "password has been used recently, choose a different password",
+            ))
+        } else {
+            Ok(())
+        }
+    }
+
+    fn mfa_key(&self) -> oauth2::Result<&str> {
+        if self.config.mfa.key.is_empty() {
+            Err(oauth2::ErrorResponse::server_error("mfa is not configured"))
+        } else {
+            Ok(&self.config.mfa.key)
+        }
+    }
+
+    fn webauthn(&self) -> oauth2::Result<&webauthn::RelyingParty> {
+        self.webauthn
+            .as_ref()
+            .ok_or_else(|| oauth2::ErrorResponse::invalid_request("webauthn is not configured"))
+    }
+
+    pub(crate) async fn user_magic_link_request(
+        &self,
+        audit: &mut Audit,
+        client: &Client,
+        email: String,
+        oauth2_request: oauth2::AuthorizationCodeRequest,
+    ) -> oauth2::Result<()> {
+        if !client.magic_link_enable {
+            return Err(oauth2::ErrorResponse::access_denied(
+                "magic link login is disabled for client",
+            ));
+        }
+        if self.oauth2_provider_home_realm(&email).is_some() {
+            return Err(oauth2::ErrorResponse::access_denied(
+                "magic link login is disabled for email domain",
+            ));
+        }
+
+        // Authorization request is stored with code and completed when link is followed,
+        // unknown email returns the same response as a sent email
+        let code = match self
+            .postgres
+            .code_insert_login(
+                client,
+                client.ttl.code_s,
+                &email,
+                oauth2_request.state(),
+                oauth2_request.scope(),
+            )
+            .await
+            .map_err(oauth2::ErrorResponse::access_denied)?
+        {
+            Some(code) => code,
+            None => {
+                audit.set_data("email_not_found", json!(true));
+                return Ok(());
+            }
+        };
+
+        let user_email = &email;
+        let subject = "Login Request";
+
+        let uri = self.uri_auth_magic_link(&code);
+
+        let text = self.mail_template(
+            client,
+            TEMPLATE_MAIL_MAGIC_LINK,
+            &json!({
+                "user_email": user_email,
+                "uri": uri,
+                "client_name": client.client_name,
+                "client_uri": client.client_uri,
+                "audit": audit.template,
+            }),
+        )?;
+
+        // Mail is sent in background, response time must not depend on mail delivery
+        self.mailto
+            .send_background(self.mailto.build(user_email, subject, &text));
+        Ok(())
+    }
+
+    pub(crate) async fn user_magic_link_login(
+        &self,
+        audit: &mut Audit,
+        client: &Client,
+        code: String,
+    ) -> oauth2::Result<(Uuid, oauth2::AuthorizationCodeRequest, LoginAction)> {
+        if !client.magic_link_enable {
+            return Err(oauth2::ErrorResponse::access_denied(
+                "magic link login is disabled for client",
+            ));
+        }
+
+        let code = self
+            .postgres
+            .code_verify(client.client_id, &code, PostgresCodeTarget::Login)
+            .await
+            .map_err(oauth2::ErrorResponse::access_denied)?;
+        let user_id = code
+            .user_id
+            .ok_or_else(|| oauth2::ErrorResponse::invalid_request("user_id is invalid"))?;
+        audit.set_user_id(user_id);
+
+        // Following a login link proves control of the email address
+        self.postgres
+            .user_email_verify(user_id, &code.email)
+            .await
+            .map_err(oauth2::ErrorResponse::server_error)?;
+
+        let oauth2_request = oauth2::AuthorizationCodeRequest::new(
+            &client.client_id(),
+            client.redirect_uri(),
+            &code.state,
+            code.scope,
+        );
+
+        let action = self
+            .user_login_action(client, user_id, false, "magic_link")
+            .await?;
+        Ok((user_id, oauth2_request, action))
+    }
+
+    pub(crate) async fn user_password_reset_request(
+        &self,
+        audit: &mut Audit,
+        client: &Client,
+        email: String,
+    ) -> oauth2::Result<()> {
+        if self.oauth2_provider_home_realm(&email).is_some() {
+            return Err(oauth2::ErrorResponse::access_denied(
+                "password reset is disabled for email domain",
+            ));
+        }
+
+        // Unknown email returns the same response as a sent email, so that responses do not
+        // reveal which users exist
+        let code = match self
+            .postgres
+            .code_insert_password_reset(client.client_id, client.ttl.code_s, &email)
+            .await
+            .map_err(|e| oauth2::ErrorResponse::server_error(&e.to_string()))?
+        {
+            Some(code) => code,
+            None => {
+                audit.set_data("email_not_found", json!(true));
+                return Ok(());
+            }
+        };
+
+        let user_email = &email;
+        let subject = "Password Reset Request";
+
+        let uri = self.uri_auth_password_reset(&code);
+        let mut uri_accept = uri.clone();
+        uri_accept
+            .query_pairs_mut()
+            .append_pair("response_type", "accept");
+        let mut uri_reject = uri.clone();
+        uri_reject
+            .query_pairs_mut()
+            .append_pair("response_type", "reject");
+
+        let text = self
             .mail_template(
                 client,
                 TEMPLATE_MAIL_PASSWORD_RESET,
@@ -617,68 +2045,344 @@ impl HttpServer {
                     "audit": audit.template,
                 }),
             )
-            .unwrap();
+            .unwrap();
+
+        // Mail is sent in background, response time must not depend on mail delivery
+        self.mailto
+            .send_background(self.mailto.build(user_email, subject, &text));
+        Ok(())
+    }
+
+    pub(crate) async fn user_password_reset_accept(
+        &self,
+        _audit: &mut Audit,
+        client: &Client,
+        code: String,
+        password: String,
+    ) -> oauth2::Result<()> {
+        // Code is read and password checked before code is consumed, so user can try again
+        let user_id = self
+            .postgres
+            .code_read(client.client_id, &code, PostgresCodeTarget::PasswordReset)
+            .await
+            .map_err(|e| oauth2::ErrorResponse::access_denied(&e.to_string()))?
+            .user_id
+            .ok_or_else(|| oauth2::ErrorResponse::invalid_request("user_id is invalid"))?;
+        let user = self.user_read_id(client, user_id).await?;
+        self.password_policy_check(&password, &[&user.email, &user.name])
+            .await
+            .map_err(oauth2::ErrorResponse::invalid_request)?;
+        self.password_history_check(user_id, &password).await?;
+
+        let code = self
+            .postgres
+            .code_verify(client.client_id, &code, PostgresCodeTarget::PasswordReset)
+            .await
+            .map_err(|e| oauth2::ErrorResponse::access_denied(&e.to_string()))?;
+
+        self.postgres
+            .user_password_reset_accept(code.user_id.unwrap(), &password)
+            .await
+            .map_err(|e| oauth2::ErrorResponse::server_error(&e.to_string()))?;
+        // Following a password reset link proves control of the email address
+        self.postgres
+            .user_email_verify(code.user_id.unwrap(), &code.email)
+            .await
+            .map_err(oauth2::ErrorResponse::server_error)?;
+        Ok(())
+    }
+
+    pub(crate) async fn user_password_reset_reject(
+        &self,
+        _audit: &mut Audit,
+        client: &Client,
+        code: String,
+    ) -> oauth2::Result<()> {
+        self.postgres
+            .code_verify(client.client_id, &code, PostgresCodeTarget::PasswordReset)
+            .await
+            .map_err(|e| oauth2::ErrorResponse::access_denied(&e.to_string()))?;
+        Ok(())
+    }
+
+    /// Send confirmation link to new email address, email address is updated when link is
+    /// followed
+    pub(crate) async fn user_email_update_request(
+        &self,
+        audit: &mut Audit,
+        client: &Client,
+        id: Uuid,
+        password: String,
+        email_new: String,
+    ) -> oauth2::Result<()> {
+        let (code, email) = self
+            .postgres
+            .code_insert_email_update(
+                client.client_id,
+                client.ttl.code_s,
+                id,
+                &password,
+                &email_new,
+            )
+            .await
+            .map_err(|_e| oauth2::ErrorResponse::server_error("email update failed"))?;
 
+        let text = self.mail_template(
+            client,
+            TEMPLATE_MAIL_EMAIL_UPDATE,
+            &json!({
+                "user_email": email,
+                "user_email_new": email_new,
+                "uri": self.uri_auth_email_update(&code, "accept"),
+                "client_name": client.client_name,
+                "client_uri": client.client_uri,
+                "audit": audit.template,
+            }),
+        )?;
         self.mailto
-            .send(self.mailto.build(user_email, subject, &text))
+            .send(self.mailto.build(&email_new, "Email Update Request", &text))
             .await
             .map_err(|e| oauth2::ErrorResponse::server_error(&e.to_string()))?;
+
+        audit.set_data("email_new", json!(email_new));
         Ok(())
     }
 
-    pub(crate) async fn user_password_reset_accept(
+    /// Update email address of user and send notice with revert link to previous email address
+    pub(crate) async fn user_email_update_accept(
         &self,
-        _audit: &mut Audit,
+        audit: &mut Audit,
         client: &Client,
         code: String,
-        password: String,
     ) -> oauth2::Result<()> {
         let code = self
             .postgres
-            .code_verify(client.client_id, &code, PostgresCodeTarget::PasswordReset)
+            .code_verify(client.client_id, &code, PostgresCodeTarget::EmailUpdate)
             .await
-            .map_err(|e| oauth2::ErrorResponse::access_denied(&e.to_string()))?;
+            .map_err(oauth2::ErrorResponse::access_denied)?;
+        let user_id = code
+            .user_id
+            .ok_or_else(|| oauth2::ErrorResponse::invalid_request("user_id is invalid"))?;
+        audit.set_user_id(user_id);
+
+        // Email address of user is stored in state, code is not valid if it has changed
+        let email_old = code.state;
+        let updated = self
+            .postgres
+            .user_email_replace(user_id, &email_old, &code.email)
+            .await
+            .map_err(oauth2::ErrorResponse::server_error)?;
+        if !updated {
+            return Err(oauth2::ErrorResponse::access_denied(
+                "email address has changed",
+            ));
+        }
 
-        self.postgres
-            .user_password_reset_accept(code.user_id.unwrap(), &password)
+        let revert_code = self
+            .postgres
+            .code_insert_email_revert(
+                client.client_id,
+                client.ttl.email_revert_s,
+                user_id,
+                &email_old,
+                &code.email,
+            )
+            .await
+            .map_err(oauth2::ErrorResponse::server_error)?;
+        let expires_at = Utc::now() + chrono::Duration::seconds(client.ttl.email_revert_s);
+
+        let text = self.mail_template(
+            client,
+            TEMPLATE_MAIL_EMAIL_UPDATE_NOTICE,
+            &json!({
+                "user_email": email_old,
+                "user_email_new": code.email,
+                "uri": self.uri_auth_email_update(&revert_code, "revert"),
+                "expires_at": expires_at.format("%Y-%m-%d %H:%M").to_string(),
+                "client_name": client.client_name,
+                "client_uri": client.client_uri,
+                "audit": audit.template,
+            }),
+        )?;
+        self.mailto
+            .send(
+                self.mailto
+                    .build(&email_old, "Email Address Updated", &text),
+            )
             .await
             .map_err(|e| oauth2::ErrorResponse::server_error(&e.to_string()))?;
+
+        audit.set_data("email_old", json!(email_old));
         Ok(())
     }
 
-    pub(crate) async fn user_password_reset_reject(
+    /// Revert email address of user to previous email address, even if it has changed again
+    /// since, the password must then be reset and sessions of user are revoked
+    pub(crate) async fn user_email_update_revert(
         &self,
-        _audit: &mut Audit,
+        audit: &mut Audit,
         client: &Client,
         code: String,
     ) -> oauth2::Result<()> {
-        self.postgres
-            .code_verify(client.client_id, &code, PostgresCodeTarget::PasswordReset)
+        let code = self
+            .postgres
+            .code_verify(client.client_id, &code, PostgresCodeTarget::EmailRevert)
             .await
-            .map_err(|e| oauth2::ErrorResponse::access_denied(&e.to_string()))?;
+            .map_err(oauth2::ErrorResponse::access_denied)?;
+        let user_id = code
+            .user_id
+            .ok_or_else(|| oauth2::ErrorResponse::invalid_request("user_id is invalid"))?;
+        audit.set_user_id(user_id);
+
+        // New email address is stored in state, previous email address is stored with code
+        let reverted = self
+            .postgres
+            .user_email_revert(user_id, &code.email)
+            .await
+            .map_err(oauth2::ErrorResponse::server_error)?;
+        if !reverted {
+            return Err(oauth2::ErrorResponse::access_denied("user not found"));
+        }
+
+        audit.set_data("email_reverted", json!(code.state));
         Ok(())
     }
 
-    pub(crate) async fn user_email_update(
+    /// Send verification link to email address of user, nothing is sent if email address is
+    /// already verified
+    pub(crate) async fn user_email_verify_request(
         &self,
-        id: Uuid,
-        password: String,
-        email_new: String,
+        audit: &mut Audit,
+        client: &Client,
+        user_id: Uuid,
     ) -> oauth2::Result<()> {
-        self.postgres
-            .user_email_update(id, &password, &email_new)
+        let (code, user_email) = match self
+            .postgres
+            .code_insert_email_verify(client.client_id, client.ttl.code_s, user_id)
+            .await
+            .map_err(oauth2::ErrorResponse::server_error)?
+        {
+            Some(x) => x,
+            None => {
+                audit.set_data("email_verified", json!(true));
+                return Ok(());
+            }
+        };
+
+        let text = self.mail_template(
+            client,
+            TEMPLATE_MAIL_EMAIL_VERIFY,
+            &json!({
+                "user_email": user_email,
+                "uri": self.uri_auth_email_verify(&code),
+                "client_name": client.client_name,
+                "client_uri": client.client_uri,
+                "audit": audit.template,
+            }),
+        )?;
+        self.mailto
+            .send(
+                self.mailto
+                    .build(&user_email, "Verify Email Address", &text),
+            )
             .await
             .map_err(|e| oauth2::ErrorResponse::server_error(&e.to_string()))?;
+
+        audit.set_data("email_verify_sent", json!(true));
+        Ok(())
+    }
+
+    pub(crate) async fn user_email_verify_accept(
+        &self,
+        audit: &mut Audit,
+        client: &Client,
+        code: String,
+    ) -> oauth2::Result<()> {
+        let code = self
+            .postgres
+            .code_verify(client.client_id, &code, PostgresCodeTarget::EmailVerify)
+            .await
+            .map_err(oauth2::ErrorResponse::access_denied)?;
+        let user_id = code
+            .user_id
+            .ok_or_else(|| oauth2::ErrorResponse::invalid_request("user_id is invalid"))?;
+        audit.set_user_id(user_id);
+
+        // Code is not valid for an email address changed after it was sent
+        let verified = self
+            .postgres
+            .user_email_verify(user_id, &code.email)
+            .await
+            .map_err(oauth2::ErrorResponse::server_error)?;
+        if !verified {
+            return Err(oauth2::ErrorResponse::access_denied(
+                "email address has changed",
+            ));
+        }
+        Ok(())
+    }
+
+    pub(crate) async fn user_account_update(
+        &self,
+        audit: &mut Audit,
+        client: &Client,
+        user_id: Uuid,
+        args: UserAccountUpdateArgs,
+    ) -> oauth2::Result<()> {
+        self.postgres
+            .user_update(
+                client,
+                RequestUserUpdate {
+                    id: user_id,
+                    name: Some(args.name),
+                    locale: Some(args.locale),
+                    timezone: Some(args.timezone),
+                    ..Default::default()
+                },
+            )
+            .await
+            .map_err(oauth2::ErrorResponse::server_error)?;
+        audit.set_data("account", json!("profile"));
+        Ok(())
+    }
+
+    pub(crate) async fn user_session_revoke(
+        &self,
+        audit: &mut Audit,
+        user_id: Uuid,
+        session_id: Uuid,
+    ) -> oauth2::Result<()> {
+        let count = self
+            .postgres
+            .session_delete(user_id, Some(session_id))
+            .await
+            .map_err(oauth2::ErrorResponse::server_error)?;
+        if count == 0 {
+            return Err(oauth2::ErrorResponse::invalid_request("session not found"));
+        }
+        audit.set_data("session_id", json!(session_id));
         Ok(())
     }
 
     pub(crate) async fn user_password_update(
         &self,
         _audit: &mut Audit,
+        client: &Client,
         id: Uuid,
         password: String,
         password_new: String,
     ) -> oauth2::Result<()> {
+        let user = self.user_read_id(client, id).await?;
+        self.password_policy_check(&password_new, &[&user.email, &user.name])
+            .await
+            .map_err(oauth2::ErrorResponse::invalid_request)?;
+        // Current password is checked first so history cannot be used to guess passwords
+        self.postgres
+            .user_password_check_id(id, &password)
+            .await
+            .map_err(|_e| oauth2::ErrorResponse::server_error("password update failed"))?;
+        self.password_history_check(id, &password_new).await?;
+
         self.postgres
             .user_password_update(id, &password, &password_new)
             .await
@@ -698,51 +2402,326 @@ impl HttpServer {
             .await
             .map_err(|e| oauth2::ErrorResponse::server_error(&e.to_string()))?;
 
-        let user_email = &email;
-        let subject = "Register Request";
+        let user_email = &email;
+        let subject = "Register Request";
+
+        let uri = self.uri_auth_register(&code);
+        let mut uri_accept = uri.clone();
+        uri_accept
+            .query_pairs_mut()
+            .append_pair("response_type", "accept");
+        let mut uri_reject = uri.clone();
+        uri_reject
+            .query_pairs_mut()
+            .append_pair("response_type", "reject");
+
+        let text = self
+            .mail_template(
+                client,
+                TEMPLATE_MAIL_REGISTER,
+                &json!({
+                    "user_email": user_email,
+                    "uri_accept": uri_accept,
+                    "uri_reject": uri_reject,
+                    "client_name": client.client_name,
+                    "client_uri": client.client_uri,
+                    "audit": audit.template,
+                }),
+            )
+            .unwrap();
+
+        // Mail is sent in background, response time must not depend on mail delivery
+        self.mailto
+            .send_background(self.mailto.build(user_email, subject, &text));
+        Ok(())
+    }
+
+    pub(crate) async fn user_register_accept_password(
+        &self,
+        audit: &mut Audit,
+        client: &Client,
+        code: String,
+        args: UserRegisterAcceptArgs,
+    ) -> oauth2::Result<Uuid> {
+        // Code is read and password checked before code is consumed, so user can try again
+        let email = self
+            .postgres
+            .code_read(client.client_id, &code, PostgresCodeTarget::Register)
+            .await
+            .map_err(|e| oauth2::ErrorResponse::access_denied(&e.to_string()))?
+            .email;
+        self.password_policy_check(&args.password, &[&email, &args.name])
+            .await
+            .map_err(oauth2::ErrorResponse::invalid_request)?;
+
+        let code = self
+            .postgres
+            .code_verify(client.client_id, &code, PostgresCodeTarget::Register)
+            .await
+            .map_err(|e| oauth2::ErrorResponse::access_denied(&e.to_string()))?;
+
+        let id = self
+            .postgres
+            .user_register_accept_password(
+                &client,
+                &client.register_scope,
+                client.register_approval,
+                &code.email,
+                &args.name,
+                &args.password,
+                args.password_allow_reset,
+            )
+            .await
+            .map_err(|e| oauth2::ErrorResponse::server_error(&e.to_string()))?;
+        if client.register_approval {
+            self.user_register_approval_notify(audit, client, id, &code.email, &args.name)
+                .await?;
+        }
+        Ok(id)
+    }
+
+    /// Notify approvers of client of registration waiting for approval, mail and webhook
+    /// errors are logged and audited so that registration is not failed after user is created
+    async fn user_register_approval_notify(
+        &self,
+        audit: &mut Audit,
+        client: &Client,
+        user_id: Uuid,
+        email: &str,
+        name: &str,
+    ) -> oauth2::Result<()> {
+        audit.set_user_id(user_id);
+        audit.set_data("approval_pending", json!(true));
+
+        let text = self.mail_template(
+            client,
+            TEMPLATE_MAIL_REGISTER_APPROVAL,
+            &json!({
+                "user_id": user_id,
+                "user_email": email,
+                "user_name": name,
+                "client_name": client.client_name,
+                "client_uri": client.client_uri,
+                "audit": audit.template,
+            }),
+        )?;
+        let mut mail_errors = Vec::new();
+        for approver in client.register_approval_email.iter() {
+            let res = self
+                .mailto
+                .send(
+                    self.mailto
+                        .build(approver, "Registration Approval Request", &text),
+                )
+                .await;
+            if let Err(e) = res {
+                warn!("register approval email failed: {}", e.to_string());
+                mail_errors.push(json!({
+                    "email": approver,
+                    "error": e.to_string(),
+                }));
+            }
+        }
+        if !mail_errors.is_empty() {
+            audit.set_data("approval_email_error", json!(mail_errors));
+        }
+
+        if let Some(webhook_uri) = client.register_approval_webhook_uri.as_ref() {
+            let res = self
+                .client
+                .post(webhook_uri.clone())
+                .json(&json!({
+                    "type": "register_approval",
+                    "client_id": client.client_id,
+                    "user_id": user_id,
+                    "email": email,
+                    "name": name,
+                }))
+                .send()
+                .await
+                .and_then(|res| res.error_for_status());
+            if let Err(e) = res {
+                warn!("register approval webhook failed: {}", e);
+                audit.set_data("approval_webhook_error", json!(e.to_string()));
+            }
+        }
+        Ok(())
+    }
+
+    /// Enable user pending registration approval and notify user, approval is audited
+    pub(crate) async fn user_register_approve(
+        &self,
+        mut audit: Audit,
+        client: &Client,
+        req: RequestUserRegisterApproval,
+    ) -> Result<()> {
+        let (_name, email) = self
+            .postgres
+            .user_register_approve(req.user_id)
+            .await?
+            .ok_or_else(|| Error::from("user not found or not pending approval"))?;
+
+        audit.set_user_id(req.user_id);
+        audit.set_status_ok();
+        self.postgres.audit_insert(audit).await?;
+
+        self.user_register_result_notify(
+            client,
+            TEMPLATE_MAIL_REGISTER_APPROVED,
+            &email,
+            "Registration Approved",
+        );
+        Ok(())
+    }
+
+    /// Delete user pending registration approval and notify user, denial is audited
+    pub(crate) async fn user_register_deny(
+        &self,
+        mut audit: Audit,
+        client: &Client,
+        req: RequestUserRegisterApproval,
+    ) -> Result<()> {
+        let (_name, email) = self
+            .postgres
+            .user_register_deny(req.user_id)
+            .await?
+            .ok_or_else(|| Error::from("user not found or not pending approval"))?;
+
+        audit.subject = Some(email.clone());
+        audit.set_user_id(req.user_id);
+        audit.set_status_ok();
+        self.postgres.audit_insert(audit).await?;
+
+        self.user_register_result_notify(
+            client,
+            TEMPLATE_MAIL_REGISTER_DENIED,
+            &email,
+            "Registration Denied",
+        );
+        Ok(())
+    }
+
+    /// Notify user of registration approval decision, the user is already updated and the
+    /// decision audited so errors are logged and do not fail the request
+    fn user_register_result_notify(
+        &self,
+        client: &Client,
+        template: &str,
+        email: &str,
+        subject: &str,
+    ) {
+        let text = self.mail_template(
+            client,
+            template,
+            &json!({
+                "user_email": email,
+                "client_name": client.client_name,
+                "client_uri": client.client_uri,
+            }),
+        );
+        match text {
+            Ok(text) => self
+                .mailto
+                .send_background(self.mailto.build(email, subject, &text)),
+            Err(e) => warn!("register result email failed: {}", e.to_string()),
+        }
+    }
+
+    pub(crate) async fn user_register_reject(
+        &self,
+        _audit: &mut Audit,
+        client: &Client,
+        code: String,
+    ) -> oauth2::Result<()> {
+        self.postgres
+            .code_verify(client.client_id, &code, PostgresCodeTarget::Register)
+            .await
+            .map_err(|e| oauth2::ErrorResponse::access_denied(&e.to_string()))?;
+        Ok(())
+    }
+
+    /// Send invitation email with registration link to email address, the invitation code
+    /// binds the access scope and is accepted even if client registration is disabled
+    pub(crate) async fn user_invite(
+        &self,
+        mut audit: Audit,
+        client: &Client,
+        req: RequestUserInvite,
+    ) -> Result<()> {
+        let scope: oauth2::Scope = req.scope.clone().into();
+        if !client.user_scope.contains(&scope) {
+            return Err(Error::from("scope invalid"));
+        }
+
+        let code = self
+            .postgres
+            .code_insert_invite(
+                client.client_id,
+                client.ttl.invite_s,
+                &req.email,
+                &req.name,
+                &scope,
+            )
+            .await?;
 
-        let uri = self.uri_auth_register(&code);
-        let mut uri_accept = uri.clone();
+        let mut uri_accept = self.uri_auth_register(&code);
         uri_accept
             .query_pairs_mut()
-            .append_pair("response_type", "accept");
-        let mut uri_reject = uri.clone();
-        uri_reject
-            .query_pairs_mut()
-            .append_pair("response_type", "reject");
-
-        let text = self
-            .mail_template(
-                client,
-                TEMPLATE_MAIL_REGISTER,
-                &json!({
-                    "user_email": user_email,
-                    "uri_accept": uri_accept,
-                    "uri_reject": uri_reject,
-                    "client_name": client.client_name,
-                    "client_uri": client.client_uri,
-                    "audit": audit.template,
-                }),
-            )
-            .unwrap();
+            .append_pair("response_type", "invite");
+        let expires_at = Utc::now() + chrono::Duration::seconds(client.ttl.invite_s);
 
+        let text = self.mail_template(
+            client,
+            TEMPLATE_MAIL_INVITE,
+            &json!({
+                "user_email": req.email,
+                "user_name": req.name,
+                "uri_accept": uri_accept,
+                "expires_at": expires_at.format("%Y-%m-%d %H:%M UTC").to_string(),
+                "client_name": client.client_name,
+                "client_uri": client.client_uri,
+            }),
+        )?;
         self.mailto
-            .send(self.mailto.build(user_email, subject, &text))
-            .await
-            .map_err(|e| oauth2::ErrorResponse::server_error(&e.to_string()))?;
+            .send(self.mailto.build(&req.email, "Invitation", &text))
+            .await?;
+
+        audit.subject = Some(req.email);
+        audit.set_data("scope", json!(req.scope));
+        audit.set_status_ok();
+        self.postgres.audit_insert(audit).await?;
         Ok(())
     }
 
-    pub(crate) async fn user_register_accept_password(
+    /// Returns invitation code without consuming it, used to render invitation form
+    pub(crate) async fn user_invite_read(
         &self,
-        _audit: &mut Audit,
+        client: &Client,
+        code: &str,
+    ) -> oauth2::Result<PostgresCode> {
+        self.postgres
+            .code_read(client.client_id, code, PostgresCodeTarget::Invite)
+            .await
+            .map_err(|e| oauth2::ErrorResponse::access_denied(&e.to_string()))
+    }
+
+    /// Accept invitation with password, invited users are not subject to registration approval
+    pub(crate) async fn user_invite_accept_password(
+        &self,
+        audit: &mut Audit,
         client: &Client,
         code: String,
         args: UserRegisterAcceptArgs,
-    ) -> oauth2::Result<String> {
+    ) -> oauth2::Result<Uuid> {
+        // Code is read and password checked before code is consumed, so user can try again
+        let email = self.user_invite_read(client, &code).await?.email;
+        self.password_policy_check(&args.password, &[&email, &args.name])
+            .await
+            .map_err(oauth2::ErrorResponse::invalid_request)?;
+
         let code = self
             .postgres
-            .code_verify(client.client_id, &code, PostgresCodeTarget::Register)
+            .code_verify(client.client_id, &code, PostgresCodeTarget::Invite)
             .await
             .map_err(|e| oauth2::ErrorResponse::access_denied(&e.to_string()))?;
 
@@ -750,7 +2729,8 @@ impl HttpServer {
             .postgres
             .user_register_accept_password(
                 &client,
-                &client.register_scope,
+                &code.scope,
+                false,
                 &code.email,
                 &args.name,
                 &args.password,
@@ -758,20 +2738,9 @@ impl HttpServer {
             )
             .await
             .map_err(|e| oauth2::ErrorResponse::server_error(&e.to_string()))?;
-        Ok(id.to_string())
-    }
-
-    pub(crate) async fn user_register_reject(
-        &self,
-        _audit: &mut Audit,
-        client: &Client,
-        code: String,
-    ) -> oauth2::Result<()> {
-        self.postgres
-            .code_verify(client.client_id, &code, PostgresCodeTarget::Register)
-            .await
-            .map_err(|e| oauth2::ErrorResponse::access_denied(&e.to_string()))?;
-        Ok(())
+        audit.set_user_id(id);
+        audit.set_data("invite", json!(true));
+        Ok(id)
     }
 
     pub(crate) async fn user_delete_request(
@@ -971,6 +2940,23 @@ impl actix_http::ResponseError for oauth2::ErrorResponse {
 pub enum Oauth2Redirect {
     Auth(oauth2::AuthorizationCodeRequest),
     Register,
+    Link,
+    Invite,
+}
+
+#[derive(Debug, Clone)]
+pub struct Oauth2ProviderUser {
+    pub sub: String,
+    pub name: String,
+    pub email: Option<String>,
+}
+
+#[derive(Debug, Clone)]
+pub struct Oauth2ProviderAuthorize {
+    pub uri: Url,
+    pub csrf: String,
+    pub pkce: Option<String>,
+    pub nonce: Option<String>,
 }
 
 impl HttpServer {
@@ -987,6 +2973,73 @@ impl HttpServer {
 }
 
 impl HttpServer {
+    fn oauth2_provider(
+        &self,
+        provider: PostgresOauth2Provider,
+    ) -> oauth2::Result<&ServerOauth2Provider> {
+        match provider {
+            PostgresOauth2Provider::Sso => self.oauth2_providers.sso.as_ref(),
+            PostgresOauth2Provider::Microsoft => self.oauth2_providers.microsoft.as_ref(),
+            PostgresOauth2Provider::Github => self.oauth2_providers.github.as_ref(),
+        }
+        .ok_or_else(|| oauth2::ErrorResponse::invalid_request("oauth2 provider not available"))
+    }
+
+    /// Returns provider email address domain is routed to, if any
+    pub(crate) fn oauth2_provider_home_realm(&self, email: &str) -> Option<PostgresOauth2Provider> {
+        let providers = &self.oauth2_providers;
+        vec![
+            (PostgresOauth2Provider::Sso, providers.sso.as_ref()),
+            (
+                PostgresOauth2Provider::Microsoft,
+                providers.microsoft.as_ref(),
+            ),
+            (PostgresOauth2Provider::Github, providers.github.as_ref()),
+        ]
+        .into_iter()
+        .find(|(_, x)| x.map(|x| x.home_realm_email(email)).unwrap_or(false))
+        .map(|(provider, _)| provider)
+    }
+
+    fn oauth2_provider_authorize(
+        &self,
+        provider: PostgresOauth2Provider,
+    ) -> oauth2::Result<Oauth2ProviderAuthorize> {
+        let provider = self.oauth2_provider(provider)?;
+
+        let mut request = provider
+            .client
+            .authorize_url(::oauth2::CsrfToken::new_random);
+        for scope in provider.scope.iter() {
+            request = request.add_scope(::oauth2::Scope::new(scope.to_string()));
+        }
+
+        let pkce = if provider.pkce {
+            let (pkce_code_challenge, pkce_code_verifier) =
+                ::oauth2::PkceCodeChallenge::new_random_sha256();
+            request = request.set_pkce_challenge(pkce_code_challenge);
+            Some(pkce_code_verifier.secret().to_string())
+        } else {
+            None
+        };
+
+        let nonce = if provider.oidc.is_some() {
+            let nonce = ::oauth2::CsrfToken::new_random().secret().to_string();
+            request = request.add_extra_param("nonce", nonce.clone());
+            Some(nonce)
+        } else {
+            None
+        };
+
+        let (uri, csrf_state) = request.url();
+        Ok(Oauth2ProviderAuthorize {
+            uri,
+            csrf: csrf_state.secret().to_string(),
+            pkce,
+            nonce,
+        })
+    }
+
     pub(crate) async fn oauth2_provider_redirect_request(
         &self,
         _audit: &mut Audit,
@@ -994,59 +3047,22 @@ impl HttpServer {
         provider: PostgresOauth2Provider,
         request: oauth2::AuthorizationCodeRequest,
     ) -> oauth2::Result<Url> {
-        match provider {
-            PostgresOauth2Provider::Sso => {
-                if let Some(provider_client) = self.oauth2_providers.sso.as_ref() {
-                    let (authorize_url, csrf_state) = provider_client
-                        .authorize_url(::oauth2::CsrfToken::new_random)
-                        .url();
-
-                    self.postgres
-                        .oauth2_code_insert_auth(
-                            client.client_id,
-                            client.ttl.oauth2_code_s,
-                            provider,
-                            csrf_state.secret(),
-                            None,
-                            request,
-                        )
-                        .await
-                        .map_err(|e| oauth2::ErrorResponse::server_error(&e.to_string()))?;
+        let authorize = self.oauth2_provider_authorize(provider)?;
 
-                    return Ok(authorize_url);
-                }
-            }
-            PostgresOauth2Provider::Microsoft => {
-                if let Some(provider_client) = self.oauth2_providers.microsoft.as_ref() {
-                    let (pkce_code_challenge, pkce_code_verifier) =
-                        ::oauth2::PkceCodeChallenge::new_random_sha256();
-                    let (authorize_url, csrf_state) = provider_client
-                        .authorize_url(::oauth2::CsrfToken::new_random)
-                        .add_scope(::oauth2::Scope::new(
-                            "https://graph.microsoft.com/User.Read".to_string(),
-                        ))
-                        .set_pkce_challenge(pkce_code_challenge)
-                        .url();
-
-                    self.postgres
-                        .oauth2_code_insert_auth(
-                            client.client_id,
-                            client.ttl.oauth2_code_s,
-                            provider,
-                            csrf_state.secret(),
-                            Some(pkce_code_verifier.secret()),
-                            request,
-                        )
-                        .await
-                        .map_err(|e| oauth2::ErrorResponse::server_error(&e.to_string()))?;
+        self.postgres
+            .oauth2_code_insert_auth(
+                client.client_id,
+                client.ttl.oauth2_code_s,
+                provider,
+                &authorize.csrf,
+                authorize.pkce.as_deref(),
+                authorize.nonce.as_deref(),
+                request,
+            )
+            .await
+            .map_err(|e| oauth2::ErrorResponse::server_error(&e.to_string()))?;
 
-                    return Ok(authorize_url);
-                }
-            }
-        }
-        Err(oauth2::ErrorResponse::invalid_request(
-            "oauth2 provider not available",
-        ))
+        Ok(authorize.uri)
     }
 
     pub(crate) async fn oauth2_provider_redirect_register_request(
@@ -1062,59 +3078,97 @@ impl HttpServer {
             .await
             .map_err(|e| oauth2::ErrorResponse::access_denied(&e.to_string()))?;
 
-        match provider {
-            PostgresOauth2Provider::Sso => {
-                if let Some(provider_client) = self.oauth2_providers.sso.as_ref() {
-                    let (authorize_url, csrf_state) = provider_client
-                        .authorize_url(::oauth2::CsrfToken::new_random)
-                        .url();
-
-                    self.postgres
-                        .oauth2_code_insert_register(
-                            client.client_id,
-                            client.ttl.oauth2_code_s,
-                            provider,
-                            csrf_state.secret(),
-                            None,
-                            &code.email,
-                        )
-                        .await
-                        .map_err(|e| oauth2::ErrorResponse::server_error(&e.to_string()))?;
+        let authorize = self.oauth2_provider_authorize(provider)?;
 
-                    return Ok(authorize_url);
-                }
-            }
-            PostgresOauth2Provider::Microsoft => {
-                if let Some(provider_client) = self.oauth2_providers.microsoft.as_ref() {
-                    let (pkce_code_challenge, pkce_code_verifier) =
-                        ::oauth2::PkceCodeChallenge::new_random_sha256();
-                    let (authorize_url, csrf_state) = provider_client
-                        .authorize_url(::oauth2::CsrfToken::new_random)
-                        .add_scope(::oauth2::Scope::new(
-                            "https://graph.microsoft.com/User.Read".to_string(),
-                        ))
-                        .set_pkce_challenge(pkce_code_challenge)
-                        .url();
-
-                    self.postgres
-                        .oauth2_code_insert_register(
-                            client.client_id,
-                            client.ttl.oauth2_code_s,
-                            provider,
-                            csrf_state.secret(),
-                            Some(pkce_code_verifier.secret()),
-                            &code.email,
-                        )
-                        .await
-                        .map_err(|e| oauth2::ErrorResponse::server_error(&e.to_string()))?;
+        self.postgres
+            .oauth2_code_insert_register(
+                client.client_id,
+                client.ttl.oauth2_code_s,
+                provider,
+                &authorize.csrf,
+                authorize.pkce.as_deref(),
+                authorize.nonce.as_deref(),
+                &code.email,
+            )
+            .await
+            .map_err(|e| oauth2::ErrorResponse::server_error(&e.to_string()))?;
 
-                    return Ok(authorize_url);
-                }
-            }
-        }
-        Err(oauth2::ErrorResponse::invalid_request(
-            "oauth2 provider not available",
-        ))
+        Ok(authorize.uri)
+    }
+
+    /// Redirect to provider to accept invitation, invitation is consumed when the provider
+    /// redirects back so that it can be used again if provider login fails
+    pub(crate) async fn oauth2_provider_redirect_invite_request(
+        &self,
+        _audit: &mut Audit,
+        client: &Client,
+        code: String,
+        provider: PostgresOauth2Provider,
+    ) -> oauth2::Result<Url> {
+        self.user_invite_read(client, &code).await?;
+
+        let authorize = self.oauth2_provider_authorize(provider)?;
+
+        self.postgres
+            .oauth2_code_insert_invite(
+                client.client_id,
+                client.ttl.oauth2_code_s,
+                provider,
+                &authorize.csrf,
+                authorize.pkce.as_deref(),
+                authorize.nonce.as_deref(),
+                &code,
+            )
+            .await
+            .map_err(|e| oauth2::ErrorResponse::server_error(&e.to_string()))?;
+
+        Ok(authorize.uri)
+    }
+
+    pub(crate) async fn oauth2_provider_redirect_link_request(
+        &self,
+        _audit: &mut Audit,
+        client: &Client,
+        user_id: Uuid,
+        provider: PostgresOauth2Provider,
+    ) -> oauth2::Result<Url> {
+        let authorize = self.oauth2_provider_authorize(provider)?;
+
+        self.postgres
+            .oauth2_code_insert_link(
+                client,
+                user_id,
+                provider,
+                &authorize.csrf,
+                authorize.pkce.as_deref(),
+                authorize.nonce.as_deref(),
+            )
+            .await
+            .map_err(|e| oauth2::ErrorResponse::server_error(&e.to_string()))?;
+
+        Ok(authorize.uri)
+    }
+
+    pub(crate) async fn oauth2_provider_unlink(
+        &self,
+        audit: &mut Audit,
+        user_id: Uuid,
+        provider: PostgresOauth2Provider,
+    ) -> oauth2::Result<()> {
+        let sub = self
+            .postgres
+            .user_oauth2_provider_unlink(user_id, provider)
+            .await
+            .map_err(|e| oauth2::ErrorResponse::invalid_request(e.to_string()))?;
+
+        audit.set_data(
+            "unlink",
+            json!({
+                "oauth2_provider": provider.as_str(),
+                "sub": sub,
+            }),
+        );
+        Ok(())
     }
 
     pub(crate) async fn oauth2_provider_microsoft_token_decode(
@@ -1196,126 +3250,277 @@ impl HttpServer {
         }
     }
 
+    pub(crate) async fn oauth2_provider_github_user(
+        &self,
+        token: &str,
+    ) -> oauth2::Result<Oauth2ProviderUser> {
+        let config = self.config.oauth2.providers.github.as_ref().unwrap();
+
+        let user = github::user(&self.client, &config.api_uri, token)
+            .await
+            .map_err(oauth2::ErrorResponse::server_error)?;
+        let email = user.email.ok_or_else(|| {
+            oauth2::ErrorResponse::access_denied("github verified primary email not found")
+        })?;
+
+        if !config.organizations.is_empty() {
+            let mut member = false;
+            for organization in config.organizations.iter() {
+                member =
+                    github::organization_member(&self.client, &config.api_uri, token, organization)
+                        .await
+                        .map_err(oauth2::ErrorResponse::server_error)?;
+                if member {
+                    break;
+                }
+            }
+            if !member {
+                return Err(oauth2::ErrorResponse::access_denied(
+                    "github organization membership is required",
+                ));
+            }
+        }
+
+        Ok(Oauth2ProviderUser {
+            sub: user.id.to_string(),
+            name: user.name.unwrap_or(user.login),
+            email: Some(email),
+        })
+    }
+
+    /// Exchange provider code for token, returns provider user.
+    /// If provider is configured using OIDC discovery the ID token is
+    /// required, and user is read from validated claims.
+    async fn oauth2_provider_exchange(
+        &self,
+        code: &PostgresOauth2Code,
+        authorization_code: String,
+    ) -> oauth2::Result<Oauth2ProviderUser> {
+        use ::oauth2::{AsyncCodeTokenRequest, TokenResponse};
+
+        let provider = self.oauth2_provider(code.provider)?;
+
+        let mut request = provider
+            .client
+            .exchange_code(::oauth2::AuthorizationCode::new(authorization_code));
+        if provider.pkce {
+            request = request.set_pkce_verifier(::oauth2::PkceCodeVerifier::new(code.pkce.clone()));
+        }
+        let token = request
+            .request_async(oauth2_provider_http_client)
+            .await
+            .map_err(|e| oauth2::ErrorResponse::access_denied(e.to_string()))?;
+
+        if let Some(oidc) = provider.oidc.as_ref() {
+            let id_token = token
+                .extra_fields()
+                .id_token
+                .as_deref()
+                .ok_or_else(|| oauth2::ErrorResponse::access_denied("id_token is required"))?;
+
+            let claims = oidc
+                .id_token_verify(&self.client, id_token, &code.nonce)
+                .await
+                .map_err(|e| oauth2::ErrorResponse::access_denied(e.to_string()))?;
+
+            return Ok(Oauth2ProviderUser {
+                sub: claims.sub.clone(),
+                name: claims.name(),
+                email: match claims.email_verified {
+                    Some(true) => claims.email.clone(),
+                    _ => None,
+                },
+            });
+        }
+
+        let token = token.access_token().secret();
+        let (sub, name) = match code.provider {
+            PostgresOauth2Provider::Sso => self.oauth2_provider_sso_token_introspect(token).await?,
+            PostgresOauth2Provider::Microsoft => {
+                self.oauth2_provider_microsoft_token_decode(token).await?
+            }
+            PostgresOauth2Provider::Github => {
+                return self.oauth2_provider_github_user(token).await;
+            }
+        };
+        Ok(Oauth2ProviderUser {
+            sub,
+            name,
+            email: None,
+        })
+    }
+
+    /// Handle provider redirect, session user ID is required to match user of link requests
+    /// so that a leaked or replayed callback cannot be used to link or log in as a user
     pub(crate) async fn oauth2_provider_redirect_response(
         &self,
-        _audit: &mut Audit,
+        audit: &mut Audit,
         client: &Client,
+        session_user_id: Option<Uuid>,
         request: RequestOauth2RedirectQuery,
-    ) -> oauth2::Result<(String, Oauth2Redirect)> {
-        use ::oauth2::{AsyncCodeTokenRequest, TokenResponse};
-
+    ) -> oauth2::Result<(Uuid, Oauth2Redirect)> {
         let code = self
             .postgres
             .oauth2_code_verify(client.client_id, &request.state)
             .await
             .map_err(|e| oauth2::ErrorResponse::access_denied(&e.to_string()))?;
+        if let PostgresOauth2Target::Link = code.target {
+            if code.user_id.is_none() || session_user_id != code.user_id {
+                return Err(oauth2::ErrorResponse::access_denied(
+                    "session does not match user of link request",
+                ));
+            }
+        }
 
-        match code.provider {
-            PostgresOauth2Provider::Sso => {
-                if let Some(provider_client) = self.oauth2_providers.sso.as_ref() {
-                    let token = provider_client
-                        .exchange_code(::oauth2::AuthorizationCode::new(request.code))
-                        .request_async(::oauth2::reqwest::async_http_client)
-                        .await
-                        .map_err(|e| oauth2::ErrorResponse::access_denied(e.to_string()))?;
-
-                    let (sub, name) = self
-                        .oauth2_provider_sso_token_introspect(token.access_token().secret())
-                        .await?;
-
-                    match code.target {
-                        PostgresOauth2Target::Auth => {
-                            let user_id = self
-                                .postgres
-                                .user_oauth2_provider_check(code.provider, &sub)
-                                .await
-                                .map_err(|e| oauth2::ErrorResponse::access_denied(e.to_string()))?;
-
-                            return Ok((
-                                user_id.to_string(),
-                                Oauth2Redirect::Auth(oauth2::AuthorizationCodeRequest::new(
-                                    &client.client_id.to_string(),
-                                    code.redirect_uri.unwrap(),
-                                    &code.state,
-                                    code.scope,
-                                )),
-                            ));
-                        }
-                        PostgresOauth2Target::Register => {
-                            let user_id = self
-                                .postgres
-                                .user_register_accept_oauth2_provider(
-                                    &client,
-                                    &client.register_scope,
-                                    &code.email,
-                                    &name,
-                                    code.provider,
-                                    &sub,
-                                )
-                                .await
-                                .map_err(|e| oauth2::ErrorResponse::server_error(e.to_string()))?;
-
-                            return Ok((user_id.to_string(), Oauth2Redirect::Register));
+        let provider_user = self.oauth2_provider_exchange(&code, request.code).await?;
+
+        match code.target {
+            PostgresOauth2Target::Auth => {
+                let user_id = match self
+                    .postgres
+                    .user_oauth2_provider_check(code.provider, &provider_user.sub)
+                    .await
+                {
+                    Ok(user_id) => user_id,
+                    Err(e) => {
+                        // Provisioning registers the user so it is denied if client registration
+                        // is disabled, and requires approval if client registration does
+                        let provider = self.oauth2_provider(code.provider)?;
+                        let email = if client.register_enable {
+                            provider.auto_provision_email(provider_user.email.as_deref())
+                        } else {
+                            None
+                        };
+                        match email {
+                            Some(email) => {
+                                let user_id = self
+                                    .postgres
+                                    .user_provision_oauth2_provider(
+                                        &client,
+                                        &client.register_scope,
+                                        client.register_approval,
+                                        email,
+                                        &provider_user.name,
+                                        code.provider,
+                                        &provider_user.sub,
+                                    )
+                                    .await
+                                    .map_err(|e| {
+                                        oauth2::ErrorResponse::access_denied(e.to_string())
+                                    })?;
+
+                                audit.set_user_id(user_id);
+                                audit.set_data(
+                                    "provision",
+                                    json!({
+                                        "oauth2_provider": code.provider.as_str(),
+                                        "sub": provider_user.sub,
+                                    }),
+                                );
+                                if client.register_approval {
+                                    self.user_register_approval_notify(
+                                        audit,
+                                        client,
+                                        user_id,
+                                        email,
+                                        &provider_user.name,
+                                    )
+                                    .await?;
+                                    return Ok((user_id, Oauth2Redirect::Register));
+                                }
+                                user_id
+                            }
+                            None => {
+                                return Err(oauth2::ErrorResponse::access_denied(e.to_string()))
+                            }
                         }
                     }
+                };
+
+                Ok((
+                    user_id,
+                    Oauth2Redirect::Auth(oauth2::AuthorizationCodeRequest::new(
+                        &client.client_id.to_string(),
+                        code.redirect_uri.unwrap(),
+                        &code.state,
+                        code.scope,
+                    )),
+                ))
+            }
+            PostgresOauth2Target::Register => {
+                let user_id = self
+                    .postgres
+                    .user_register_accept_oauth2_provider(
+                        &client,
+                        &client.register_scope,
+                        client.register_approval,
+                        &code.email,
+                        &provider_user.name,
+                        code.provider,
+                        &provider_user.sub,
+                    )
+                    .await
+                    .map_err(|e| oauth2::ErrorResponse::server_error(e.to_string()))?;
+                if client.register_approval {
+                    self.user_register_approval_notify(
+                        audit,
+                        client,
+                        user_id,
+                        &code.email,
+                        &provider_user.name,
+                    )
+                    .await?;
                 }
+
+                Ok((user_id, Oauth2Redirect::Register))
             }
-            PostgresOauth2Provider::Microsoft => {
-                if let Some(provider_client) = self.oauth2_providers.microsoft.as_ref() {
-                    let pkce_verifier = ::oauth2::PkceCodeVerifier::new(code.pkce);
+            PostgresOauth2Target::Invite => {
+                // Invitation code is stored in state of provider code
+                let invite = self
+                    .postgres
+                    .code_verify(client.client_id, &code.state, PostgresCodeTarget::Invite)
+                    .await
+                    .map_err(|e| oauth2::ErrorResponse::access_denied(&e.to_string()))?;
 
-                    let token = provider_client
-                        .exchange_code(::oauth2::AuthorizationCode::new(request.code))
-                        .set_pkce_verifier(pkce_verifier)
-                        .request_async(::oauth2::reqwest::async_http_client)
-                        .await
-                        .map_err(|e| oauth2::ErrorResponse::access_denied(e.to_string()))?;
-
-                    let (sub, name) = self
-                        .oauth2_provider_microsoft_token_decode(token.access_token().secret())
-                        .await?;
-
-                    match code.target {
-                        PostgresOauth2Target::Auth => {
-                            let user_id = self
-                                .postgres
-                                .user_oauth2_provider_check(code.provider, &sub)
-                                .await
-                                .map_err(|e| oauth2::ErrorResponse::access_denied(e.to_string()))?;
-
-                            return Ok((
-                                user_id.to_string(),
-                                Oauth2Redirect::Auth(oauth2::AuthorizationCodeRequest::new(
-                                    &client.client_id.to_string(),
-                                    code.redirect_uri.unwrap(),
-                                    &code.state,
-                                    code.scope,
-                                )),
-                            ));
-                        }
-                        PostgresOauth2Target::Register => {
-                            let user_id = self
-                                .postgres
-                                .user_register_accept_oauth2_provider(
-                                    &client,
-                                    &client.register_scope,
-                                    &code.email,
-                                    &name,
-                                    code.provider,
-                                    &sub,
-                                )
-                                .await
-                                .map_err(|e| oauth2::ErrorResponse::server_error(e.to_string()))?;
-
-                            return Ok((user_id.to_string(), Oauth2Redirect::Register));
-                        }
-                    }
-                }
+                let user_id = self
+                    .postgres
+                    .user_register_accept_oauth2_provider(
+                        &client,
+                        &invite.scope,
+                        false,
+                        &invite.email,
+                        &provider_user.name,
+                        code.provider,
+                        &provider_user.sub,
+                    )
+                    .await
+                    .map_err(|e| oauth2::ErrorResponse::server_error(e.to_string()))?;
+                audit.set_user_id(user_id);
+                audit.set_data("invite", json!(true));
+
+                Ok((user_id, Oauth2Redirect::Invite))
+            }
+            PostgresOauth2Target::Link => {
+                let user_id = code
+                    .user_id
+                    .ok_or_else(|| oauth2::ErrorResponse::access_denied("user_id is required"))?;
+                audit.set_user_id(user_id);
+
+                self.postgres
+                    .user_oauth2_provider_link(user_id, code.provider, &provider_user.sub)
+                    .await
+                    .map_err(|e| oauth2::ErrorResponse::access_denied(e.to_string()))?;
+
+                audit.set_data(
+                    "link",
+                    json!({
+                        "oauth2_provider": code.provider.as_str(),
+                        "sub": provider_user.sub,
+                    }),
+                );
+                Ok((user_id, Oauth2Redirect::Link))
             }
         }
-        Err(oauth2::ErrorResponse::invalid_request(
-            "oauth2 provider not available",
-        ))
     }
 
     pub(crate) async fn oauth2_authorization_code(
@@ -1435,3 +3640,130 @@ impl HttpServer {
         }
     }
 }
+
+/// Provider HTTP client, some providers (GitHub) only return JSON token
+/// responses if requested using accept header
+async fn oauth2_provider_http_client(
+    mut request: ::oauth2::HttpRequest,
+) -> std::result::Result<::oauth2::HttpResponse, ::oauth2::reqwest::Error<reqwest::Error>> {
+    request.headers.insert(
+        ::oauth2::http::header::ACCEPT,
+        ::oauth2::http::HeaderValue::from_static("application/json"),
+    );
+    ::oauth2::reqwest::async_http_client(request).await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const PASSWORD: &str = "correct horse battery staple";
+
+    /// Returns server using the `sso_test` database at `TEST_POSTGRES_HOST`, the database is
+    /// set up with a client and user with password, test is skipped if it is not set
+    async fn server(client_id: Uuid, user_id: Uuid, email: &str) -> Option<HttpServer> {
+        let host = std::env::var("TEST_POSTGRES_HOST").ok()?;
+        let hash = password::hash(&ConfigPassword::default(), PASSWORD).unwrap();
+
+        let path = std::env::temp_dir().join(format!("sso-test-{}.toml", Uuid::new_v4()));
+        std::fs::write(
+            &path,
+            format!(
+                r#"
+                [oauth2]
+                domain = "http://localhost:7042/"
+
+                [oauth2.clients.{client_id}]
+                name = "Test"
+                uri = "http://localhost:8080/"
+                secret = "secret"
+                redirect_uri = "http://localhost:8080/oauth2"
+
+                [oauth2.users.{user_id}]
+                name = "Test"
+                email = "{email}"
+                password = "{hash}"
+                [oauth2.users.{user_id}.access.{client_id}]
+
+                [postgres]
+                user = "postgres"
+                password = "postgres"
+                dbname = "sso_test"
+                host = "{host}"
+                port = 5432
+
+                [mfa]
+                key = "mfa"
+
+                [lockout]
+                email_failures = 3
+                "#,
+                client_id = client_id,
+                user_id = user_id,
+                email = email,
+                hash = hash,
+                host = host,
+            ),
+        )
+        .unwrap();
+        let config = Config::from_env(path.to_str().unwrap());
+        std::fs::remove_file(&path).unwrap();
+
+        Some(HttpServer::from_config(config.unwrap()).await.unwrap())
+    }
+
+    #[actix_rt::test]
+    async fn test_login_lockout_totp() {
+        let client_id = Uuid::new_v4();
+        let user_id = Uuid::new_v4();
+        let email = format!("{}@example.com", user_id);
+        let server = match server(client_id, user_id, &email).await {
+            Some(server) => server,
+            None => return,
+        };
+        let client = server
+            .client_from_config(client_id, &server.config.oauth2.clients[&client_id])
+            .unwrap();
+
+        let secret = server.user_totp_enrol(user_id).await.unwrap();
+        server.postgres.user_totp_update(user_id, 1).await.unwrap();
+        let totp = ["000000", "111111"]
+            .iter()
+            .find(|x| totp::verify(&secret.secret, x, totp::timestamp(), 1).is_none())
+            .unwrap();
+
+        let req = actix_web::test::TestRequest::default().to_http_request();
+        let args = || UserLoginArgs {
+            email: email.clone(),
+            password: PASSWORD.to_string(),
+        };
+
+        // Correct password does not reset failures of incorrect second factor codes
+        for _ in 0..3 {
+            let mut audit = Audit::from_http_request("test", &req);
+            let (_, action) = server
+                .user_password_login(&mut audit, &client, args())
+                .await
+                .unwrap();
+            let code = match action {
+                LoginAction::RequireMfa(code) => code,
+                action => panic!("unexpected action {:?}", action),
+            };
+            let e = server
+                .user_totp_login(&mut audit, &client, code, totp.to_string())
+                .await
+                .unwrap_err();
+            assert_eq!(e.error_description(), "totp code is incorrect");
+        }
+
+        let mut audit = Audit::from_http_request("test", &req);
+        let e = server
+            .user_password_login(&mut audit, &client, args())
+            .await
+            .unwrap_err();
+        assert_eq!(
+            e.error_description(),
+            "too many failed login attempts, try again later"
+        );
+    }
+}
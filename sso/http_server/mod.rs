@@ -13,13 +13,14 @@ mod route_script;
 mod route_user;
 mod route_user_access;
 mod route_user_api_key;
+mod route_user_register;
+mod route_user_session;
 mod route_well_known;
 mod template;
 
 pub use crate::http_server::error::*;
 
 use crate::internal::*;
-use ::oauth2::basic::BasicClient;
 
 /// HTTP Server
 #[derive(Clone)]
@@ -29,7 +30,10 @@ pub struct HttpServer {
     client: reqwest::Client,
     handlebars: Arc<handlebars::Handlebars<'static>>,
     mailto: mailto::Mailto,
+    ldap: Option<ldap::Ldap>,
+    webauthn: Option<webauthn::RelyingParty>,
     metrics: metrics::Metrics,
+    rate_limit: rate_limit::RateLimit,
     oauth2_providers: ServerOauth2Providers,
     opentelemetry: Arc<ServerOpentelemetry>,
 }
@@ -65,11 +69,20 @@ impl HttpServer {
             .build()?;
 
         let sso =
-            Self::oauth2_provider_client_sso(&config.oauth2.domain, &config.oauth2.providers.sso)?;
-        let microsoft = Self::oauth2_provider_client_microsoft(
+            Self::oauth2_provider_sso(&client, &config.oauth2.domain, &config.oauth2.providers.sso)
+                .await?;
+        let microsoft = Self::oauth2_provider_microsoft(
+            &client,
             &config.oauth2.domain,
             &config.oauth2.providers.microsoft,
-        )?;
+        )
+        .await?;
+        let github = Self::oauth2_provider_github(
+            &client,
+            &config.oauth2.domain,
+            &config.oauth2.providers.github,
+        )
+        .await?;
 
         let metrics = metrics::from_config(config.metrics.clone())?;
         let opentelemetry = Arc::new(ServerOpentelemetry {
@@ -103,6 +116,12 @@ impl HttpServer {
                 .with_description("Total number of api errors.")
                 .init()
                 .bind(&[]),
+            login_lockout_count: metrics
+                .meter()
+                .u64_counter("login_lockout_count")
+                .with_description("Total number of login lockouts.")
+                .init()
+                .bind(&[]),
         });
 
         Ok(Self {
@@ -111,8 +130,24 @@ impl HttpServer {
             client,
             handlebars: Arc::new(handlebars::Handlebars::new()),
             mailto: mailto::from_config(&metrics, config.mailto.clone()).await?,
+            ldap: config.ldap.clone().map(ldap::from_config),
+            webauthn: config
+                .webauthn
+                .as_ref()
+                .map(|x| webauthn::RelyingParty::from_uri(&config.oauth2.domain, &x.rp_name))
+                .transpose()?,
+            rate_limit: rate_limit::from_config(
+                &metrics,
+                &config.rate_limit,
+                &config.http.trusted_proxies,
+                &config.oauth2.clients,
+            ),
             metrics,
-            oauth2_providers: ServerOauth2Providers { sso, microsoft },
+            oauth2_providers: ServerOauth2Providers {
+                sso,
+                microsoft,
+                github,
+            },
             opentelemetry,
         })
     }
@@ -151,6 +186,7 @@ impl HttpServer {
                         .max_age(cookie_max_age)
                         .same_site(cookie_same_site),
                 ))
+                .wrap(server.rate_limit.clone())
                 .data(server.clone())
                 .with_json_spec_at("openapi.json")
                 .service(api::ServerApi::public())
@@ -236,6 +272,10 @@ impl HttpServer {
         ServerUri::auth_password_reset(&self.config.oauth2.domain, code)
     }
 
+    pub(crate) fn uri_auth_magic_link(&self, code: &str) -> Url {
+        ServerUri::auth_magic_link(&self.config.oauth2.domain, code)
+    }
+
     pub(crate) fn uri_auth_password_update(&self, client: &Client, message: Option<&str>) -> Url {
         ServerUri::auth_password_update(
             &self.config.oauth2.domain,
@@ -245,6 +285,22 @@ impl HttpServer {
         )
     }
 
+    pub(crate) fn uri_auth_link(&self, client: &Client) -> Url {
+        ServerUri::auth_link(
+            &self.config.oauth2.domain,
+            &client.client_id(),
+            &client.redirect_uri().to_string(),
+        )
+    }
+
+    pub(crate) fn uri_auth_account(&self, client: &Client) -> Url {
+        ServerUri::auth_account(
+            &self.config.oauth2.domain,
+            &client.client_id(),
+            &client.redirect_uri().to_string(),
+        )
+    }
+
     pub(crate) fn uri_auth_register(&self, code: &str) -> Url {
         ServerUri::auth_register(&self.config.oauth2.domain, code)
     }
@@ -252,52 +308,172 @@ impl HttpServer {
     pub(crate) fn uri_auth_delete(&self, code: &str) -> Url {
         ServerUri::auth_delete(&self.config.oauth2.domain, code)
     }
+
+    pub(crate) fn uri_auth_email_update(&self, code: &str, response_type: &str) -> Url {
+        ServerUri::auth_email_update(&self.config.oauth2.domain, code, response_type)
+    }
+
+    pub(crate) fn uri_auth_email_verify(&self, code: &str) -> Url {
+        ServerUri::auth_email_verify(&self.config.oauth2.domain, code)
+    }
 }
 
 impl oauth2::AuthorizationServerIf for HttpServer {}
 
 impl HttpServer {
-    fn oauth2_provider_client_microsoft(
+    async fn oauth2_provider_microsoft(
+        client: &reqwest::Client,
         domain: &Url,
         provider: &Option<ConfigOauth2Provider>,
-    ) -> Result<Option<BasicClient>> {
+    ) -> Result<Option<ServerOauth2Provider>> {
         if let Some(provider) = provider {
-            let client = BasicClient::new(
-                ::oauth2::ClientId::new(provider.client_id.clone()),
-                Some(::oauth2::ClientSecret::new(provider.client_secret.clone())),
-                ::oauth2::AuthUrl::from_url(provider.authorize_uri.clone()),
-                Some(::oauth2::TokenUrl::from_url(provider.token_uri.clone())),
-            )
-            .set_auth_type(::oauth2::AuthType::RequestBody)
-            .set_redirect_url(::oauth2::RedirectUrl::from_url(ServerUri::oauth2_redirect(
+            Self::oauth2_provider_auto_provision_check(provider, false)?;
+            let mut provider = Self::oauth2_provider_build(
+                client,
                 domain,
-            )));
-            Ok(Some(client))
+                provider,
+                vec!["https://graph.microsoft.com/User.Read".to_string()],
+                true,
+            )
+            .await?;
+            provider.client = provider
+                .client
+                .set_auth_type(::oauth2::AuthType::RequestBody);
+            Ok(Some(provider))
         } else {
             Ok(None)
         }
     }
 
-    fn oauth2_provider_client_sso(
+    async fn oauth2_provider_github(
+        client: &reqwest::Client,
         domain: &Url,
         provider: &Option<ConfigOauth2Provider>,
-    ) -> Result<Option<BasicClient>> {
+    ) -> Result<Option<ServerOauth2Provider>> {
         if let Some(provider) = provider {
-            let client = BasicClient::new(
-                ::oauth2::ClientId::new(provider.client_id.clone()),
-                Some(::oauth2::ClientSecret::new(provider.client_secret.clone())),
-                ::oauth2::AuthUrl::from_url(provider.authorize_uri.clone()),
-                Some(::oauth2::TokenUrl::from_url(provider.token_uri.clone())),
-            )
-            .set_redirect_url(::oauth2::RedirectUrl::from_url(
-                ServerUri::oauth2_redirect(domain),
-            ));
-            Ok(Some(client))
+            Self::oauth2_provider_auto_provision_check(provider, true)?;
+            let mut provider = provider.clone();
+            if provider.authorize_uri.is_none() {
+                provider.authorize_uri = Some(Url::parse(GITHUB_AUTHORIZE_URI).unwrap());
+            }
+            if provider.token_uri.is_none() {
+                provider.token_uri = Some(Url::parse(GITHUB_TOKEN_URI).unwrap());
+            }
+
+            let mut scope = vec!["read:user".to_string(), "user:email".to_string()];
+            if !provider.organizations.is_empty() {
+                scope.push("read:org".to_string());
+            }
+
+            let mut provider =
+                Self::oauth2_provider_build(client, domain, &provider, scope, false).await?;
+            provider.client = provider
+                .client
+                .set_auth_type(::oauth2::AuthType::RequestBody);
+            Ok(Some(provider))
+        } else {
+            Ok(None)
+        }
+    }
+
+    async fn oauth2_provider_sso(
+        client: &reqwest::Client,
+        domain: &Url,
+        provider: &Option<ConfigOauth2Provider>,
+    ) -> Result<Option<ServerOauth2Provider>> {
+        if let Some(provider) = provider {
+            Self::oauth2_provider_auto_provision_check(provider, false)?;
+            let provider =
+                Self::oauth2_provider_build(client, domain, provider, Vec::new(), false).await?;
+            Ok(Some(provider))
         } else {
             Ok(None)
         }
     }
 
+    /// Returns error if provider is configured to provision users but does not return a
+    /// verified email address, providers return the `email_verified` ID token claim if
+    /// configured with an issuer, and `verified_email` is true for providers which return a
+    /// verified email address without one (the GitHub verified primary email address)
+    fn oauth2_provider_auto_provision_check(
+        provider: &ConfigOauth2Provider,
+        verified_email: bool,
+    ) -> Result<()> {
+        if provider.auto_provision && !verified_email && provider.issuer.is_none() {
+            return Err(Error::from(
+                "oauth2 provider auto_provision requires issuer for verified email address",
+            ));
+        }
+        Ok(())
+    }
+
+    async fn oauth2_provider_build(
+        client: &reqwest::Client,
+        domain: &Url,
+        provider: &ConfigOauth2Provider,
+        mut scope: Vec<String>,
+        pkce: bool,
+    ) -> Result<ServerOauth2Provider> {
+        let oidc = match provider.issuer.as_ref() {
+            Some(issuer) => Some(
+                openid::Provider::discover(
+                    client,
+                    issuer,
+                    &provider.client_id,
+                    provider.jwks_refresh_s,
+                )
+                .await?,
+            ),
+            None => None,
+        };
+
+        let authorize_uri = provider
+            .authorize_uri
+            .clone()
+            .or_else(|| {
+                oidc.as_ref()
+                    .map(|x| x.configuration().authorization_endpoint.clone())
+            })
+            .ok_or_else(|| Error::from("oauth2 provider authorize_uri is required"))?;
+        let token_uri = provider
+            .token_uri
+            .clone()
+            .or_else(|| {
+                oidc.as_ref()
+                    .map(|x| x.configuration().token_endpoint.clone())
+            })
+            .ok_or_else(|| Error::from("oauth2 provider token_uri is required"))?;
+
+        if oidc.is_some() {
+            scope.extend(vec![
+                "openid".to_string(),
+                "profile".to_string(),
+                "email".to_string(),
+            ]);
+        }
+        scope.extend(provider.scope.iter().cloned());
+
+        let client = openid::ProviderClient::new(
+            ::oauth2::ClientId::new(provider.client_id.clone()),
+            Some(::oauth2::ClientSecret::new(provider.client_secret.clone())),
+            ::oauth2::AuthUrl::from_url(authorize_uri),
+            Some(::oauth2::TokenUrl::from_url(token_uri)),
+        )
+        .set_redirect_url(::oauth2::RedirectUrl::from_url(ServerUri::oauth2_redirect(
+            domain,
+        )));
+
+        Ok(ServerOauth2Provider {
+            client,
+            scope,
+            pkce,
+            oidc,
+            auto_provision: provider.auto_provision,
+            auto_provision_domains: provider.auto_provision_domains.clone(),
+            home_realm_domains: provider.home_realm_domains.clone(),
+        })
+    }
+
     fn cookie_same_site(value: &str) -> Result<actix_web::cookie::SameSite> {
         match value {
             "strict" => Ok(actix_web::cookie::SameSite::Strict),
@@ -307,6 +483,12 @@ impl HttpServer {
     }
 }
 
+/// GitHub authorization URI
+const GITHUB_AUTHORIZE_URI: &str = "https://github.com/login/oauth/authorize";
+
+/// GitHub token URI
+const GITHUB_TOKEN_URI: &str = "https://github.com/login/oauth/access_token";
+
 /// Server Opentelemetry
 #[derive(Debug)]
 pub(crate) struct ServerOpentelemetry {
@@ -315,13 +497,27 @@ pub(crate) struct ServerOpentelemetry {
     pub validation_err_count: BoundCounter<'static, u64>,
     pub api_ok_count: BoundCounter<'static, u64>,
     pub api_err_count: BoundCounter<'static, u64>,
+    pub login_lockout_count: BoundCounter<'static, u64>,
 }
 
 /// Server OAuth2 Providers
 #[derive(Clone)]
 pub(crate) struct ServerOauth2Providers {
-    pub sso: Option<BasicClient>,
-    pub microsoft: Option<BasicClient>,
+    pub sso: Option<ServerOauth2Provider>,
+    pub microsoft: Option<ServerOauth2Provider>,
+    pub github: Option<ServerOauth2Provider>,
+}
+
+/// Server OAuth2 Provider
+#[derive(Clone)]
+pub(crate) struct ServerOauth2Provider {
+    pub client: openid::ProviderClient,
+    pub scope: Vec<String>,
+    pub pkce: bool,
+    pub oidc: Option<openid::Provider>,
+    pub auto_provision: bool,
+    pub auto_provision_domains: Vec<String>,
+    pub home_realm_domains: Vec<String>,
 }
 
 /// Server URI
@@ -359,6 +555,13 @@ impl ServerUri {
         uri
     }
 
+    pub fn auth_magic_link(domain: &Url, code: &str) -> Url {
+        let mut uri = domain.clone();
+        uri.set_path("/v2/auth/magic-link");
+        uri.query_pairs_mut().clear().append_pair("code", &code);
+        uri
+    }
+
     pub fn auth_password_update(
         domain: &Url,
         client_id: &str,
@@ -377,6 +580,26 @@ impl ServerUri {
         uri
     }
 
+    pub fn auth_link(domain: &Url, client_id: &str, redirect_uri: &str) -> Url {
+        let mut uri = domain.clone();
+        uri.set_path("/v2/auth/link");
+        uri.query_pairs_mut()
+            .clear()
+            .append_pair("client_id", &client_id)
+            .append_pair("redirect_uri", &redirect_uri);
+        uri
+    }
+
+    pub fn auth_account(domain: &Url, client_id: &str, redirect_uri: &str) -> Url {
+        let mut uri = domain.clone();
+        uri.set_path("/v2/auth/account");
+        uri.query_pairs_mut()
+            .clear()
+            .append_pair("client_id", &client_id)
+            .append_pair("redirect_uri", &redirect_uri);
+        uri
+    }
+
     pub fn auth_register(domain: &Url, code: &str) -> Url {
         let mut uri = domain.clone();
         uri.set_path("/v2/auth/register");
@@ -384,6 +607,23 @@ impl ServerUri {
         uri
     }
 
+    pub fn auth_email_update(domain: &Url, code: &str, response_type: &str) -> Url {
+        let mut uri = domain.clone();
+        uri.set_path("/v2/auth/email-update");
+        uri.query_pairs_mut()
+            .clear()
+            .append_pair("code", &code)
+            .append_pair("response_type", &response_type);
+        uri
+    }
+
+    pub fn auth_email_verify(domain: &Url, code: &str) -> Url {
+        let mut uri = domain.clone();
+        uri.set_path("/v2/auth/email-verify");
+        uri.query_pairs_mut().clear().append_pair("code", &code);
+        uri
+    }
+
     pub fn auth_delete(domain: &Url, code: &str) -> Url {
         let mut uri = domain.clone();
         uri.set_path("/v2/auth/delete");
@@ -392,6 +632,40 @@ impl ServerUri {
     }
 }
 
+impl ServerOauth2Provider {
+    /// Returns email address if user can be automatically provisioned
+    pub fn auto_provision_email<'a>(&self, email: Option<&'a str>) -> Option<&'a str> {
+        if !self.auto_provision {
+            return None;
+        }
+        let email = email?;
+        if self.auto_provision_domains.is_empty() {
+            return Some(email);
+        }
+        let domain = email.rsplit('@').next()?;
+        if self
+            .auto_provision_domains
+            .iter()
+            .any(|x| x.eq_ignore_ascii_case(domain))
+        {
+            Some(email)
+        } else {
+            None
+        }
+    }
+
+    /// Returns true if email address domain is routed to provider
+    pub fn home_realm_email(&self, email: &str) -> bool {
+        match email.rsplit('@').next() {
+            Some(domain) => self
+                .home_realm_domains
+                .iter()
+                .any(|x| x.eq_ignore_ascii_case(domain)),
+            None => false,
+        }
+    }
+}
+
 impl std::fmt::Debug for HttpServer {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         write!(f, "HttpServer {{ config, ... }}")
@@ -0,0 +1,110 @@
+use crate::http_server::internal::*;
+
+#[api_v2_operation(summary = "User account interface")]
+pub async fn get(
+    server: Data<HttpServer>,
+    req: HttpRequest,
+    query: Query<RequestAuthQuery>,
+) -> oauth2::Result<HttpResponse> {
+    server_request!(&server, &req, async {
+        let mut audit = Audit::from_http_request("sso_auth_account", &req);
+        let query = server_oauth2_validate!(&server, query);
+        let query = AuthClientId::parse(query)?;
+
+        let client = server.client_from_id(&mut audit, &query).await?;
+
+        server_oauth2_error!(&server, audit, &client, TEMPLATE_ERROR, async {
+            let session = server.request_session_required(&mut audit, &req).await?;
+
+            let context = server.template_account_context(&client, &session).await?;
+
+            server.response_template_context(&client, TEMPLATE_AUTH_ACCOUNT, context)
+        })
+    })
+}
+
+#[api_v2_operation(summary = "User account interface")]
+pub async fn post(
+    server: Data<HttpServer>,
+    req: HttpRequest,
+    query: Query<RequestAuthQuery>,
+    body: Form<RequestAuthAccount>,
+) -> oauth2::Result<HttpResponse> {
+    server_request!(&server, &req, async {
+        let mut audit = Audit::from_http_request("sso_auth_account", &req);
+        let query = server_oauth2_validate!(&server, query);
+        let query = AuthClientId::parse(query)?;
+        let body = server_oauth2_validate!(&server, body);
+        let (csrf_token, account_type) = body.into_inner()?;
+
+        let client = server.client_from_id(&mut audit, &query).await?;
+
+        server.csrf_verify(&client, csrf_token).await?;
+
+        server_oauth2_form_error!(&server, audit, &client, TEMPLATE_AUTH_ACCOUNT, async {
+            let user_id = server.request_identity_required(&mut audit, &req).await?;
+
+            match account_type {
+                AuthAccountType::Profile(args) => {
+                    server
+                        .user_account_update(&mut audit, &client, user_id, args)
+                        .await?;
+                }
+                AuthAccountType::EmailVerify => {
+                    server
+                        .user_email_verify_request(&mut audit, &client, user_id)
+                        .await?;
+                }
+                AuthAccountType::SessionRevoke(session_id) => {
+                    server
+                        .user_session_revoke(&mut audit, user_id, session_id)
+                        .await?;
+                }
+            }
+
+            Ok(server.response_redirect(server.uri_auth_account(&client)))
+        })
+    })
+}
+
+enum AuthAccountType {
+    Profile(UserAccountUpdateArgs),
+    EmailVerify,
+    SessionRevoke(Uuid),
+}
+
+impl RequestAuthAccount {
+    fn into_inner(self) -> oauth2::Result<(String, AuthAccountType)> {
+        match self.account_type.as_ref() {
+            "profile" => {
+                let name = if let Some(name) = self.name {
+                    name
+                } else {
+                    return Err(oauth2::ErrorResponse::invalid_request("name is required"));
+                };
+                Ok((
+                    self.csrf_token,
+                    AuthAccountType::Profile(UserAccountUpdateArgs {
+                        name,
+                        locale: self.locale.unwrap_or_default(),
+                        timezone: self.timezone.unwrap_or_default(),
+                    }),
+                ))
+            }
+            "email_verify" => Ok((self.csrf_token, AuthAccountType::EmailVerify)),
+            "session_revoke" => {
+                let session_id = if let Some(session_id) = self.session_id {
+                    session_id
+                } else {
+                    return Err(oauth2::ErrorResponse::invalid_request(
+                        "session_id is required",
+                    ));
+                };
+                Ok((self.csrf_token, AuthAccountType::SessionRevoke(session_id)))
+            }
+            _ => Err(oauth2::ErrorResponse::invalid_request(
+                "account_type is invalid",
+            )),
+        }
+    }
+}
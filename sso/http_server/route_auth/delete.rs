@@ -67,8 +67,7 @@ pub async fn post(
                         .user_delete_accept(&mut audit, &client, id, code)
                         .await?;
 
-                    let ident = server.request_identity(&req).await;
-                    ident.forget();
+                    server.session_logout(&req).await?;
 
                     TEMPLATE_AUTH_DELETE_ACCEPT_OK
                 }
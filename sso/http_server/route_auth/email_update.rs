@@ -4,21 +4,30 @@ use crate::http_server::internal::*;
 pub async fn get(
     server: Data<HttpServer>,
     req: HttpRequest,
-    query: Query<RequestAuthQuery>,
+    query: Query<RequestAuthEmailUpdateQuery>,
 ) -> oauth2::Result<HttpResponse> {
     server_request!(&server, &req, async {
         let mut audit = Audit::from_http_request("sso_auth_email_update", &req);
         let query = server_oauth2_validate!(&server, query);
-        let query = AuthClientId::parse(query)?;
 
-        let client = server.client_from_id(&mut audit, &query).await?;
+        let (query_client, query_code, query) = AuthEmailUpdateQueryParse::parse(&query)?;
+
+        let client = server
+            .client_from_id_or_code(&mut audit, query_client, query_code)
+            .await?;
 
         server_oauth2_error!(&server, audit, &client, TEMPLATE_ERROR, async {
-            let _id = server.request_identity_required(&mut audit, &req).await?;
+            // Confirmation and revert links may be followed from another browser, so only
+            // the request form requires a session
+            if let AuthEmailUpdateQueryParse::Request = query {
+                let _id = server.request_identity_required(&mut audit, &req).await?;
+            }
 
             let context = server.template_csrf_context(&client).await?;
 
-            server.response_template_context(&client, TEMPLATE_AUTH_EMAIL_UPDATE, context)
+            let template = query.template_get();
+
+            server.response_template_context(&client, template, context)
         })
     })
 }
@@ -27,39 +36,144 @@ pub async fn get(
 pub async fn post(
     server: Data<HttpServer>,
     req: HttpRequest,
-    query: Query<RequestAuthQuery>,
+    query: Query<RequestAuthEmailUpdateQuery>,
     body: Form<RequestAuthEmailUpdate>,
 ) -> oauth2::Result<HttpResponse> {
     server_request!(&server, &req, async {
         let mut audit = Audit::from_http_request("sso_auth_email_update", &req);
         let query = server_oauth2_validate!(&server, query);
-        let query = AuthClientId::parse(query)?;
         let body = server_oauth2_validate!(&server, body);
-        let (csrf_token, password, email_new) = body.into_inner()?;
 
-        let client = server.client_from_id(&mut audit, &query).await?;
+        let (query_client, query_code, query) = AuthEmailUpdateQueryParse::parse(&query)?;
+        let csrf_token = body.csrf_token.clone();
+
+        let client = server
+            .client_from_id_or_code(&mut audit, query_client, query_code)
+            .await?;
 
         server.csrf_verify(&client, csrf_token).await?;
 
-        server_oauth2_form_error!(&server, audit, &client, TEMPLATE_AUTH_EMAIL_UPDATE, async {
-            let user_id = server.request_identity_required(&mut audit, &req).await?;
+        let template = query.template_get();
+
+        server_oauth2_form_error!(&server, audit, &client, &template, async {
+            let output = match query {
+                AuthEmailUpdateQueryParse::Request => {
+                    let id = server.request_identity_required(&mut audit, &req).await?;
+                    let (password, email_new) = body.into_inner()?;
+
+                    server
+                        .user_email_update_request(&mut audit, &client, id, password, email_new)
+                        .await?;
+
+                    TEMPLATE_AUTH_EMAIL_UPDATE_OK
+                }
+                AuthEmailUpdateQueryParse::Accept(code) => {
+                    server
+                        .user_email_update_accept(&mut audit, &client, code)
+                        .await?;
 
-            server
-                .user_email_update(user_id, password, email_new)
-                .await?;
+                    TEMPLATE_AUTH_EMAIL_UPDATE_ACCEPT_OK
+                }
+                AuthEmailUpdateQueryParse::Revert(code) => {
+                    server
+                        .user_email_update_revert(&mut audit, &client, code)
+                        .await?;
 
-            server.response_template(&client, TEMPLATE_AUTH_EMAIL_UPDATE_OK)
+                    server.session_logout(&req).await?;
+
+                    TEMPLATE_AUTH_EMAIL_UPDATE_REVERT_OK
+                }
+            };
+
+            server.response_template(&client, output)
         })
     })
 }
 
 impl RequestAuthEmailUpdate {
-    fn into_inner(self) -> oauth2::Result<(String, String, String)> {
-        if self.email_new != self.email_confirm {
+    fn into_inner(self) -> oauth2::Result<(String, String)> {
+        let password = if let Some(password) = self.password {
+            password
+        } else {
+            return Err(oauth2::ErrorResponse::invalid_request(
+                "password is required",
+            ));
+        };
+        let email_new = if let Some(email_new) = self.email_new {
+            email_new
+        } else {
+            return Err(oauth2::ErrorResponse::invalid_request(
+                "email_new is required",
+            ));
+        };
+        if Some(&email_new) != self.email_confirm.as_ref() {
             return Err(oauth2::ErrorResponse::invalid_request(
                 "email_new does not match email_confirm",
             ));
         }
-        Ok((self.csrf_token, self.password, self.email_new))
+        Ok((password, email_new))
+    }
+}
+
+enum AuthEmailUpdateQueryParse {
+    Request,
+    Accept(String),
+    Revert(String),
+}
+
+impl AuthEmailUpdateQueryParse {
+    fn template_get(&self) -> &'static str {
+        match self {
+            Self::Request => TEMPLATE_AUTH_EMAIL_UPDATE,
+            Self::Accept(_) => TEMPLATE_AUTH_EMAIL_UPDATE_ACCEPT,
+            Self::Revert(_) => TEMPLATE_AUTH_EMAIL_UPDATE_REVERT,
+        }
+    }
+
+    fn parse(
+        query: &RequestAuthEmailUpdateQuery,
+    ) -> oauth2::Result<(Option<AuthClientId>, Option<String>, Self)> {
+        let client_id = if let Some(client_id) = query.client_id {
+            let redirect_uri = if let Some(redirect_uri) = query.redirect_uri.as_deref() {
+                match Url::parse(redirect_uri) {
+                    Ok(redirect_uri) => redirect_uri,
+                    Err(_e) => {
+                        return Err(oauth2::ErrorResponse::invalid_request(
+                            "redirect_uri is invalid",
+                        ));
+                    }
+                }
+            } else {
+                return Err(oauth2::ErrorResponse::invalid_request(
+                    "redirect_uri is required",
+                ));
+            };
+            Some(AuthClientId {
+                client_id,
+                redirect_uri,
+                message: None,
+            })
+        } else {
+            None
+        };
+
+        let response_type = if let Some(response_type) = query.response_type.as_deref() {
+            response_type
+        } else {
+            return Ok((client_id, None, Self::Request));
+        };
+        let code = if let Some(code) = query.code.as_deref() {
+            code.to_string()
+        } else {
+            return Err(oauth2::ErrorResponse::invalid_request("code is required"));
+        };
+
+        match response_type {
+            "accept" => Ok((client_id, Some(code.clone()), Self::Accept(code))),
+            "revert" => Ok((client_id, Some(code.clone()), Self::Revert(code))),
+            _ => Err(oauth2::ErrorResponse::invalid_request(
+                "response_type is invalid",
+            )),
+        }
     }
 }
@@ -0,0 +1,48 @@
+use crate::http_server::internal::*;
+
+#[api_v2_operation(summary = "User email verification interface")]
+pub async fn get(
+    server: Data<HttpServer>,
+    req: HttpRequest,
+    query: Query<RequestAuthCodeQuery>,
+) -> oauth2::Result<HttpResponse> {
+    server_request!(&server, &req, async {
+        let mut audit = Audit::from_http_request("sso_auth_email_verify", &req);
+        let query = server_oauth2_validate!(&server, query);
+
+        let client = server.client_from_code(&mut audit, &query.code).await?;
+
+        server_oauth2_error!(&server, audit, &client, TEMPLATE_ERROR, async {
+            // Code is consumed by form submission so that links fetched by mail scanners remain valid
+            let context = server.template_csrf_context(&client).await?;
+
+            server.response_template_context(&client, TEMPLATE_AUTH_EMAIL_VERIFY, context)
+        })
+    })
+}
+
+#[api_v2_operation(summary = "User email verification interface")]
+pub async fn post(
+    server: Data<HttpServer>,
+    req: HttpRequest,
+    query: Query<RequestAuthCodeQuery>,
+    body: Form<RequestAuthEmailVerify>,
+) -> oauth2::Result<HttpResponse> {
+    server_request!(&server, &req, async {
+        let mut audit = Audit::from_http_request("sso_auth_email_verify", &req);
+        let query = server_oauth2_validate!(&server, query);
+        let body = server_oauth2_validate!(&server, body);
+
+        let client = server.client_from_code(&mut audit, &query.code).await?;
+
+        server.csrf_verify(&client, body.csrf_token.clone()).await?;
+
+        server_oauth2_form_error!(&server, audit, &client, TEMPLATE_AUTH_EMAIL_VERIFY, async {
+            server
+                .user_email_verify_accept(&mut audit, &client, query.code.clone())
+                .await?;
+
+            server.response_template(&client, TEMPLATE_AUTH_EMAIL_VERIFY_OK)
+        })
+    })
+}
@@ -0,0 +1,85 @@
+use crate::http_server::internal::*;
+
+#[api_v2_operation(summary = "User link OAuth2 provider interface")]
+pub async fn get(
+    server: Data<HttpServer>,
+    req: HttpRequest,
+    query: Query<RequestAuthQuery>,
+) -> oauth2::Result<HttpResponse> {
+    server_request!(&server, &req, async {
+        let mut audit = Audit::from_http_request("sso_auth_link", &req);
+        let query = server_oauth2_validate!(&server, query);
+        let query = AuthClientId::parse(query)?;
+
+        let client = server.client_from_id(&mut audit, &query).await?;
+
+        server_oauth2_error!(&server, audit, &client, TEMPLATE_ERROR, async {
+            let user_id = server.request_identity_required(&mut audit, &req).await?;
+
+            let context = server.template_link_context(&client, user_id).await?;
+
+            server.response_template_context(&client, TEMPLATE_AUTH_LINK, context)
+        })
+    })
+}
+
+#[api_v2_operation(summary = "User link OAuth2 provider interface")]
+pub async fn post(
+    server: Data<HttpServer>,
+    req: HttpRequest,
+    query: Query<RequestAuthQuery>,
+    body: Form<RequestAuthLink>,
+) -> oauth2::Result<HttpResponse> {
+    server_request!(&server, &req, async {
+        let mut audit = Audit::from_http_request("sso_auth_link", &req);
+        let query = server_oauth2_validate!(&server, query);
+        let query = AuthClientId::parse(query)?;
+        let body = server_oauth2_validate!(&server, body);
+        let (csrf_token, link_type) = body.into_inner()?;
+
+        let client = server.client_from_id(&mut audit, &query).await?;
+
+        server.csrf_verify(&client, csrf_token).await?;
+
+        server_oauth2_form_error!(&server, audit, &client, TEMPLATE_AUTH_LINK, async {
+            let user_id = server.request_identity_required(&mut audit, &req).await?;
+
+            match link_type {
+                AuthLinkType::Link(provider) => {
+                    let uri = server
+                        .oauth2_provider_redirect_link_request(
+                            &mut audit, &client, user_id, provider,
+                        )
+                        .await?;
+
+                    Ok(server.response_redirect(uri))
+                }
+                AuthLinkType::Unlink(provider) => {
+                    server
+                        .oauth2_provider_unlink(&mut audit, user_id, provider)
+                        .await?;
+
+                    Ok(server.response_redirect(server.uri_auth_link(&client)))
+                }
+            }
+        })
+    })
+}
+
+enum AuthLinkType {
+    Link(PostgresOauth2Provider),
+    Unlink(PostgresOauth2Provider),
+}
+
+impl RequestAuthLink {
+    fn into_inner(self) -> oauth2::Result<(String, AuthLinkType)> {
+        let provider = PostgresOauth2Provider::from_str(&self.oauth2_provider)?;
+        match self.link_type.as_ref() {
+            "link" => Ok((self.csrf_token, AuthLinkType::Link(provider))),
+            "unlink" => Ok((self.csrf_token, AuthLinkType::Unlink(provider))),
+            _ => Err(oauth2::ErrorResponse::invalid_request(
+                "link_type is invalid",
+            )),
+        }
+    }
+}
@@ -13,8 +13,7 @@ pub async fn get(
 
         let client = server.client_from_id(&mut audit, &query).await?;
 
-        let ident = server.request_identity(&req).await;
-        ident.forget();
+        server.session_logout(&req).await?;
 
         server.response_template(&client, TEMPLATE_AUTH_LOGOUT)
     })
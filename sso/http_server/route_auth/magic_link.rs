@@ -0,0 +1,83 @@
+use crate::http_server::internal::*;
+
+#[api_v2_operation(summary = "User magic link login interface")]
+pub async fn get(
+    server: Data<HttpServer>,
+    req: HttpRequest,
+    query: Query<RequestAuthCodeQuery>,
+) -> oauth2::Result<HttpResponse> {
+    server_request!(&server, &req, async {
+        let mut audit = Audit::from_http_request("sso_auth_magic_link", &req);
+        let query = server_oauth2_validate!(&server, query);
+
+        let client = server.client_from_code(&mut audit, &query.code).await?;
+
+        server_oauth2_error!(&server, audit, &client, TEMPLATE_ERROR, async {
+            // Code is consumed by form submission so that links fetched by mail scanners remain valid
+            let context = server.template_csrf_context(&client).await?;
+
+            server.response_template_context(&client, TEMPLATE_AUTH_MAGIC_LINK_ACCEPT, context)
+        })
+    })
+}
+
+#[api_v2_operation(summary = "User magic link login interface")]
+pub async fn post(
+    server: Data<HttpServer>,
+    req: HttpRequest,
+    query: Query<RequestAuthCodeQuery>,
+    body: Form<RequestAuthMagicLink>,
+) -> oauth2::Result<HttpResponse> {
+    server_request!(&server, &req, async {
+        let mut audit = Audit::from_http_request("sso_auth_magic_link", &req);
+        let query = server_oauth2_validate!(&server, query);
+        let body = server_oauth2_validate!(&server, body);
+
+        let client = server.client_from_code(&mut audit, &query.code).await?;
+
+        server.csrf_verify(&client, body.csrf_token.clone()).await?;
+
+        server_oauth2_form_error!(
+            &server,
+            audit,
+            &client,
+            TEMPLATE_AUTH_MAGIC_LINK_ACCEPT,
+            async {
+                let (user_id, oauth2_request, action) = server
+                    .user_magic_link_login(&mut audit, &client, query.code.clone())
+                    .await?;
+
+                let redirect_uri = match action {
+                    LoginAction::Login => {
+                        oauth2_request.user_redirect_uri(client.server_authorize_uri())
+                    }
+                    LoginAction::RequireUpdate => server
+                        .uri_auth_password_update(&client, Some("Password update is required")),
+                    LoginAction::RequireMfa(code) => {
+                        // Second factor form is submitted to authorize endpoint
+                        let action =
+                            oauth2_request.user_redirect_uri(client.server_authorize_uri());
+                        let context = server
+                            .template_mfa_context(&client, user_id, code, Some(action))
+                            .await?;
+
+                        return server.response_template_context(
+                            &client,
+                            TEMPLATE_AUTH_MFA,
+                            context,
+                        );
+                    }
+                };
+
+                server
+                    .user_device_login(&mut audit, &client, user_id)
+                    .await?;
+                server
+                    .session_login(&mut audit, &req, user_id, vec!["magic_link".to_string()])
+                    .await?;
+
+                Ok(server.response_redirect(redirect_uri))
+            }
+        )
+    })
+}
@@ -1,6 +1,13 @@
+pub mod account;
 pub mod delete;
 pub mod email_update;
+pub mod email_verify;
+pub mod link;
 pub mod logout;
+pub mod magic_link;
 pub mod password_reset;
 pub mod password_update;
+pub mod recovery;
 pub mod register;
+pub mod totp;
+pub mod webauthn;
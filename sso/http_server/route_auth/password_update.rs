@@ -14,11 +14,14 @@ pub async fn get(
         let client = server.client_from_id(&mut audit, &query).await?;
 
         server_oauth2_error!(&server, audit, &client, TEMPLATE_ERROR, async {
-            let _id = server.request_identity_required(&mut audit, &req).await?;
+            let user_id = server.request_identity_required(&mut audit, &req).await?;
 
-            let context = server
+            let mut context = server
                 .template_csrf_message_context(&client, query.message)
                 .await?;
+            if let Some(expires_at) = server.user_password_expires_at(&client, user_id).await? {
+                context.password_expiry(expires_at);
+            }
 
             server.response_template_context(&client, TEMPLATE_AUTH_PASSWORD_UPDATE, context)
         })
@@ -49,10 +52,20 @@ pub async fn post(
             &client,
             TEMPLATE_AUTH_PASSWORD_UPDATE,
             async {
-                let user_id = server.request_identity_required(&mut audit, &req).await?;
+                let session = server.request_session_required(&mut audit, &req).await?;
 
                 server
-                    .user_password_update(&mut audit, user_id, password, password_new)
+                    .user_password_update(
+                        &mut audit,
+                        &client,
+                        session.user_id,
+                        password,
+                        password_new,
+                    )
+                    .await?;
+                // Sessions of user are revoked by password update, current session is replaced
+                server
+                    .session_login(&mut audit, &req, session.user_id, session.methods)
                     .await?;
 
                 server.response_template(&client, TEMPLATE_AUTH_PASSWORD_UPDATE_OK)
@@ -0,0 +1,54 @@
+use crate::http_server::internal::*;
+
+#[api_v2_operation(summary = "User recovery codes interface")]
+pub async fn get(
+    server: Data<HttpServer>,
+    req: HttpRequest,
+    query: Query<RequestAuthQuery>,
+) -> oauth2::Result<HttpResponse> {
+    server_request!(&server, &req, async {
+        let mut audit = Audit::from_http_request("sso_auth_recovery", &req);
+        let query = server_oauth2_validate!(&server, query);
+        let query = AuthClientId::parse(query)?;
+
+        let client = server.client_from_id(&mut audit, &query).await?;
+
+        server_oauth2_error!(&server, audit, &client, TEMPLATE_ERROR, async {
+            let user_id = server.request_identity_required(&mut audit, &req).await?;
+
+            let context = server.template_recovery_context(&client, user_id).await?;
+
+            server.response_template_context(&client, TEMPLATE_AUTH_RECOVERY, context)
+        })
+    })
+}
+
+#[api_v2_operation(summary = "User recovery codes interface")]
+pub async fn post(
+    server: Data<HttpServer>,
+    req: HttpRequest,
+    query: Query<RequestAuthQuery>,
+    body: Form<RequestAuthRecovery>,
+) -> oauth2::Result<HttpResponse> {
+    server_request!(&server, &req, async {
+        let mut audit = Audit::from_http_request("sso_auth_recovery", &req);
+        let query = server_oauth2_validate!(&server, query);
+        let query = AuthClientId::parse(query)?;
+        let body = server_oauth2_validate!(&server, body);
+
+        let client = server.client_from_id(&mut audit, &query).await?;
+
+        server.csrf_verify(&client, body.csrf_token).await?;
+
+        server_oauth2_form_error!(&server, audit, &client, TEMPLATE_AUTH_RECOVERY, async {
+            let user_id = server.request_identity_required(&mut audit, &req).await?;
+
+            let codes = server.user_recovery_generate(&mut audit, user_id).await?;
+
+            let mut context = server.template_context(&client);
+            context.recovery_codes(codes);
+
+            server.response_template_context(&client, TEMPLATE_AUTH_RECOVERY_OK, context)
+        })
+    })
+}
@@ -14,7 +14,11 @@ pub async fn get(
         let client = server.client_from_code(&mut audit, query.code()).await?;
 
         server_oauth2_error!(&server, audit, &client, TEMPLATE_ERROR, async {
-            let context = server.template_csrf_context(&client).await?;
+            let mut context = server.template_csrf_context(&client).await?;
+            if let AuthRegisterQueryParse::Invite(code) = &query {
+                let code = server.user_invite_read(&client, code).await?;
+                context.invite(code.state, code.email);
+            }
 
             let template = query.template_get();
 
@@ -50,12 +54,19 @@ pub async fn post(
 
                     match request {
                         AuthRegisterFormParse::Password(args) => {
-                            let user_id = server.request_identity(&req).await;
-
                             let id = server
                                 .user_register_accept_password(&mut audit, &client, code, args)
                                 .await?;
-                            user_id.remember(id);
+                            if client.register_approval {
+                                // User is disabled until registration is approved
+                                return server.response_template(
+                                    &client,
+                                    TEMPLATE_AUTH_REGISTER_ACCEPT_PENDING,
+                                );
+                            }
+                            server
+                                .session_login(&mut audit, &req, id, vec!["register".to_string()])
+                                .await?;
 
                             server.response_template(&client, TEMPLATE_AUTH_REGISTER_ACCEPT_OK)
                         }
@@ -70,6 +81,31 @@ pub async fn post(
                         }
                     }
                 }
+                AuthRegisterQueryParse::Invite(code) => {
+                    let request = AuthRegisterFormParse::parse(body)?;
+
+                    match request {
+                        AuthRegisterFormParse::Password(args) => {
+                            let id = server
+                                .user_invite_accept_password(&mut audit, &client, code, args)
+                                .await?;
+                            server
+                                .session_login(&mut audit, &req, id, vec!["register".to_string()])
+                                .await?;
+
+                            server.response_template(&client, TEMPLATE_AUTH_REGISTER_ACCEPT_OK)
+                        }
+                        AuthRegisterFormParse::Oauth2(args) => {
+                            let redirect_uri = server
+                                .oauth2_provider_redirect_invite_request(
+                                    &mut audit, &client, code, args,
+                                )
+                                .await?;
+
+                            Ok(server.response_redirect(redirect_uri))
+                        }
+                    }
+                }
                 AuthRegisterQueryParse::Reject(code) => {
                     server
                         .user_register_reject(&mut audit, &client, code)
@@ -84,6 +120,7 @@ pub async fn post(
 
 enum AuthRegisterQueryParse {
     Accept(String),
+    Invite(String),
     Reject(String),
 }
 
@@ -96,6 +133,7 @@ impl AuthRegisterQueryParse {
     fn template_get(&self) -> &'static str {
         match self {
             Self::Accept(_) => TEMPLATE_AUTH_REGISTER_ACCEPT,
+            Self::Invite(_) => TEMPLATE_AUTH_REGISTER_INVITE,
             Self::Reject(_) => TEMPLATE_AUTH_REGISTER_REJECT,
         }
     }
@@ -103,6 +141,7 @@ impl AuthRegisterQueryParse {
     fn code(&self) -> &str {
         match self {
             Self::Accept(code) => code,
+            Self::Invite(code) => code,
             Self::Reject(code) => code,
         }
     }
@@ -110,6 +149,7 @@ impl AuthRegisterQueryParse {
     fn parse(req: RequestAuthResponseQuery) -> oauth2::Result<Self> {
         match req.response_type.as_ref() {
             "accept" => Ok(Self::Accept(req.code)),
+            "invite" => Ok(Self::Invite(req.code)),
             "reject" => Ok(Self::Reject(req.code)),
             _ => Err(oauth2::ErrorResponse::invalid_request(
                 "response_type is invalid",
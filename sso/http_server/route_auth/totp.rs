@@ -0,0 +1,80 @@
+use crate::http_server::internal::*;
+
+#[api_v2_operation(summary = "User TOTP enrolment interface")]
+pub async fn get(
+    server: Data<HttpServer>,
+    req: HttpRequest,
+    query: Query<RequestAuthQuery>,
+) -> oauth2::Result<HttpResponse> {
+    server_request!(&server, &req, async {
+        let mut audit = Audit::from_http_request("sso_auth_totp", &req);
+        let query = server_oauth2_validate!(&server, query);
+        let query = AuthClientId::parse(query)?;
+
+        let client = server.client_from_id(&mut audit, &query).await?;
+
+        server_oauth2_error!(&server, audit, &client, TEMPLATE_ERROR, async {
+            let user_id = server.request_identity_required(&mut audit, &req).await?;
+
+            let context = server.template_totp_context(&client, user_id).await?;
+
+            server.response_template_context(&client, TEMPLATE_AUTH_TOTP_ENROL, context)
+        })
+    })
+}
+
+#[api_v2_operation(summary = "User TOTP enrolment interface")]
+pub async fn post(
+    server: Data<HttpServer>,
+    req: HttpRequest,
+    query: Query<RequestAuthQuery>,
+    body: Form<RequestAuthTotp>,
+) -> oauth2::Result<HttpResponse> {
+    server_request!(&server, &req, async {
+        let mut audit = Audit::from_http_request("sso_auth_totp", &req);
+        let query = server_oauth2_validate!(&server, query);
+        let query = AuthClientId::parse(query)?;
+        let body = server_oauth2_validate!(&server, body);
+        let (csrf_token, totp_type) = body.into_inner()?;
+
+        let client = server.client_from_id(&mut audit, &query).await?;
+
+        server.csrf_verify(&client, csrf_token).await?;
+
+        server_oauth2_form_error!(&server, audit, &client, TEMPLATE_AUTH_TOTP_ENROL, async {
+            let user_id = server.request_identity_required(&mut audit, &req).await?;
+
+            let mut context = server.template_context(&client);
+            match totp_type {
+                AuthTotpType::Enable(totp) => {
+                    let codes = server.user_totp_enable(&mut audit, user_id, &totp).await?;
+                    if let Some(codes) = codes {
+                        context.recovery_codes(codes);
+                    }
+                }
+                AuthTotpType::Disable(totp) => {
+                    server.user_totp_disable(&mut audit, user_id, &totp).await?;
+                }
+            }
+
+            server.response_template_context(&client, TEMPLATE_AUTH_TOTP_ENROL_OK, context)
+        })
+    })
+}
+
+enum AuthTotpType {
+    Enable(String),
+    Disable(String),
+}
+
+impl RequestAuthTotp {
+    fn into_inner(self) -> oauth2::Result<(String, AuthTotpType)> {
+        match self.totp_type.as_ref() {
+            "enable" => Ok((self.csrf_token, AuthTotpType::Enable(self.totp))),
+            "disable" => Ok((self.csrf_token, AuthTotpType::Disable(self.totp))),
+            _ => Err(oauth2::ErrorResponse::invalid_request(
+                "totp_type is invalid",
+            )),
+        }
+    }
+}
@@ -0,0 +1,114 @@
+use crate::http_server::internal::*;
+
+#[api_v2_operation(summary = "User passkey interface")]
+pub async fn get(
+    server: Data<HttpServer>,
+    req: HttpRequest,
+    query: Query<RequestAuthQuery>,
+) -> oauth2::Result<HttpResponse> {
+    server_request!(&server, &req, async {
+        let mut audit = Audit::from_http_request("sso_auth_webauthn", &req);
+        let query = server_oauth2_validate!(&server, query);
+        let query = AuthClientId::parse(query)?;
+
+        let client = server.client_from_id(&mut audit, &query).await?;
+
+        server_oauth2_error!(&server, audit, &client, TEMPLATE_ERROR, async {
+            let user_id = server.request_identity_required(&mut audit, &req).await?;
+
+            let context = server.template_webauthn_context(&client, user_id).await?;
+
+            server.response_template_context(&client, TEMPLATE_AUTH_WEBAUTHN, context)
+        })
+    })
+}
+
+#[api_v2_operation(summary = "User passkey interface")]
+pub async fn post(
+    server: Data<HttpServer>,
+    req: HttpRequest,
+    query: Query<RequestAuthQuery>,
+    body: Form<RequestAuthWebauthn>,
+) -> oauth2::Result<HttpResponse> {
+    server_request!(&server, &req, async {
+        let mut audit = Audit::from_http_request("sso_auth_webauthn", &req);
+        let query = server_oauth2_validate!(&server, query);
+        let query = AuthClientId::parse(query)?;
+        let body = server_oauth2_validate!(&server, body);
+        let (csrf_token, webauthn_type) = body.into_inner()?;
+
+        let client = server.client_from_id(&mut audit, &query).await?;
+
+        server.csrf_verify(&client, csrf_token).await?;
+
+        server_oauth2_form_error!(&server, audit, &client, TEMPLATE_AUTH_WEBAUTHN, async {
+            let user_id = server.request_identity_required(&mut audit, &req).await?;
+
+            let mut context = server.template_context(&client);
+            match webauthn_type {
+                AuthWebauthnType::Register(args) => {
+                    let codes = server
+                        .user_webauthn_register(&mut audit, &client, user_id, args)
+                        .await?;
+                    if let Some(codes) = codes {
+                        context.recovery_codes(codes);
+                    }
+                }
+                AuthWebauthnType::Delete(credential_id) => {
+                    server
+                        .user_webauthn_delete(&mut audit, user_id, &credential_id)
+                        .await?;
+                }
+            }
+
+            server.response_template_context(&client, TEMPLATE_AUTH_WEBAUTHN_OK, context)
+        })
+    })
+}
+
+enum AuthWebauthnType {
+    Register(UserWebauthnRegisterArgs),
+    Delete(String),
+}
+
+impl RequestAuthWebauthn {
+    fn into_inner(self) -> oauth2::Result<(String, AuthWebauthnType)> {
+        match self.webauthn_type.as_ref() {
+            "register" => {
+                let (challenge, client_data, attestation_object) =
+                    match (self.challenge, self.client_data, self.attestation_object) {
+                        (Some(challenge), Some(client_data), Some(attestation_object)) => {
+                            (challenge, client_data, attestation_object)
+                        }
+                        _ => {
+                            return Err(oauth2::ErrorResponse::invalid_request(
+                                "webauthn response is required",
+                            ))
+                        }
+                    };
+                Ok((
+                    self.csrf_token,
+                    AuthWebauthnType::Register(UserWebauthnRegisterArgs {
+                        name: self.name.unwrap_or_default(),
+                        challenge,
+                        client_data,
+                        attestation_object,
+                    }),
+                ))
+            }
+            "delete" => {
+                let credential_id = if let Some(credential_id) = self.credential_id {
+                    credential_id
+                } else {
+                    return Err(oauth2::ErrorResponse::invalid_request(
+                        "credential_id is required",
+                    ));
+                };
+                Ok((self.csrf_token, AuthWebauthnType::Delete(credential_id)))
+            }
+            _ => Err(oauth2::ErrorResponse::invalid_request(
+                "webauthn_type is invalid",
+            )),
+        }
+    }
+}
@@ -22,16 +22,32 @@ pub async fn get(
         let client = server.client_from_id(&mut audit, &client_id).await?;
 
         server_oauth2_error!(&server, audit, &client, TEMPLATE_ERROR, async {
-            let ident = server.request_identity(&req).await;
+            let id = match server.request_session(&req).await? {
+                Some(session) if server.user_mfa_missing(&client, session.user_id).await? => {
+                    // Client requires MFA, user must log in again to enrol a factor
+                    server.session_logout(&req).await?;
+                    None
+                }
+                Some(session)
+                    if server
+                        .user_email_unverified(&client, session.user_id)
+                        .await? =>
+                {
+                    // Client requires verified email, user must log in again to be sent a link
+                    server.session_logout(&req).await?;
+                    None
+                }
+                session => session.map(|x| x.user_id),
+            };
 
-            if let Some(id) = ident.identity() {
+            if let Some(id) = id {
                 let redirect_uri = server
-                    .oauth2_authorization_code(&mut audit, &client, request, id)
+                    .oauth2_authorization_code(&mut audit, &client, request, id.to_string())
                     .await?;
 
                 Ok(server.response_redirect(redirect_uri))
             } else {
-                let context = server.template_csrf_context(&client).await?;
+                let context = server.template_auth_context(&client).await?;
 
                 server.response_template_context(&client, TEMPLATE_AUTH, context)
             }
@@ -69,23 +85,110 @@ pub async fn post(
         server_oauth2_form_error!(&server, audit, &client, TEMPLATE_AUTH, async {
             match request {
                 AuthorizeFormParse::PasswordLogin(request) => {
-                    let ident = server.request_identity(&req).await;
-                    let (user_id, action) = server.user_password_login(&mut audit, request).await?;
-                    ident.remember(user_id);
-
-                    let redirect_uri = match action {
-                        LoginAction::Login => {
-                            oauth2_request.user_redirect_uri(client.server_authorize_uri())
-                        }
-                        LoginAction::RequireUpdate => server
-                            .uri_auth_password_update(&client, Some("Password update is required")),
-                    };
+                    if let Some(provider) = server.oauth2_provider_home_realm(&request.email) {
+                        audit.set_data("home_realm", json!(provider.as_str()));
 
-                    Ok(server.response_redirect(redirect_uri))
+                        let redirect_uri = server
+                            .oauth2_provider_redirect_request(
+                                &mut audit,
+                                &client,
+                                provider,
+                                oauth2_request,
+                            )
+                            .await?;
+
+                        return Ok(server.response_redirect(redirect_uri));
+                    }
+                    if request.password.is_empty() {
+                        return Err(oauth2::ErrorResponse::invalid_request(
+                            "password is required",
+                        ));
+                    }
+
+                    let (user_id, action) = server
+                        .response_pad(server.user_password_login(&mut audit, &client, request))
+                        .await?;
+
+                    login_response(
+                        &server,
+                        &mut audit,
+                        &req,
+                        &client,
+                        oauth2_request,
+                        user_id,
+                        vec!["password".to_string()],
+                        action,
+                    )
+                    .await
+                }
+                AuthorizeFormParse::Totp(code, totp) => {
+                    let (user_id, methods, action) = server
+                        .user_totp_login(&mut audit, &client, code, totp)
+                        .await?;
+
+                    login_response(
+                        &server,
+                        &mut audit,
+                        &req,
+                        &client,
+                        oauth2_request,
+                        user_id,
+                        methods,
+                        action,
+                    )
+                    .await
+                }
+                AuthorizeFormParse::Recovery(code, recovery_code) => {
+                    let (user_id, methods, action) = server
+                        .user_recovery_login(&mut audit, &client, code, recovery_code)
+                        .await?;
+
+                    login_response(
+                        &server,
+                        &mut audit,
+                        &req,
+                        &client,
+                        oauth2_request,
+                        user_id,
+                        methods,
+                        action,
+                    )
+                    .await
+                }
+                AuthorizeFormParse::Webauthn(code, args) => {
+                    let (user_id, methods, action) = server
+                        .user_webauthn_login(&mut audit, &client, code, args)
+                        .await?;
+
+                    login_response(
+                        &server,
+                        &mut audit,
+                        &req,
+                        &client,
+                        oauth2_request,
+                        user_id,
+                        methods,
+                        action,
+                    )
+                    .await
+                }
+                AuthorizeFormParse::MagicLink(email) => {
+                    server
+                        .response_pad(server.user_magic_link_request(
+                            &mut audit,
+                            &client,
+                            email,
+                            oauth2_request,
+                        ))
+                        .await?;
+
+                    server.response_template(&client, TEMPLATE_AUTH_MAGIC_LINK)
                 }
                 AuthorizeFormParse::PasswordReset(email) => {
                     server
-                        .user_password_reset_request(&mut audit, &client, email)
+                        .response_pad(
+                            server.user_password_reset_request(&mut audit, &client, email),
+                        )
                         .await?;
 
                     server.response_template(&client, TEMPLATE_AUTH_PASSWORD_RESET)
@@ -104,7 +207,7 @@ pub async fn post(
                 }
                 AuthorizeFormParse::Register(email) => {
                     server
-                        .user_register_request(&mut audit, &client, email)
+                        .response_pad(server.user_register_request(&mut audit, &client, email))
                         .await?;
 
                     server.response_template(&client, TEMPLATE_AUTH_REGISTER)
@@ -114,11 +217,50 @@ pub async fn post(
     })
 }
 
+#[allow(clippy::too_many_arguments)]
+async fn login_response(
+    server: &HttpServer,
+    audit: &mut Audit,
+    req: &HttpRequest,
+    client: &Client,
+    oauth2_request: oauth2::AuthorizationCodeRequest,
+    user_id: Uuid,
+    methods: Vec<String>,
+    action: LoginAction,
+) -> oauth2::Result<HttpResponse> {
+    server
+        .user_email_verified_check(audit, client, user_id)
+        .await?;
+
+    let redirect_uri = match action {
+        LoginAction::Login => oauth2_request.user_redirect_uri(client.server_authorize_uri()),
+        LoginAction::RequireUpdate => {
+            server.uri_auth_password_update(client, Some("Password update is required"))
+        }
+        LoginAction::RequireMfa(code) => {
+            let context = server
+                .template_mfa_context(client, user_id, code, None)
+                .await?;
+
+            return server.response_template_context(client, TEMPLATE_AUTH_MFA, context);
+        }
+    };
+
+    server.user_device_login(audit, client, user_id).await?;
+    server.session_login(audit, req, user_id, methods).await?;
+
+    Ok(server.response_redirect(redirect_uri))
+}
+
 enum AuthorizeFormParse {
     PasswordLogin(UserLoginArgs),
     PasswordReset(String),
+    MagicLink(String),
     Oauth2(PostgresOauth2Provider),
     Register(String),
+    Totp(String, String),
+    Recovery(String, String),
+    Webauthn(Option<String>, UserWebauthnLoginArgs),
 }
 
 impl AuthorizeFormParse {
@@ -130,13 +272,8 @@ impl AuthorizeFormParse {
                 } else {
                     return Err(oauth2::ErrorResponse::invalid_request("email is required"));
                 };
-                let password = if let Some(password) = req.password.as_deref() {
-                    password.to_string()
-                } else {
-                    return Err(oauth2::ErrorResponse::invalid_request(
-                        "password is required",
-                    ));
-                };
+                // Password is checked after home realm discovery, which only requires email
+                let password = req.password.unwrap_or_default();
                 Ok((
                     req.csrf_token,
                     Self::PasswordLogin(UserLoginArgs { email, password }),
@@ -150,6 +287,14 @@ impl AuthorizeFormParse {
                 };
                 Ok((req.csrf_token, Self::PasswordReset(email)))
             }
+            "magic_link" => {
+                let email = if let Some(email) = req.email.as_deref() {
+                    email.to_string()
+                } else {
+                    return Err(oauth2::ErrorResponse::invalid_request("email is required"));
+                };
+                Ok((req.csrf_token, Self::MagicLink(email)))
+            }
             "oauth2" => {
                 let oauth2_provider = if let Some(oauth2_provider) = req.oauth2_provider.as_deref()
                 {
@@ -161,6 +306,49 @@ impl AuthorizeFormParse {
                 };
                 Ok((req.csrf_token, Self::Oauth2(oauth2_provider)))
             }
+            "totp" => {
+                let code = if let Some(code) = req.code.as_deref() {
+                    code.to_string()
+                } else {
+                    return Err(oauth2::ErrorResponse::invalid_request("code is required"));
+                };
+                let totp = if let Some(totp) = req.totp.as_deref() {
+                    totp.to_string()
+                } else {
+                    return Err(oauth2::ErrorResponse::invalid_request("totp is required"));
+                };
+                Ok((req.csrf_token, Self::Totp(code, totp)))
+            }
+            "recovery" => {
+                let code = if let Some(code) = req.code.as_deref() {
+                    code.to_string()
+                } else {
+                    return Err(oauth2::ErrorResponse::invalid_request("code is required"));
+                };
+                let recovery_code = if let Some(recovery_code) = req.recovery_code.as_deref() {
+                    recovery_code.to_string()
+                } else {
+                    return Err(oauth2::ErrorResponse::invalid_request(
+                        "recovery_code is required",
+                    ));
+                };
+                Ok((req.csrf_token, Self::Recovery(code, recovery_code)))
+            }
+            "webauthn" => {
+                let required = |x: Option<String>, name: &str| {
+                    x.ok_or_else(|| {
+                        oauth2::ErrorResponse::invalid_request(format!("{} is required", name))
+                    })
+                };
+                let args = UserWebauthnLoginArgs {
+                    challenge: required(req.challenge, "challenge")?,
+                    credential_id: required(req.credential_id, "credential_id")?,
+                    client_data: required(req.client_data, "client_data")?,
+                    authenticator_data: required(req.authenticator_data, "authenticator_data")?,
+                    signature: required(req.signature, "signature")?,
+                };
+                Ok((req.csrf_token, Self::Webauthn(req.code, args)))
+            }
             "register" => {
                 let email = if let Some(email) = req.email.as_deref() {
                     email.to_string()
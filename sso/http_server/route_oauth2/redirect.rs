@@ -15,24 +15,77 @@ pub async fn get(
             .await?;
 
         server_oauth2_error!(&server, audit, &client, TEMPLATE_ERROR, async {
-            let user_id = server.request_identity(&req).await;
-
-            let (id, redirect_request) = server
-                .oauth2_provider_redirect_response(&mut audit, &client, query)
+            let session = server.request_session(&req).await?;
+            let (user_id, redirect_request) = server
+                .oauth2_provider_redirect_response(
+                    &mut audit,
+                    &client,
+                    session.map(|x| x.user_id),
+                    query,
+                )
                 .await?;
-            user_id.remember(id.clone());
 
             match redirect_request {
                 Oauth2Redirect::Auth(oauth2_request) => {
+                    server
+                        .user_email_verified_check(&mut audit, &client, user_id)
+                        .await?;
+
+                    if let Some(code) = server.user_mfa_code(&client, user_id, "oauth2").await? {
+                        // Second factor form is submitted to authorize endpoint
+                        let action =
+                            oauth2_request.user_redirect_uri(client.server_authorize_uri());
+                        let context = server
+                            .template_mfa_context(&client, user_id, code, Some(action))
+                            .await?;
+
+                        return server.response_template_context(
+                            &client,
+                            TEMPLATE_AUTH_MFA,
+                            context,
+                        );
+                    }
+                    server
+                        .user_device_login(&mut audit, &client, user_id)
+                        .await?;
+                    server
+                        .session_login(&mut audit, &req, user_id, vec!["oauth2".to_string()])
+                        .await?;
+
                     let redirect_uri = server
-                        .oauth2_authorization_code(&mut audit, &client, oauth2_request, id)
+                        .oauth2_authorization_code(
+                            &mut audit,
+                            &client,
+                            oauth2_request,
+                            user_id.to_string(),
+                        )
                         .await?;
 
                     Ok(server.response_redirect(redirect_uri))
                 }
                 Oauth2Redirect::Register => {
+                    if client.register_approval {
+                        // User is disabled until registration is approved
+                        return server
+                            .response_template(&client, TEMPLATE_AUTH_REGISTER_ACCEPT_PENDING);
+                    }
+                    server
+                        .session_login(&mut audit, &req, user_id, vec!["register".to_string()])
+                        .await?;
+
                     server.response_template(&client, TEMPLATE_AUTH_REGISTER_ACCEPT_OK)
                 }
+                Oauth2Redirect::Invite => {
+                    server
+                        .session_login(&mut audit, &req, user_id, vec!["register".to_string()])
+                        .await?;
+
+                    server.response_template(&client, TEMPLATE_AUTH_REGISTER_ACCEPT_OK)
+                }
+                Oauth2Redirect::Link => {
+                    // Link is requested by logged in user, session is never created here
+                    server.response_template(&client, TEMPLATE_AUTH_LINK_OK)
+                }
             }
         })
     })
@@ -23,3 +23,15 @@ pub(crate) async fn password_strength_get(
             .body(include_str!("password_strength.js")))
     })
 }
+
+#[api_v2_operation()]
+pub(crate) async fn webauthn_get(
+    server: Data<HttpServer>,
+    req: HttpRequest,
+) -> HttpResult<HttpResponse> {
+    server_request!(&server, &req, async {
+        Ok(HttpResponse::Ok()
+            .content_type("text/javascript; charset=utf-8")
+            .body(include_str!("webauthn.js")))
+    })
+}
@@ -1,4 +1,4 @@
-export function password_strength(id) {
+export function password_strength(id, score_min) {
     var strength = {
         0: "Worst",
         1: "Bad",
@@ -15,5 +15,10 @@ export function password_strength(id) {
         meter.value = result.score;
         meter.title = strength[result.score] + " Password";
         warning.innerText = result.feedback.warning;
+        if (score_min && password.value && result.score < score_min) {
+            password.setCustomValidity("Password is too weak, " + strength[score_min] + " strength is required");
+        } else {
+            password.setCustomValidity("");
+        }
     });
 }
@@ -0,0 +1,109 @@
+function base64url_decode(value) {
+    var base64 = value.replace(/-/g, "+").replace(/_/g, "/");
+    var binary = atob(base64);
+    var bytes = new Uint8Array(binary.length);
+    for (var i = 0; i < binary.length; i++) {
+        bytes[i] = binary.charCodeAt(i);
+    }
+    return bytes;
+}
+
+function base64url_encode(buffer) {
+    var bytes = new Uint8Array(buffer);
+    var binary = "";
+    for (var i = 0; i < bytes.length; i++) {
+        binary += String.fromCharCode(bytes[i]);
+    }
+    return btoa(binary).replace(/\+/g, "-").replace(/\//g, "_").replace(/=+$/, "");
+}
+
+function webauthn_form(id) {
+    var form = document.getElementById(id);
+    var button = form.querySelector("button");
+    var options = JSON.parse(form.dataset.webauthn);
+
+    if (!window.PublicKeyCredential) {
+        button.disabled = true;
+        button.title = "Passkeys are not supported by this browser";
+    }
+    return { form: form, button: button, options: options };
+}
+
+function webauthn_credentials(credentials) {
+    return credentials.map(function (id) {
+        return { type: "public-key", id: base64url_decode(id) };
+    });
+}
+
+export function webauthn_register(id) {
+    var w = webauthn_form(id);
+
+    w.button.addEventListener("click", function () {
+        navigator.credentials
+            .create({
+                publicKey: {
+                    challenge: base64url_decode(w.options.challenge),
+                    rp: { id: w.options.rp_id, name: w.options.rp_name },
+                    user: {
+                        id: base64url_decode(w.options.user_id),
+                        name: w.options.user_name,
+                        displayName: w.options.user_display_name,
+                    },
+                    pubKeyCredParams: [
+                        { type: "public-key", alg: -7 },
+                        { type: "public-key", alg: -257 },
+                    ],
+                    excludeCredentials: webauthn_credentials(w.options.credentials),
+                    authenticatorSelection: {
+                        residentKey: "preferred",
+                        requireResidentKey: false,
+                        userVerification: "preferred",
+                    },
+                    attestation: "none",
+                    timeout: 60000,
+                },
+            })
+            .then(function (credential) {
+                w.form.elements["challenge"].value = w.options.challenge;
+                w.form.elements["credential_id"].value = base64url_encode(credential.rawId);
+                w.form.elements["client_data"].value = base64url_encode(credential.response.clientDataJSON);
+                w.form.elements["attestation_object"].value = base64url_encode(
+                    credential.response.attestationObject
+                );
+                w.form.submit();
+            })
+            .catch(function (err) {
+                w.button.title = err.message;
+            });
+    });
+}
+
+export function webauthn_login(id) {
+    var w = webauthn_form(id);
+
+    w.button.addEventListener("click", function () {
+        navigator.credentials
+            .get({
+                publicKey: {
+                    challenge: base64url_decode(w.options.challenge),
+                    rpId: w.options.rp_id,
+                    allowCredentials: webauthn_credentials(w.options.credentials),
+                    userVerification: w.options.user_verification,
+                    timeout: 60000,
+                },
+            })
+            .then(function (credential) {
+                w.form.elements["challenge"].value = w.options.challenge;
+                w.form.elements["credential_id"].value = base64url_encode(credential.rawId);
+                w.form.elements["client_data"].value = base64url_encode(credential.response.clientDataJSON);
+                w.form.elements["authenticator_data"].value = base64url_encode(
+                    credential.response.authenticatorData
+                );
+                w.form.elements["signature"].value = base64url_encode(credential.response.signature);
+                w.form.submit();
+            })
+            .catch(function (err) {
+                w.button.title = err.message;
+            });
+    });
+}
@@ -12,6 +12,13 @@ pub(crate) async fn post(
 
         let client = server.client_required(auth).await?;
 
+        if let Some(password) = &body.password {
+            server
+                .password_policy_check(&password.password, &[&body.email, &body.name])
+                .await
+                .map_err(HttpError::bad_request)?;
+        }
+
         let res = server.postgres.user_access_insert(&client, body).await;
 
         server.response_json(res)
@@ -1,4 +1,6 @@
 pub mod create;
 pub mod delete;
+pub mod invite;
 pub mod read;
+pub mod unlock;
 pub mod update;
@@ -0,0 +1,22 @@
+use crate::http_server::internal::*;
+
+#[api_v2_operation(summary = "Unlock user locked by failed logins")]
+pub(crate) async fn post(
+    server: Data<HttpServer>,
+    req: HttpRequest,
+    auth: BasicAuth,
+    body: Json<RequestUserUnlock>,
+) -> HttpResult<Json<()>> {
+    server_request!(&server, &req, async {
+        let body = server_validate!(&server, body);
+
+        let client = server.client_required(auth).await?;
+
+        let mut audit = Audit::from_http_request("sso_user_unlock", &req);
+        audit.set_client(&client);
+
+        let res = server.postgres.user_unlock(audit, &body.email).await;
+
+        server.response_json(res)
+    })
+}
@@ -0,0 +1,22 @@
+use crate::http_server::internal::*;
+
+#[api_v2_operation(summary = "Approve user registration")]
+pub(crate) async fn post(
+    server: Data<HttpServer>,
+    req: HttpRequest,
+    auth: BasicAuth,
+    body: Json<RequestUserRegisterApproval>,
+) -> HttpResult<Json<()>> {
+    server_request!(&server, &req, async {
+        let body = server_validate!(&server, body);
+
+        let client = server.client_required(auth).await?;
+
+        let mut audit = Audit::from_http_request("sso_user_register_approve", &req);
+        audit.set_client(&client);
+
+        let res = server.user_register_approve(audit, &client, body).await;
+
+        server.response_json(res)
+    })
+}
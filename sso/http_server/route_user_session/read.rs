@@ -0,0 +1,19 @@
+use crate::http_server::internal::*;
+
+#[api_v2_operation(summary = "Read user sessions")]
+pub(crate) async fn post(
+    server: Data<HttpServer>,
+    req: HttpRequest,
+    auth: BasicAuth,
+    body: Json<RequestUserSessionRead>,
+) -> HttpResult<Json<ResponseUserSessionMany>> {
+    server_request!(&server, &req, async {
+        let body = server_validate!(&server, body);
+
+        let _client = server.client_required(auth).await?;
+
+        let res = server.postgres.session_read(body).await;
+
+        server.response_json(res)
+    })
+}
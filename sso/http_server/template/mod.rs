@@ -13,10 +13,103 @@ pub(crate) struct ContextLen {
     maxlength: usize,
 }
 
+#[derive(Debug, Serialize)]
+pub(crate) struct ContextPasswordPolicy {
+    minlength: usize,
+    maxlength: usize,
+    score_min: u8,
+}
+
+#[derive(Debug, Serialize)]
+pub(crate) struct ContextPasswordExpiry {
+    expired: bool,
+    expires_at: String,
+}
+
 #[derive(Debug, Serialize)]
 pub(crate) struct ContextOauth2Providers {
     sso: bool,
     microsoft: bool,
+    github: bool,
+}
+
+#[derive(Debug, Default, Serialize)]
+pub(crate) struct ContextTotp {
+    enable: bool,
+    uri: Option<String>,
+    secret: Option<String>,
+    qr: Option<String>,
+}
+
+#[derive(Debug, Serialize)]
+pub(crate) struct ContextInvite {
+    name: String,
+    email: String,
+}
+
+#[derive(Debug, Serialize)]
+pub(crate) struct ContextMfa {
+    code: String,
+    action: Option<String>,
+    recovery: bool,
+}
+
+#[derive(Debug, Default, Serialize)]
+pub(crate) struct ContextRecovery {
+    count: i64,
+    codes: Option<Vec<String>>,
+}
+
+#[derive(Debug, Serialize)]
+pub(crate) struct ContextWebauthn {
+    options: String,
+    credentials: Vec<ContextWebauthnCredential>,
+}
+
+#[derive(Debug, Serialize)]
+pub(crate) struct ContextWebauthnCredential {
+    id: String,
+    name: String,
+    created_at: String,
+}
+
+#[derive(Debug, Serialize)]
+pub(crate) struct ContextAccount {
+    name: String,
+    email: String,
+    email_verified: bool,
+    locale: String,
+    timezone: String,
+    uri_link: String,
+    uri_password_update: String,
+    sessions: Vec<ContextAccountSession>,
+    access: Vec<ContextAccountAccess>,
+    audit: Vec<ContextAccountAudit>,
+}
+
+#[derive(Debug, Serialize)]
+pub(crate) struct ContextAccountSession {
+    id: String,
+    user_agent: String,
+    remote_addr: String,
+    methods: String,
+    created_at: String,
+    last_seen_at: String,
+    current: bool,
+}
+
+#[derive(Debug, Serialize)]
+pub(crate) struct ContextAccountAccess {
+    client_name: String,
+    scope: String,
+}
+
+#[derive(Debug, Serialize)]
+pub(crate) struct ContextAccountAudit {
+    created_at: String,
+    audit_type: String,
+    ok: bool,
+    remote_addr: String,
 }
 
 #[derive(Debug, Serialize)]
@@ -26,23 +119,38 @@ pub(crate) struct Context {
     client_uri: Url,
     client_domain: String,
     client_register_enable: bool,
+    client_magic_link_enable: bool,
     csrf_token: Option<String>,
     error: Option<ContextError>,
     message: Option<String>,
     password: ContextLen,
+    password_policy: ContextPasswordPolicy,
+    password_expiry: Option<ContextPasswordExpiry>,
     email: ContextLen,
     name: ContextLen,
     oauth2_providers: ContextOauth2Providers,
+    oauth2_providers_linked: Option<ContextOauth2Providers>,
+    totp: Option<ContextTotp>,
+    mfa: Option<ContextMfa>,
+    recovery: Option<ContextRecovery>,
+    webauthn: Option<ContextWebauthn>,
+    account: Option<ContextAccount>,
+    invite: Option<ContextInvite>,
 }
 
 impl Context {
-    pub fn build(oauth2_providers: &ConfigOauth2Providers, client: &Client) -> Self {
+    pub fn build(
+        oauth2_providers: &ConfigOauth2Providers,
+        password: &ConfigPassword,
+        client: &Client,
+    ) -> Self {
         Self {
             client_id: client.client_id(),
             client_name: client.client_name(),
             client_uri: client.client_uri(),
             client_domain: client.client_uri().domain().unwrap().to_string(),
             client_register_enable: client.register_enable,
+            client_magic_link_enable: client.magic_link_enable,
             csrf_token: None,
             error: None,
             message: None,
@@ -50,6 +158,12 @@ impl Context {
                 minlength: validate::PASSWORD_MIN,
                 maxlength: validate::PASSWORD_MAX,
             },
+            password_policy: ContextPasswordPolicy {
+                minlength: password.length_min,
+                maxlength: password.length_max,
+                score_min: password.score_min,
+            },
+            password_expiry: None,
             email: ContextLen {
                 minlength: 1,
                 maxlength: 1000,
@@ -61,7 +175,15 @@ impl Context {
             oauth2_providers: ContextOauth2Providers {
                 sso: oauth2_providers.sso.is_some(),
                 microsoft: oauth2_providers.microsoft.is_some(),
+                github: oauth2_providers.github.is_some(),
             },
+            oauth2_providers_linked: None,
+            totp: None,
+            mfa: None,
+            recovery: None,
+            webauthn: None,
+            account: None,
+            invite: None,
         }
     }
 
@@ -76,6 +198,135 @@ impl Context {
     pub fn message(&mut self, message: String) {
         self.message = Some(message);
     }
+
+    pub fn password_expiry(&mut self, expires_at: DateTime<Utc>) {
+        self.password_expiry = Some(ContextPasswordExpiry {
+            expired: expires_at <= Utc::now(),
+            expires_at: expires_at.format("%Y-%m-%d").to_string(),
+        });
+    }
+
+    pub fn oauth2_providers_linked(&mut self, linked: &[ResponseUserOauth2Provider]) {
+        let is_linked = |provider: PostgresOauth2Provider| {
+            linked
+                .iter()
+                .any(|x| x.oauth2_provider == provider.as_str())
+        };
+        self.oauth2_providers_linked = Some(ContextOauth2Providers {
+            sso: is_linked(PostgresOauth2Provider::Sso),
+            microsoft: is_linked(PostgresOauth2Provider::Microsoft),
+            github: is_linked(PostgresOauth2Provider::Github),
+        });
+    }
+
+    pub fn totp(&mut self, enable: bool) {
+        self.totp.get_or_insert_with(ContextTotp::default).enable = enable;
+    }
+
+    pub fn mfa(&mut self, code: String, action: Option<Url>, recovery: bool) {
+        self.mfa = Some(ContextMfa {
+            code,
+            action: action.map(|x| x.to_string()),
+            recovery,
+        });
+    }
+
+    pub fn recovery_count(&mut self, count: i64) {
+        self.recovery
+            .get_or_insert_with(ContextRecovery::default)
+            .count = count;
+    }
+
+    pub fn recovery_codes(&mut self, codes: Vec<String>) {
+        let recovery = self.recovery.get_or_insert_with(ContextRecovery::default);
+        recovery.count = codes.len() as i64;
+        recovery.codes = Some(codes);
+    }
+
+    pub fn totp_enrol(&mut self, uri: Url, secret: String, qr: String) {
+        let totp = self.totp.get_or_insert_with(ContextTotp::default);
+        totp.uri = Some(uri.to_string());
+        totp.secret = Some(secret);
+        totp.qr = Some(qr);
+    }
+
+    pub fn account(&mut self, account: ContextAccount) {
+        self.account = Some(account);
+    }
+
+    pub fn invite(&mut self, name: String, email: String) {
+        self.invite = Some(ContextInvite { name, email });
+    }
+
+    pub fn webauthn(&mut self, options: Value, credentials: &[PostgresUserWebauthn]) {
+        self.webauthn = Some(ContextWebauthn {
+            options: options.to_string(),
+            credentials: credentials
+                .iter()
+                .map(|x| ContextWebauthnCredential {
+                    id: x.credential_id.clone(),
+                    name: x.name.clone(),
+                    created_at: x.created_at.format("%Y-%m-%d").to_string(),
+                })
+                .collect(),
+        });
+    }
+}
+
+impl ContextAccount {
+    pub fn new(user: &ResponseUser, uri_link: Url, uri_password_update: Url) -> Self {
+        Self {
+            name: user.name.clone(),
+            email: user.email.clone(),
+            email_verified: user.email_verified_at.is_some(),
+            locale: user.locale.clone(),
+            timezone: user.timezone.clone(),
+            uri_link: uri_link.to_string(),
+            uri_password_update: uri_password_update.to_string(),
+            sessions: Vec::new(),
+            access: Vec::new(),
+            audit: Vec::new(),
+        }
+    }
+
+    pub fn sessions(&mut self, sessions: &[ResponseUserSession], current_id: Uuid) {
+        self.sessions = sessions
+            .iter()
+            .map(|x| ContextAccountSession {
+                id: x.id.to_string(),
+                user_agent: x.user_agent.clone(),
+                remote_addr: x.remote_addr.clone(),
+                methods: x.methods.join(", "),
+                created_at: x.created_at.format("%Y-%m-%d %H:%M").to_string(),
+                last_seen_at: x.last_seen_at.format("%Y-%m-%d %H:%M").to_string(),
+                current: x.id == current_id,
+            })
+            .collect();
+    }
+
+    pub fn access(&mut self, client_name: String, scope: String) {
+        self.access
+            .push(ContextAccountAccess { client_name, scope });
+    }
+
+    pub fn audit(&mut self, audit: &[ResponseAudit]) {
+        self.audit = audit
+            .iter()
+            .map(|x| ContextAccountAudit {
+                created_at: x.created_at.format("%Y-%m-%d %H:%M").to_string(),
+                audit_type: x.audit_type.clone(),
+                ok: x.status_code.map(|x| x < 400).unwrap_or(true),
+                remote_addr: x
+                    .data
+                    .as_ref()
+                    .and_then(|x| x.get("http_request"))
+                    .and_then(|x| x.get("realip_remote_addr"))
+                    .and_then(|x| x.as_str())
+                    .unwrap_or_default()
+                    .to_string(),
+            })
+            .collect();
+    }
 }
 
 impl From<(i64, oauth2::ErrorResponse)> for ContextError {
@@ -112,10 +363,26 @@ pub const TEMPLATE_AUTH_PASSWORD_RESET_REJECT: &str = include_str!("password_res
 pub const TEMPLATE_AUTH_PASSWORD_RESET_REJECT_OK: &str =
     include_str!("password_reset/reject_ok.hbs");
 
+pub const TEMPLATE_AUTH_MAGIC_LINK: &str = include_str!("magic_link/request.hbs");
+
+pub const TEMPLATE_AUTH_MAGIC_LINK_ACCEPT: &str = include_str!("magic_link/accept.hbs");
+
+pub const TEMPLATE_AUTH_EMAIL_VERIFY: &str = include_str!("email_verify/accept.hbs");
+
+pub const TEMPLATE_AUTH_EMAIL_VERIFY_OK: &str = include_str!("email_verify/accept_ok.hbs");
+
 pub const TEMPLATE_AUTH_EMAIL_UPDATE: &str = include_str!("email_update/request.hbs");
 
 pub const TEMPLATE_AUTH_EMAIL_UPDATE_OK: &str = include_str!("email_update/request_ok.hbs");
 
+pub const TEMPLATE_AUTH_EMAIL_UPDATE_ACCEPT: &str = include_str!("email_update/accept.hbs");
+
+pub const TEMPLATE_AUTH_EMAIL_UPDATE_ACCEPT_OK: &str = include_str!("email_update/accept_ok.hbs");
+
+pub const TEMPLATE_AUTH_EMAIL_UPDATE_REVERT: &str = include_str!("email_update/revert.hbs");
+
+pub const TEMPLATE_AUTH_EMAIL_UPDATE_REVERT_OK: &str = include_str!("email_update/revert_ok.hbs");
+
 pub const TEMPLATE_AUTH_PASSWORD_UPDATE: &str = include_str!("password_update/request.hbs");
 
 pub const TEMPLATE_AUTH_PASSWORD_UPDATE_OK: &str = include_str!("password_update/request_ok.hbs");
@@ -126,10 +393,34 @@ pub const TEMPLATE_AUTH_REGISTER_ACCEPT: &str = include_str!("register/accept.hb
 
 pub const TEMPLATE_AUTH_REGISTER_ACCEPT_OK: &str = include_str!("register/accept_ok.hbs");
 
+pub const TEMPLATE_AUTH_REGISTER_ACCEPT_PENDING: &str = include_str!("register/accept_pending.hbs");
+
+pub const TEMPLATE_AUTH_REGISTER_INVITE: &str = include_str!("register/invite.hbs");
+
 pub const TEMPLATE_AUTH_REGISTER_REJECT: &str = include_str!("register/reject.hbs");
 
 pub const TEMPLATE_AUTH_REGISTER_REJECT_OK: &str = include_str!("register/reject_ok.hbs");
 
+pub const TEMPLATE_AUTH_LINK: &str = include_str!("link/request.hbs");
+
+pub const TEMPLATE_AUTH_LINK_OK: &str = include_str!("link/request_ok.hbs");
+
+pub const TEMPLATE_AUTH_MFA: &str = include_str!("mfa.hbs");
+
+pub const TEMPLATE_AUTH_TOTP_ENROL: &str = include_str!("totp/request.hbs");
+
+pub const TEMPLATE_AUTH_TOTP_ENROL_OK: &str = include_str!("totp/request_ok.hbs");
+
+pub const TEMPLATE_AUTH_RECOVERY: &str = include_str!("recovery/request.hbs");
+
+pub const TEMPLATE_AUTH_RECOVERY_OK: &str = include_str!("recovery/request_ok.hbs");
+
+pub const TEMPLATE_AUTH_WEBAUTHN: &str = include_str!("webauthn/request.hbs");
+
+pub const TEMPLATE_AUTH_WEBAUTHN_OK: &str = include_str!("webauthn/request_ok.hbs");
+
+pub const TEMPLATE_AUTH_ACCOUNT: &str = include_str!("account.hbs");
+
 pub const TEMPLATE_AUTH_LOGOUT: &str = include_str!("logout.hbs");
 
 pub const TEMPLATE_AUTH_DELETE: &str = include_str!("delete/request.hbs");
@@ -172,6 +463,133 @@ Forwarded For Address: {{audit.realip_remote}}
 User Agent: {{audit.user_agent}}{{/if}}
 "#;
 
+pub const TEMPLATE_MAIL_PASSWORD_EXPIRY: &str = r#"
+Password Expiry
+
+You are receiving this email because the password of the following user will expire soon.
+
+{{user_email}}
+
+The password expires on {{expires_at}}, after which it must be updated at login. Click the
+following link to update your password now.
+
+{{{uri}}}
+
+This login was made by the following client.
+
+{{client_name}}
+{{{client_uri}}}
+
+{{#if audit}}More technical information about this login.
+
+Timestamp: {{audit.timestamp}}
+Remote Address: {{audit.remote}}
+Forwarded For Address: {{audit.realip_remote}}
+User Agent: {{audit.user_agent}}{{/if}}
+"#;
+
+pub const TEMPLATE_MAIL_MAGIC_LINK: &str = r#"
+Login Request
+
+You are receiving this email because a login link was requested for the following user.
+
+{{user_email}}
+
+If you made this request, click the following link to log in.
+
+{{{uri}}}
+
+If you did not make this request, you can ignore this email.
+
+This request was made by the following client.
+
+{{client_name}}
+{{{client_uri}}}
+
+{{#if audit}}More technical information about this request.
+
+Timestamp: {{audit.timestamp}}
+Remote Address: {{audit.remote}}
+Forwarded For Address: {{audit.realip_remote}}
+User Agent: {{audit.user_agent}}{{/if}}
+"#;
+
+pub const TEMPLATE_MAIL_EMAIL_UPDATE: &str = r#"
+Email Update Request
+
+You are receiving this email because a request was made to change the email address of the following user to this email address.
+
+{{user_email}}
+
+If you made this request, click the following link to confirm the change.
+
+{{{uri}}}
+
+If you did not make this request, you can ignore this email.
+
+This request was made by the following client.
+
+{{client_name}}
+{{{client_uri}}}
+
+{{#if audit}}More technical information about this request.
+
+Timestamp: {{audit.timestamp}}
+Remote Address: {{audit.remote}}
+Forwarded For Address: {{audit.realip_remote}}
+User Agent: {{audit.user_agent}}{{/if}}
+"#;
+
+pub const TEMPLATE_MAIL_EMAIL_UPDATE_NOTICE: &str = r#"
+Email Address Updated
+
+You are receiving this email because the email address of the following user was changed.
+
+{{user_email}}
+
+The new email address is {{user_email_new}}. If you did not make this change, click the following link before {{expires_at}} to restore this email address, even if it has been changed again since. All sessions will be logged out and you will need to reset your password.
+
+{{{uri}}}
+
+This change was made using the following client.
+
+{{client_name}}
+{{{client_uri}}}
+
+{{#if audit}}More technical information about this change.
+
+Timestamp: {{audit.timestamp}}
+Remote Address: {{audit.remote}}
+Forwarded For Address: {{audit.realip_remote}}
+User Agent: {{audit.user_agent}}{{/if}}
+"#;
+
+pub const TEMPLATE_MAIL_EMAIL_VERIFY: &str = r#"
+Verify Email Address
+
+You are receiving this email because verification was requested for the following email address.
+
+{{user_email}}
+
+If you made this request, click the following link to verify your email address.
+
+{{{uri}}}
+
+If you did not make this request, you can ignore this email.
+
+This request was made by the following client.
+
+{{client_name}}
+{{{client_uri}}}
+
+{{#if audit}}More technical information about this request.
+
+Timestamp: {{audit.timestamp}}
+Remote Address: {{audit.remote}}
+Forwarded For Address: {{audit.realip_remote}}
+User Agent: {{audit.user_agent}}{{/if}}
+"#;
+
 pub const TEMPLATE_MAIL_REGISTER: &str = r#"
 Register Request
 
@@ -200,6 +618,121 @@ Forwarded For Address: {{audit.realip_remote}}
 User Agent: {{audit.user_agent}}{{/if}}
 "#;
 
+pub const TEMPLATE_MAIL_INVITE: &str = r#"
+Invitation
+
+Hello {{user_name}},
+
+You are receiving this email because you have been invited to register with the following client.
+
+{{client_name}}
+{{{client_uri}}}
+
+To accept the invitation for the following email address, click the following link.
+
+{{user_email}}
+
+{{{uri_accept}}}
+
+This invitation expires at {{expires_at}}. If you were not expecting this invitation, you can ignore this email.
+"#;
+
+pub const TEMPLATE_MAIL_REGISTER_APPROVAL: &str = r#"
+Registration Approval Request
+
+You are receiving this email because you are an approver for registrations with the following client.
+
+{{client_name}}
+{{{client_uri}}}
+
+The following user has registered and is waiting for approval.
+
+Name: {{user_name}}
+Email: {{user_email}}
+User ID: {{user_id}}
+
+The user is disabled until the registration is approved or denied using the API.
+
+{{#if audit}}More technical information about this registration.
+
+Timestamp: {{audit.timestamp}}
+Remote Address: {{audit.remote}}
+Forwarded For Address: {{audit.realip_remote}}
+User Agent: {{audit.user_agent}}{{/if}}
+"#;
+
+pub const TEMPLATE_MAIL_REGISTER_APPROVED: &str = r#"
+Registration Approved
+
+You are receiving this email because the registration of the following email address has been approved.
+
+{{user_email}}
+
+You can now log in using the following client.
+
+{{client_name}}
+{{{client_uri}}}
+"#;
+
+pub const TEMPLATE_MAIL_REGISTER_DENIED: &str = r#"
+Registration Denied
+
+You are receiving this email because the registration of the following email address has been denied, and the account has been deleted.
+
+{{user_email}}
+
+This registration was made with the following client.
+
+{{client_name}}
+{{{client_uri}}}
+"#;
+
+pub const TEMPLATE_MAIL_RECOVERY: &str = r#"
+Recovery Code Used
+
+You are receiving this email because a recovery code was used to log in as the following user.
+
+{{user_email}}
+
+There are {{recovery_count}} recovery codes remaining. If you did not log in, your password may be known to someone else, change your password and generate new recovery codes.
+
+This login was made using the following client.
+
+{{client_name}}
+{{{client_uri}}}
+
+{{#if audit}}More technical information about this login.
+
+Timestamp: {{audit.timestamp}}
+Remote Address: {{audit.remote}}
+Forwarded For Address: {{audit.realip_remote}}
+User Agent: {{audit.user_agent}}{{/if}}
+"#;
+
+pub const TEMPLATE_MAIL_DEVICE_NEW: &str = r#"
+New Device Login
+
+You are receiving this email because the following user logged in from a device that has not been used before.
+
+{{user_email}}
+
+If this was you, you can ignore this email. If you did not log in, your password may be known to someone else, review and revoke sessions using the following link and change your password.
+
+{{{uri}}}
+
+This login was made using the following client.
+
+{{client_name}}
+{{{client_uri}}}
+
+{{#if audit}}More technical information about this login.
+
+Timestamp: {{audit.timestamp}}
+Remote Address: {{audit.remote}}
+Forwarded For Address: {{audit.realip_remote}}
+User Agent: {{audit.user_agent}}{{/if}}
+"#;
+
 pub const TEMPLATE_MAIL_DELETE: &str = r#"
 Delete Request
 
@@ -1,12 +1,15 @@
 pub(crate) use crate::{
     common::*,
     config::*,
+    device,
     error::*,
+    github,
     http_server::*,
-    mailto, metrics,
+    ldap, lockout, mailto, metrics,
     oauth2::{self, AuthorizationServerIf, ClientIf, SerializeJson, UserRedirectUri},
+    openid, password,
     postgres::*,
-    util, validate,
+    rate_limit, totp, util, validate, webauthn,
 };
 pub(crate) use chrono::{DateTime, Utc};
 pub(crate) use opentelemetry::metrics::{BoundCounter, BoundValueRecorder};
@@ -36,6 +39,54 @@ pub(crate) fn default_as_86400() -> i64 {
     3600
 }
 
+pub(crate) fn default_as_604800() -> i64 {
+    604800
+}
+
+pub(crate) fn default_as_2592000() -> i64 {
+    2592000
+}
+
+pub(crate) fn default_as_7776000() -> i64 {
+    7776000
+}
+
+pub(crate) fn default_as_7() -> i64 {
+    7
+}
+
+pub(crate) fn default_as_8() -> usize {
+    8
+}
+
+pub(crate) fn default_as_64() -> usize {
+    64
+}
+
+pub(crate) fn default_as_60() -> i64 {
+    60
+}
+
+pub(crate) fn default_as_500() -> u64 {
+    500
+}
+
+pub(crate) fn default_as_19456() -> u32 {
+    19456
+}
+
+pub(crate) fn default_as_2() -> u32 {
+    2
+}
+
+pub(crate) fn default_as_1() -> u32 {
+    1
+}
+
 pub(crate) fn default_as_sso() -> String {
     "sso".to_string()
 }
+
+pub(crate) fn default_as_github_api_uri() -> Url {
+    Url::parse("https://api.github.com").unwrap()
+}
@@ -0,0 +1,382 @@
+//! # LDAP
+use crate::internal::*;
+use ldap3::{LdapConnAsync, LdapConnSettings, LdapResult, Scope, SearchEntry};
+use std::time::Duration;
+
+/// LDAP result code of bind with incorrect password
+const RC_INVALID_CREDENTIALS: u32 = 49;
+
+/// LDAP Configuration
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Config {
+    /// Directory URI, for example `ldap://localhost:389` or `ldaps://localhost:636`
+    pub uri: String,
+    /// Use StartTLS on `ldap://` connections
+    #[serde(default)]
+    pub starttls: bool,
+    /// Disable TLS certificate verification (development only)
+    #[serde(default)]
+    pub no_tls_verify: bool,
+    /// DN used to search directory, anonymous search if not set
+    pub bind_dn: Option<String>,
+    /// Password used to search directory
+    #[serde(default)]
+    pub bind_password: String,
+    /// Search base DN
+    pub search_base: String,
+    /// Search filter, `{email}` is replaced by escaped email address
+    #[serde(default = "default_search_filter")]
+    pub search_filter: String,
+    /// Email address attribute
+    #[serde(default = "default_email_attribute")]
+    pub email_attribute: String,
+    /// Name attribute
+    #[serde(default = "default_name_attribute")]
+    pub name_attribute: String,
+    /// Group membership attribute
+    #[serde(default = "default_group_attribute")]
+    pub group_attribute: String,
+    /// Group DN to scope map, if not empty access scope is updated on login, otherwise users
+    /// without access are given access with client register scope
+    #[serde(default)]
+    pub groups: HashMap<String, Vec<String>>,
+    /// Number of seconds before connection and directory operations of a login time out
+    #[serde(default = "default_timeout_s")]
+    pub timeout_s: u64,
+}
+
+/// LDAP
+#[derive(Debug, Clone)]
+pub struct Ldap {
+    config: Arc<Config>,
+}
+
+/// LDAP User
+#[derive(Debug, Clone)]
+pub struct User {
+    /// Distinguished name
+    pub dn: String,
+    /// Email address
+    pub email: String,
+    /// Name
+    pub name: String,
+    /// Group DNs
+    pub groups: Vec<String>,
+}
+
+/// LDAP Authentication
+#[derive(Debug, Clone)]
+pub enum Authenticate {
+    /// Email address is not found in directory
+    NotFound,
+    /// Password is incorrect
+    PasswordIncorrect,
+    /// User bound with password
+    User(User),
+}
+
+/// Create LDAP from configuration
+pub fn from_config(config: Config) -> Ldap {
+    Ldap::from_config(config)
+}
+
+impl Ldap {
+    /// Returns configuration
+    pub fn config(&self) -> &Config {
+        self.config.as_ref()
+    }
+
+    /// Create LDAP from configuration
+    pub fn from_config(config: Config) -> Self {
+        Self {
+            config: Arc::new(config),
+        }
+    }
+
+    /// Search directory for user and bind with password
+    ///
+    /// Returns an error if the directory is unavailable, does not respond before the
+    /// configured timeout or returns an unexpected result.
+    pub async fn authenticate(&self, email: &str, password: &str) -> Result<Authenticate> {
+        // An empty password is an unauthenticated bind which servers may accept
+        if password.is_empty() {
+            return Ok(Authenticate::PasswordIncorrect);
+        }
+
+        let timeout = Duration::from_secs(self.config.timeout_s);
+        actix_rt::time::timeout(timeout, self.authenticate_inner(email, password))
+            .await
+            .map_err(|_e| Error::from("ldap operation timed out"))?
+    }
+
+    async fn authenticate_inner(&self, email: &str, password: &str) -> Result<Authenticate> {
+        let settings = LdapConnSettings::new()
+            .set_conn_timeout(Duration::from_secs(self.config.timeout_s))
+            .set_starttls(self.config.starttls)
+            .set_no_tls_verify(self.config.no_tls_verify);
+        let (conn, mut ldap) = LdapConnAsync::with_settings(settings, &self.config.uri).await?;
+        ldap3::drive!(conn);
+
+        if let Some(bind_dn) = &self.config.bind_dn {
+            ldap.simple_bind(bind_dn, &self.config.bind_password)
+                .await?
+                .success()?;
+        }
+
+        let filter = self
+            .config
+            .search_filter
+            .replace("{email}", &ldap3::ldap_escape(email));
+        let attrs = vec![
+            self.config.email_attribute.as_str(),
+            self.config.name_attribute.as_str(),
+            self.config.group_attribute.as_str(),
+        ];
+        let (mut entries, _) = ldap
+            .search(&self.config.search_base, Scope::Subtree, &filter, attrs)
+            .await?
+            .success()?;
+
+        let entry = match entries.len() {
+            0 => {
+                ldap.unbind().await?;
+                return Ok(Authenticate::NotFound);
+            }
+            1 => SearchEntry::construct(entries.remove(0)),
+            _ => {
+                ldap.unbind().await?;
+                return Err(Error::from("ldap search returned multiple entries"));
+            }
+        };
+
+        let bind = ldap.simple_bind(&entry.dn, password).await?;
+        ldap.unbind().await?;
+        if !Self::bind_success(bind)? {
+            return Ok(Authenticate::PasswordIncorrect);
+        }
+
+        let email = Self::attribute(&entry, &self.config.email_attribute)
+            .unwrap_or_else(|| email.to_string());
+        let name =
+            Self::attribute(&entry, &self.config.name_attribute).unwrap_or_else(|| email.clone());
+        let groups = entry
+            .attrs
+            .get(&self.config.group_attribute)
+            .cloned()
+            .unwrap_or_default();
+
+        Ok(Authenticate::User(User {
+            dn: entry.dn,
+            email,
+            name,
+            groups,
+        }))
+    }
+
+    /// Returns false if bind failed because password is incorrect, other failures such as
+    /// a busy or unavailable directory are returned as errors
+    fn bind_success(bind: LdapResult) -> Result<bool> {
+        match bind.rc {
+            RC_INVALID_CREDENTIALS => Ok(false),
+            _ => bind.success().map(|_| true).map_err(Into::into),
+        }
+    }
+
+    /// Returns scope mapped from user groups, or `None` if no groups are configured
+    pub fn groups_scope(&self, user: &User) -> Option<oauth2::Scope> {
+        if self.config.groups.is_empty() {
+            return None;
+        }
+
+        let mut scope: Vec<String> = Vec::new();
+        for group in user.groups.iter() {
+            let mapped = self
+                .config
+                .groups
+                .iter()
+                .find(|(dn, _)| dn.eq_ignore_ascii_case(group))
+                .map(|(_, x)| x);
+            if let Some(mapped) = mapped {
+                for x in mapped {
+                    if !scope.contains(x) {
+                        scope.push(x.to_string());
+                    }
+                }
+            }
+        }
+        Some(oauth2::Scope::from(scope))
+    }
+
+    fn attribute(entry: &SearchEntry, name: &str) -> Option<String> {
+        entry
+            .attrs
+            .get(name)
+            .and_then(|x| x.first())
+            .filter(|x| !x.is_empty())
+            .cloned()
+    }
+}
+
+fn default_search_filter() -> String {
+    "(mail={email})".to_string()
+}
+
+fn default_email_attribute() -> String {
+    "mail".to_string()
+}
+
+fn default_name_attribute() -> String {
+    "cn".to_string()
+}
+
+fn default_group_attribute() -> String {
+    "memberOf".to_string()
+}
+
+fn default_timeout_s() -> u64 {
+    5
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::Instant;
+
+    fn ldap(uri: &str) -> Ldap {
+        Ldap::from_config(Config {
+            uri: uri.to_string(),
+            starttls: false,
+            no_tls_verify: false,
+            bind_dn: None,
+            bind_password: String::new(),
+            search_base: "dc=sso,dc=local".to_string(),
+            search_filter: default_search_filter(),
+            email_attribute: default_email_attribute(),
+            name_attribute: default_name_attribute(),
+            group_attribute: default_group_attribute(),
+            groups: HashMap::new(),
+            timeout_s: 1,
+        })
+    }
+
+    fn bind(rc: u32) -> LdapResult {
+        LdapResult {
+            rc,
+            matched: String::new(),
+            text: String::new(),
+            refs: Vec::new(),
+            ctrls: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn test_bind_success() {
+        assert!(Ldap::bind_success(bind(0)).unwrap());
+        assert!(!Ldap::bind_success(bind(RC_INVALID_CREDENTIALS)).unwrap());
+        // Busy, unavailable and unwilling to perform are not password errors
+        assert!(Ldap::bind_success(bind(51)).is_err());
+        assert!(Ldap::bind_success(bind(52)).is_err());
+        assert!(Ldap::bind_success(bind(53)).is_err());
+    }
+
+    #[actix_rt::test]
+    async fn test_authenticate_empty_password() {
+        let ldap = ldap("ldap://127.0.0.1:1");
+        let res = ldap.authenticate("user@example.com", "").await.unwrap();
+        assert!(matches!(res, Authenticate::PasswordIncorrect));
+    }
+
+    #[actix_rt::test]
+    async fn test_authenticate_unavailable() {
+        // Port is closed after listener is dropped
+        let port = std::net::TcpListener::bind("127.0.0.1:0")
+            .unwrap()
+            .local_addr()
+            .unwrap()
+            .port();
+        let ldap = ldap(&format!("ldap://127.0.0.1:{}", port));
+        assert!(ldap
+            .authenticate("user@example.com", "guest")
+            .await
+            .is_err());
+    }
+
+    #[actix_rt::test]
+    async fn test_authenticate_timeout() {
+        // Listener accepts connections but never responds
+        let listener = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+        let ldap = ldap(&format!("ldap://{}", listener.local_addr().unwrap()));
+
+        let start = Instant::now();
+        assert!(ldap
+            .authenticate("user@example.com", "guest")
+            .await
+            .is_err());
+        assert!(start.elapsed() < Duration::from_secs(3));
+    }
+
+    #[actix_rt::test]
+    async fn test_authenticate_directory() {
+        // Directory started by `.devcontainer/docker-ldap.yml`, skipped if not set
+        let uri = match std::env::var("TEST_LDAP_URI") {
+            Ok(uri) => uri,
+            Err(_) => return,
+        };
+        let admin_dn = "cn=admin,dc=sso,dc=local";
+        let id = Uuid::new_v4().to_string();
+        let dn = format!("uid={},dc=sso,dc=local", id);
+        let email = format!("{}@example.com", id);
+
+        let (conn, mut admin) = LdapConnAsync::new(&uri).await.unwrap();
+        ldap3::drive!(conn);
+        admin
+            .simple_bind(admin_dn, "guest")
+            .await
+            .unwrap()
+            .success()
+            .unwrap();
+        let attr = |x: &str| vec![x.to_string()].into_iter().collect();
+        admin
+            .add(
+                &dn,
+                vec![
+                    ("objectClass".to_string(), attr("inetOrgPerson")),
+                    ("uid".to_string(), attr(&id)),
+                    ("cn".to_string(), attr("Test User")),
+                    ("sn".to_string(), attr("User")),
+                    ("mail".to_string(), attr(&email)),
+                    ("userPassword".to_string(), attr("guest")),
+                ],
+            )
+            .await
+            .unwrap()
+            .success()
+            .unwrap();
+
+        let mut config = ldap(&uri).config().clone();
+        config.bind_dn = Some(admin_dn.to_string());
+        config.bind_password = "guest".to_string();
+        let ldap = Ldap::from_config(config);
+
+        let user = ldap.authenticate(&email, "guest").await;
+        let incorrect = ldap.authenticate(&email, "incorrect").await;
+        let not_found = ldap.authenticate(&format!("not-{}", email), "guest").await;
+
+        admin.delete(&dn).await.unwrap().success().unwrap();
+        admin.unbind().await.unwrap();
+
+        match user.unwrap() {
+            Authenticate::User(user) => {
+                assert!(user.dn.eq_ignore_ascii_case(&dn));
+                assert_eq!(user.email, email);
+                assert_eq!(user.name, "Test User");
+            }
+            res => panic!("unexpected result {:?}", res),
+        }
+        assert!(matches!(
+            incorrect.unwrap(),
+            Authenticate::PasswordIncorrect
+        ));
+        assert!(matches!(not_found.unwrap(), Authenticate::NotFound));
+    }
+}
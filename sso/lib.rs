@@ -23,17 +23,25 @@ extern crate validator_derive;
 pub mod cli;
 mod common;
 pub mod config;
+pub mod device;
 mod error;
+pub mod github;
 pub mod http_client;
 pub mod http_server;
 mod internal;
+pub mod ldap;
+pub mod lockout;
 pub mod mailto;
 pub mod metrics;
 pub mod oauth2;
 pub mod openid;
+pub mod password;
 mod postgres;
+pub mod rate_limit;
+pub mod totp;
 pub mod util;
 pub mod validate;
+pub mod webauthn;
 
 pub use crate::error::*;
 
@@ -61,10 +69,7 @@ pub use crate::error::*;
 // - User password flag tests (reset, update required etc)
 
 // todo: Feature
-// - Pwned password support somehow, pass back as header in oauth2 flow? oidc related?
-//     - Config setting to enable/disable
 // - Docker default compose for running example (part of manual)?
-// - Confirm emails for reset/update/etc.
 // - Token naming from request headers?
 // - Email/password updates when using oauth2?
 // - Forward authentication support (caddy, nginx, traefik?)
@@ -73,8 +78,7 @@ pub use crate::error::*;
 //     - If access denied authorize and redirect back to client
 // - PKCE support
 // - Matrix client oauth2, other integrations testing?
-// - Github Oauth2 provider support
-//     - More generic Oauth2 provider support, method of configuration?
+// - More generic Oauth2 provider support, method of configuration?
 // - Expose more SMTP transport options?
 // - CORS support for API requests, or docs on how to add at proxy layer?
 //     - OWASP secure headers project
@@ -112,9 +116,8 @@ pub use crate::error::*;
 // - User metadata, may require access controls
 // - Possible JWT usage for ids/keys/etc., oidc related?
 //     - https://cheatsheetseries.owasp.org/cheatsheets/JSON_Web_Token_Cheat_Sheet_for_Java.html
-// - Rate limiting support, or at proxy level?
 // - Kubernetes examples/support/integration, also systemd examples?
-// - Token revokation support, totp (libreauth) support/options? webauthn? sqrl?
+// - Token revokation support, sqrl?
 // - Improved OpenAPI mapping interface, possibility of using hyper/tower?
 // - Client scopes to limit sso server access (forward auth/trusted/untrusted?)
 // - CLI improvements and documentation
@@ -130,5 +133,4 @@ pub use crate::error::*;
 // - User sessions route, other HTML interfaces? GUI service example?
 // - Client/user groups for segmentation
 // - GDPR and other data protection compliance research
-// - Constant time responses for authentication endpoints to resist timing attacks
 // - Embeddable services/support for integrations?
@@ -0,0 +1,120 @@
+//! # Lockout
+//!
+//! Failed logins are counted per email address and per client IP address. When the number
+//! of failures reaches the configured threshold the email or address is locked, and the
+//! lockout duration doubles for each further failure up to the configured maximum.
+use crate::internal::*;
+use std::net::{IpAddr, SocketAddr};
+
+/// Returns number of seconds to lock for after failures, none if below threshold
+pub fn duration_s(config: &ConfigLockout, threshold: i32, failures: i32) -> Option<i64> {
+    if threshold <= 0 || failures < threshold {
+        return None;
+    }
+    let exponent = (failures - threshold).min(32) as u32;
+    let duration_s = config
+        .lockout_s
+        .saturating_mul(2i64.saturating_pow(exponent));
+    Some(duration_s.min(config.lockout_max_s))
+}
+
+/// Returns email address normalised for failure counting
+pub fn email(email: &str) -> String {
+    email.trim().to_lowercase()
+}
+
+/// Returns IP address of remote address with any port removed
+pub fn address(remote: &str) -> String {
+    if let Ok(addr) = SocketAddr::from_str(remote) {
+        addr.ip().to_string()
+    } else if let Ok(addr) = IpAddr::from_str(remote) {
+        addr.to_string()
+    } else {
+        remote.to_string()
+    }
+}
+
+/// Returns client IP address of request from peer address, if peer is a trusted proxy the
+/// `X-Forwarded-For` header is read from right to left until an untrusted address is found
+pub fn client_address(
+    peer: Option<&str>,
+    forwarded_for: Option<&str>,
+    trusted_proxies: &[IpAddr],
+) -> Option<String> {
+    let mut client = address(peer?);
+    if let Some(forwarded_for) = forwarded_for {
+        for addr in forwarded_for.rsplit(',').map(|x| address(x.trim())) {
+            match IpAddr::from_str(&client) {
+                Ok(ip) if trusted_proxies.contains(&ip) && !addr.is_empty() => client = addr,
+                _ => break,
+            }
+        }
+    }
+    Some(client)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_duration_s() {
+        let config = ConfigLockout {
+            email_failures: 5,
+            address_failures: 20,
+            lockout_s: 60,
+            lockout_max_s: 3600,
+            reset_s: 3600,
+        };
+
+        assert_eq!(duration_s(&config, 0, 100), None);
+        assert_eq!(duration_s(&config, 5, 4), None);
+        assert_eq!(duration_s(&config, 5, 5), Some(60));
+        assert_eq!(duration_s(&config, 5, 6), Some(120));
+        assert_eq!(duration_s(&config, 5, 8), Some(480));
+        assert_eq!(duration_s(&config, 5, 11), Some(3600));
+        assert_eq!(duration_s(&config, 5, 1000), Some(3600));
+    }
+
+    #[test]
+    fn test_address() {
+        assert_eq!(address("192.168.1.1:1234"), "192.168.1.1");
+        assert_eq!(address("192.168.1.1"), "192.168.1.1");
+        assert_eq!(address("[::1]:1234"), "::1");
+        assert_eq!(address("::1"), "::1");
+        assert_eq!(address("unknown"), "unknown");
+    }
+
+    #[test]
+    fn test_client_address() {
+        let trusted = vec![IpAddr::from_str("10.0.0.1").unwrap()];
+
+        assert_eq!(client_address(None, None, &trusted), None);
+        assert_eq!(
+            client_address(Some("192.168.1.1:1234"), None, &trusted).unwrap(),
+            "192.168.1.1"
+        );
+        // Header is ignored unless peer is a trusted proxy
+        assert_eq!(
+            client_address(Some("192.168.1.1:1234"), Some("1.2.3.4"), &trusted).unwrap(),
+            "192.168.1.1"
+        );
+        assert_eq!(
+            client_address(Some("10.0.0.1:1234"), Some("1.2.3.4"), &trusted).unwrap(),
+            "1.2.3.4"
+        );
+        // Addresses added by client before trusted proxy are ignored
+        assert_eq!(
+            client_address(Some("10.0.0.1:1234"), Some("1.2.3.4, 5.6.7.8"), &trusted).unwrap(),
+            "5.6.7.8"
+        );
+        assert_eq!(
+            client_address(Some("10.0.0.1:1234"), Some("1.2.3.4, 10.0.0.1"), &trusted).unwrap(),
+            "1.2.3.4"
+        );
+        assert_eq!(
+            client_address(Some("10.0.0.1:1234"), None, &trusted).unwrap(),
+            "10.0.0.1"
+        );
+    }
+}
@@ -114,6 +114,17 @@ impl Mailto {
         }
     }
 
+    /// Send mail in background task, errors are logged and counted but not returned
+    /// so that response time of request does not depend on mail delivery
+    pub fn send_background(&self, send: Send) {
+        let mailto = self.clone();
+        actix_rt::spawn(async move {
+            if let Err(e) = mailto.send(send).await {
+                warn!("mailto send failed: {}", e.to_string());
+            }
+        });
+    }
+
     async fn send_inner(&self, send: Send) -> Result<()> {
         let json_out = serde_json::to_string(&send).unwrap();
 
@@ -227,3 +238,44 @@ impl std::fmt::Debug for Mailto {
         write!(f, "Mailto {{ config, ... }}")
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[actix_rt::test]
+    async fn test_send_background() {
+        // Server accepts connections but never responds, so SMTP send blocks
+        let server = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+        let path = std::env::temp_dir().join(format!("sso-mailto-{}.log", Uuid::new_v4()));
+        let config = Config {
+            stdout: ConfigStdout::default(),
+            file: ConfigFile {
+                file: Some(path.to_str().unwrap().to_string()),
+            },
+            smtp: Some(ConfigSmtp {
+                host: "127.0.0.1".to_string(),
+                port: server.local_addr().unwrap().port(),
+                from: "sso@example.com".to_string(),
+                login: None,
+            }),
+        };
+        let metrics = metrics::from_config(metrics::Config::default()).unwrap();
+        let mailto = from_config(&metrics, config).await.unwrap();
+
+        let start = std::time::Instant::now();
+        mailto.send_background(mailto.build("user@example.com", "Subject", "Text"));
+        assert!(start.elapsed() < std::time::Duration::from_millis(100));
+
+        let mut text = String::new();
+        for _ in 0..50 {
+            actix_rt::time::delay_for(std::time::Duration::from_millis(10)).await;
+            text = std::fs::read_to_string(&path).unwrap();
+            if !text.is_empty() {
+                break;
+            }
+        }
+        assert!(text.contains("user@example.com"));
+        std::fs::remove_file(&path).unwrap();
+    }
+}
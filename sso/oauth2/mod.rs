@@ -397,15 +397,17 @@ pub struct IntrospectionResponseArgs {
     scope: Scope,
     username: String,
     sub: String,
+    email_verified: bool,
 }
 
 impl IntrospectionResponseArgs {
     /// Returns new arguments
-    pub fn new<S: Into<Scope>>(scope: S, username: &str, sub: &str) -> Self {
+    pub fn new<S: Into<Scope>>(scope: S, username: &str, sub: &str, email_verified: bool) -> Self {
         Self {
             scope: scope.into(),
             username: username.to_string(),
             sub: sub.to_string(),
+            email_verified,
         }
     }
 }
@@ -418,6 +420,7 @@ pub struct IntrospectionResponse {
     client_id: String,
     username: String,
     sub: String,
+    email_verified: bool,
 }
 
 impl SerializeJson for Option<IntrospectionResponse> {
@@ -429,6 +432,7 @@ impl SerializeJson for Option<IntrospectionResponse> {
                 "client_id": res.client_id,
                 "username": res.username,
                 "sub": res.sub,
+                "email_verified": res.email_verified,
             }))
             .unwrap(),
             None => serde_json::to_string(&json!({
@@ -755,6 +759,7 @@ pub trait AuthorizationServerIf {
             client_id: client.client_id(),
             username: args.username,
             sub: args.sub,
+            email_verified: args.email_verified,
         }
     }
 }
@@ -888,12 +893,13 @@ mod tests {
                 vec!["scope-1", "scope-2"],
                 "username-xyz",
                 "user-id-xyz",
+                true,
             ),
         );
 
         assert_eq!(
             Some(introspect_res).serialize_json(),
-            r#"{"active":true,"client_id":"client-id-xyz","scope":"scope-1 scope-2","sub":"user-id-xyz","username":"username-xyz"}"#
+            r#"{"active":true,"client_id":"client-id-xyz","email_verified":true,"scope":"scope-1 scope-2","sub":"user-id-xyz","username":"username-xyz"}"#
         );
 
         // If authorization server returned refresh token, client makes request to authorization server to refresh token
@@ -0,0 +1,280 @@
+//! # Passwords
+//!
+//! Passwords are hashed with Argon2id and stored in the PHC string format. Legacy bcrypt
+//! hashes created by the pgcrypto extension can be verified so that they can be upgraded
+//! when a user logs in.
+use crate::internal::*;
+use actix_web::error::BlockingError;
+use ring::rand::SecureRandom;
+use std::path::Path;
+
+/// Salt length in bytes
+pub const SALT_LEN: usize = 16;
+
+/// Hash length in bytes
+pub const HASH_LEN: u32 = 32;
+
+/// Breached password dataset SHA-1 hash prefix length in hex characters
+pub const PWNED_PREFIX_LEN: usize = 5;
+
+/// Minimum length of words taken from user inputs that are banned in passwords
+pub const POLICY_WORD_MIN: usize = 4;
+
+/// Returns Argon2id hash of password using configured parameters
+pub fn hash(config: &ConfigPassword, password: &str) -> Result<String> {
+    let mut salt = [0u8; SALT_LEN];
+    ring::rand::SystemRandom::new()
+        .fill(&mut salt)
+        .map_err(|_e| Error::from("password salt generate failed"))?;
+
+    let argon2_config = argon2::Config {
+        variant: argon2::Variant::Argon2id,
+        version: argon2::Version::Version13,
+        mem_cost: config.argon2_memory_kib,
+        time_cost: config.argon2_iterations,
+        lanes: config.argon2_parallelism,
+        thread_mode: argon2::ThreadMode::Sequential,
+        secret: &[],
+        ad: &[],
+        hash_length: HASH_LEN,
+    };
+    argon2::hash_encoded(password.as_bytes(), &salt, &argon2_config)
+        .map_err(|e| Error::from(e.to_string()))
+}
+
+/// Returns Argon2id hash of a random password, which is verified in place of a user's hash
+/// when the user does not exist so that response time does not reveal which users exist
+pub fn hash_dummy(config: &ConfigPassword) -> Result<String> {
+    let mut password = [0u8; SALT_LEN];
+    ring::rand::SystemRandom::new()
+        .fill(&mut password)
+        .map_err(|_e| Error::from("password dummy generate failed"))?;
+
+    hash(config, &base64::encode(&password))
+}
+
+/// Returns true if password matches Argon2 or legacy bcrypt hash
+pub fn verify(hash: &str, password: &str) -> Result<bool> {
+    if hash.starts_with("$argon2") {
+        argon2::verify_encoded(hash, password.as_bytes()).map_err(|e| Error::from(e.to_string()))
+    } else if hash.starts_with("$2") {
+        bcrypt::verify(password, hash).map_err(|e| Error::from(e.to_string()))
+    } else {
+        Err(Error::from("password hash format is invalid"))
+    }
+}
+
+/// Returns Argon2id hash of password using configured parameters, hashing is run on the
+/// blocking thread pool so that it does not block server threads
+pub async fn hash_block(config: &ConfigPassword, password: &str) -> Result<String> {
+    let config = config.clone();
+    let password = password.to_string();
+    actix_web::web::block(move || hash(&config, &password))
+        .await
+        .map_err(block_error)
+}
+
+/// Returns Argon2id hash of a random password, see `hash_dummy`, hashing is run on the
+/// blocking thread pool
+pub async fn hash_dummy_block(config: &ConfigPassword) -> Result<String> {
+    let config = config.clone();
+    actix_web::web::block(move || hash_dummy(&config))
+        .await
+        .map_err(block_error)
+}
+
+/// Returns true if password matches Argon2 or legacy bcrypt hash, verification is run on
+/// the blocking thread pool
+pub async fn verify_block(hash: &str, password: &str) -> Result<bool> {
+    let hash = hash.to_string();
+    let password = password.to_string();
+    actix_web::web::block(move || verify(&hash, &password))
+        .await
+        .map_err(block_error)
+}
+
+fn block_error(e: BlockingError<Error>) -> Error {
+    match e {
+        BlockingError::Error(e) => e,
+        BlockingError::Canceled => Error::from("password thread pool canceled"),
+    }
+}
+
+/// Returns true if hash is not Argon2id with configured parameters and should be replaced
+pub fn rehash_required(config: &ConfigPassword, hash: &str) -> bool {
+    let prefix = format!(
+        "$argon2id$v=19$m={},t={},p={}$",
+        config.argon2_memory_kib, config.argon2_iterations, config.argon2_parallelism
+    );
+    !hash.starts_with(&prefix)
+}
+
+/// Returns error if password does not meet configured policy
+///
+/// User inputs such as email address and name are split into words which are banned in
+/// addition to the configured list, and are passed to `zxcvbn` when estimating strength.
+pub fn policy_check(config: &ConfigPassword, password: &str, user_inputs: &[&str]) -> Result<()> {
+    let len = password.chars().count();
+    if len < config.length_min || len > config.length_max {
+        return Err(Error::from(format!(
+            "password must be {}-{} characters",
+            config.length_min, config.length_max
+        )));
+    }
+
+    let words = policy_words(user_inputs);
+    let password_lower = password.to_lowercase();
+    let banned = config
+        .banned_words
+        .iter()
+        .map(|x| x.to_lowercase())
+        .chain(words.iter().cloned())
+        .filter(|x| !x.is_empty())
+        .any(|x| password_lower.contains(&x));
+    if banned {
+        return Err(Error::from(
+            "password contains a banned word, name or email address",
+        ));
+    }
+
+    if config.score_min > 0 {
+        let words: Vec<&str> = words.iter().map(|x| x.as_str()).collect();
+        let entropy = zxcvbn::zxcvbn(password, &words).map_err(|e| Error::from(e.to_string()))?;
+        if entropy.score() < config.score_min {
+            return Err(Error::from("password is too weak"));
+        }
+    }
+    Ok(())
+}
+
+fn policy_words(user_inputs: &[&str]) -> Vec<String> {
+    user_inputs
+        .iter()
+        .flat_map(|x| x.split(|c: char| !c.is_alphanumeric()))
+        .filter(|x| x.chars().count() >= POLICY_WORD_MIN)
+        .map(|x| x.to_lowercase())
+        .collect()
+}
+
+/// Returns number of times password appears in breached password dataset
+///
+/// The dataset is a directory in the k-anonymity range format, with one file per upper case
+/// hex SHA-1 hash prefix (e.g. `5BAA6.txt`). Each line of a file contains the remaining hash
+/// characters and a count separated by a colon, missing files are treated as empty ranges.
+pub async fn pwned_count(path: &Path, password: &str) -> Result<u64> {
+    let hash = sha1_hex(password);
+    let (prefix, suffix) = hash.split_at(PWNED_PREFIX_LEN);
+
+    let file = path.join(format!("{}.txt", prefix));
+    match tokio::fs::read_to_string(file).await {
+        Ok(range) => Ok(pwned_range_count(&range, suffix)),
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(0),
+        Err(e) => Err(e.into()),
+    }
+}
+
+fn pwned_range_count(range: &str, suffix: &str) -> u64 {
+    range
+        .lines()
+        .filter_map(|line| {
+            let mut parts = line.trim().splitn(2, ':');
+            match (parts.next(), parts.next()) {
+                (Some(x), Some(count)) if x.eq_ignore_ascii_case(suffix) => count.parse().ok(),
+                _ => None,
+            }
+        })
+        .next()
+        .unwrap_or(0)
+}
+
+fn sha1_hex(value: &str) -> String {
+    ring::digest::digest(&ring::digest::SHA1_FOR_LEGACY_USE_ONLY, value.as_bytes())
+        .as_ref()
+        .iter()
+        .map(|x| format!("{:02X}", x))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn config() -> ConfigPassword {
+        ConfigPassword {
+            argon2_memory_kib: 1024,
+            argon2_iterations: 1,
+            argon2_parallelism: 1,
+            ..ConfigPassword::default()
+        }
+    }
+
+    #[test]
+    fn test_hash_verify() {
+        let config = config();
+        let hash = hash(&config, "guest123").unwrap();
+        assert!(hash.starts_with("$argon2id$v=19$m=1024,t=1,p=1$"));
+        assert!(verify(&hash, "guest123").unwrap());
+        assert!(!verify(&hash, "guest124").unwrap());
+        assert!(!rehash_required(&config, &hash));
+        assert!(rehash_required(&ConfigPassword::default(), &hash));
+    }
+
+    #[actix_rt::test]
+    async fn test_hash_verify_block() {
+        let config = config();
+        let hash = hash_block(&config, "guest123").await.unwrap();
+        assert!(verify_block(&hash, "guest123").await.unwrap());
+        assert!(!verify_block(&hash, "guest124").await.unwrap());
+        assert!(verify_block("guest123", "guest123").await.is_err());
+    }
+
+    #[test]
+    fn test_verify_bcrypt() {
+        let config = config();
+        let hash = bcrypt::hash("guest123", 4).unwrap();
+        assert!(verify(&hash, "guest123").unwrap());
+        assert!(!verify(&hash, "guest124").unwrap());
+        assert!(rehash_required(&config, &hash));
+        assert!(verify("guest123", "guest123").is_err());
+    }
+
+    #[test]
+    fn test_policy_check() {
+        let mut config = config();
+        config.banned_words = vec!["Acme".to_string()];
+        let user_inputs = ["jane.doe@example.com", "Jane Doe"];
+
+        assert!(policy_check(&config, "correct horse", &user_inputs).is_ok());
+        assert!(policy_check(&config, "short", &user_inputs).is_err());
+        assert!(policy_check(&config, &"x".repeat(65), &user_inputs).is_err());
+        assert!(policy_check(&config, "acme1234", &user_inputs).is_err());
+        assert!(policy_check(&config, "JANE1234", &user_inputs).is_err());
+        assert!(policy_check(&config, "examples!", &user_inputs).is_err());
+        // Words shorter than minimum are not banned
+        assert!(policy_check(&config, "doe12345", &user_inputs).is_ok());
+
+        config.score_min = 3;
+        assert!(policy_check(&config, "guestguest", &user_inputs).is_err());
+        assert!(policy_check(&config, "kN7#pLq2!vXw9@Rz", &user_inputs).is_ok());
+    }
+
+    #[test]
+    fn test_pwned_range_count() {
+        let hash = sha1_hex("password");
+        assert_eq!(hash, "5BAA61E4C9B93F3F0682250B6CF8331B7EE68FD8");
+
+        let (_prefix, suffix) = hash.split_at(PWNED_PREFIX_LEN);
+        let range = "1D2DA4053E34E76F6576ED1DA63134B5E2A:2\r\n\
+                     1E4C9B93F3F0682250B6CF8331B7EE68FD8:3861493\r\n\
+                     1E4D7E0B1C7E8E3A4C0A51E1E3F1F4E3A9C:0\r\n";
+        assert_eq!(pwned_range_count(range, suffix), 3861493);
+        assert_eq!(
+            pwned_range_count(range, "1E4D7E0B1C7E8E3A4C0A51E1E3F1F4E3A9C"),
+            0
+        );
+        assert_eq!(
+            pwned_range_count(range, "0000000000000000000000000000000000A"),
+            0
+        );
+    }
+}
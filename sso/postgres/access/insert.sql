@@ -0,0 +1,7 @@
+INSERT INTO
+    sso.access_table("client_id", "user_id", "enable", "scope", "static")
+VALUES
+    ($1, $2, TRUE, $3, FALSE)
+ON CONFLICT
+    ("client_id", "user_id")
+DO NOTHING
@@ -0,0 +1,25 @@
+SELECT
+    "created_at",
+    "id",
+    "client_id",
+    "user_id",
+    "token_id",
+    "api_key_id",
+    "type",
+    "subject",
+    "data",
+    "status_code"
+FROM
+    sso.audit_table
+WHERE
+    "user_id" = $1
+AND
+    (
+        COALESCE("data"->'http_request'->>'method', 'POST') <> 'GET'
+    OR
+        "type" = 'sso_oauth2_redirect'
+    )
+ORDER BY
+    "id" DESC
+LIMIT
+    $2
@@ -9,7 +9,7 @@ WITH cte AS (
     WHERE
         up."user_id" = $3
     AND
-        sso._password_check($4, "hash")
+        up."hash" = $4
 )
 INSERT INTO
     sso.code_table("client_id", "value", "target", "ttl", "user_id", "email")
@@ -0,0 +1,30 @@
+WITH cte AS (
+    SELECT
+        u."id",
+        u."email"
+    FROM
+        sso.user_password_table AS up
+    INNER JOIN
+        sso.user_table AS u ON u."id" = up."user_id"
+    WHERE
+        up."user_id" = $3
+    AND
+        up."hash" = $4
+    AND
+        u."static" IS FALSE
+)
+INSERT INTO
+    sso.code_table("client_id", "value", "target", "ttl", "user_id", "state", "email")
+SELECT
+    $1,
+    sso._secret_generate(32),
+    'EmailUpdate',
+    sso._ttl_timestamptz($2),
+    cte."id",
+    cte."email",
+    $5
+FROM
+    cte
+RETURNING
+    "value",
+    "state"
@@ -0,0 +1,27 @@
+WITH cte AS (
+    SELECT
+        u."id",
+        u."email"
+    FROM
+        sso.user_table AS u
+    WHERE
+        u."id" = $3
+    AND
+        u."enable" = TRUE
+    AND
+        u."email_verified_at" IS NULL
+)
+INSERT INTO
+    sso.code_table("client_id", "value", "target", "ttl", "user_id", "email")
+SELECT
+    $1,
+    sso._secret_generate(32),
+    'EmailVerify',
+    sso._ttl_timestamptz($2),
+    cte."id",
+    cte."email"
+FROM
+    cte
+RETURNING
+    "value",
+    "email"
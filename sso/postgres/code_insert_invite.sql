@@ -0,0 +1,12 @@
+INSERT INTO
+    sso.code_table("client_id", "value", "target", "ttl", "state", "scope", "email")
+SELECT
+    $1,
+    sso._secret_generate(32),
+    'Invite',
+    sso._ttl_timestamptz($2),
+    $4,
+    $5,
+    $3
+RETURNING
+    "value"
@@ -0,0 +1,31 @@
+WITH cte AS (
+    SELECT
+        a."user_id"
+    FROM
+        sso.access_table AS a
+    INNER JOIN
+        sso.user_table AS u ON u."id" = a."user_id"
+    WHERE
+        a."client_id" = $1
+    AND
+        a."enable" = TRUE
+    AND
+        u."email" = $3
+    AND
+        u."enable" = TRUE
+)
+INSERT INTO
+    sso.code_table("client_id", "value", "target", "ttl", "user_id", "state", "scope", "email")
+SELECT
+    $1,
+    sso._secret_generate(32),
+    'Login',
+    sso._ttl_timestamptz($2),
+    cte."user_id",
+    $4,
+    $5,
+    $3
+FROM
+    cte
+RETURNING
+    "value"
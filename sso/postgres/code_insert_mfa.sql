@@ -0,0 +1,6 @@
+INSERT INTO
+    sso.code_table("client_id", "value", "target", "ttl", "user_id", "state")
+VALUES
+    ($1, sso._secret_generate(32), 'Mfa', sso._ttl_timestamptz($2), $3, $4)
+RETURNING
+    "value"
@@ -1,6 +1,7 @@
 WITH cte AS (
     SELECT
-        "user_id"
+        u."id",
+        u."email"
     FROM
         sso.user_password_table AS up
     INNER JOIN
@@ -9,13 +10,14 @@ WITH cte AS (
         u."email" = $3
 )
 INSERT INTO
-    sso.code_table("client_id", "value", "target", "ttl", "user_id")
+    sso.code_table("client_id", "value", "target", "ttl", "user_id", "email")
 SELECT
     $1,
     sso._secret_generate(32),
     'PasswordReset',
     sso._ttl_timestamptz($2),
-    cte."user_id"
+    cte."id",
+    cte."email"
 FROM
     cte
 RETURNING
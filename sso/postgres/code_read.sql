@@ -0,0 +1,16 @@
+SELECT
+    "client_id",
+    "user_id",
+    "state",
+    "scope",
+    "email"
+FROM
+    sso.code_table
+WHERE
+    "client_id" = $1
+AND
+    "value" = $2
+AND
+    "target" = $3
+AND
+    "ttl" >= CURRENT_TIMESTAMP
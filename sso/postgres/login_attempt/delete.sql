@@ -0,0 +1,6 @@
+DELETE FROM
+    sso.login_attempt_table
+WHERE
+    "target" = $1
+AND
+    "value" = $2
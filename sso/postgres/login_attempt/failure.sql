@@ -0,0 +1,15 @@
+INSERT INTO
+    sso.login_attempt_table("target", "value", "failures")
+VALUES
+    ($1, $2, 1)
+ON CONFLICT
+    ("target", "value")
+DO UPDATE SET
+    "failures" = CASE
+        WHEN sso.login_attempt_table."updated_at" < (CURRENT_TIMESTAMP - ($3::BIGINT * '1s'::INTERVAL))
+            THEN 1
+        ELSE sso.login_attempt_table."failures" + 1
+    END,
+    "locked_until" = NULL
+RETURNING
+    "failures"
@@ -0,0 +1,8 @@
+UPDATE
+    sso.login_attempt_table
+SET
+    "locked_until" = (CURRENT_TIMESTAMP + ($3::BIGINT * '1s'::INTERVAL))
+WHERE
+    "target" = $1
+AND
+    "value" = $2
@@ -0,0 +1,10 @@
+SELECT
+    "locked_until"
+FROM
+    sso.login_attempt_table
+WHERE
+    "target" = $1
+AND
+    "value" = $2
+AND
+    "locked_until" > CURRENT_TIMESTAMP
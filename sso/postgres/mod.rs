@@ -5,6 +5,9 @@ use tokio_postgres::Row;
 #[derive(Clone)]
 pub(crate) struct Postgres {
     pool: deadpool_postgres::Pool,
+    password: ConfigPassword,
+    password_dummy: String,
+    session: ConfigSession,
 }
 
 /// Postgres Query
@@ -17,7 +20,14 @@ impl Postgres {
 
         Self::setup(&pool, &config.oauth2.users).await?;
 
-        Ok(Self { pool })
+        let password_dummy = password::hash_dummy(&config.password)?;
+
+        Ok(Self {
+            pool,
+            password: config.password.clone(),
+            password_dummy,
+            session: config.session.clone(),
+        })
     }
 
     /// Returns ok if postgres connection can execute queries
@@ -92,8 +102,7 @@ impl Postgres {
     }
 
     pub async fn password_hash(&self, password: &str) -> Result<String> {
-        let conn = self.pool.get().await?;
-        PostgresQuery::password_hash(&conn, &password.to_string()).await
+        password::hash_block(&self.password, password).await
     }
 
     pub async fn user_password_check(
@@ -102,16 +111,53 @@ impl Postgres {
         password: &str,
     ) -> Result<PostgresUserPasswordCheck> {
         let conn = self.pool.get().await?;
-        let check = PostgresQuery::user_password_check(&conn, email, password).await?;
+        let check = match PostgresQuery::user_password_check(&conn, email).await? {
+            Some(check) => check,
+            None => {
+                password::verify_block(&self.password_dummy, password).await?;
+                return Err("email not found".into());
+            }
+        };
+        // Password is verified before enable flag is checked so that disabled users take
+        // the same time as other failures
+        if !password::verify_block(&check.hash, password).await? {
+            return Err("password is incorrect".into());
+        }
         if !check.enable {
             return Err("user is disabled".into());
         }
-        if !check.check {
-            return Err("password is incorrect".into());
+
+        // Legacy hashes are upgraded after successful login, static user hashes are
+        // read from configuration and are not updated
+        if !check.static_ && password::rehash_required(&self.password, &check.hash) {
+            let hash = self.password_hash(password).await?;
+            PostgresQuery::user_password_rehash(&conn, &check.id, &check.hash, &hash).await?;
         }
         Ok(check)
     }
 
+    /// Returns ok if password is current password of user
+    pub async fn user_password_check_id(&self, id: Uuid, password: &str) -> Result<()> {
+        let conn = self.pool.get().await?;
+        Self::user_password_verify(&conn, id, password).await?;
+        Ok(())
+    }
+
+    /// Returns current password hash of user if password is correct
+    async fn user_password_verify(
+        conn: &deadpool_postgres::Client,
+        id: Uuid,
+        password: &str,
+    ) -> Result<String> {
+        let hash = PostgresQuery::user_password_read(conn, &id)
+            .await?
+            .ok_or_else(|| Error::from("user not found or password incorrect"))?;
+        if !password::verify_block(&hash, password).await? {
+            return Err("user not found or password incorrect".into());
+        }
+        Ok(hash)
+    }
+
     pub async fn user_read(
         &self,
         client: &Client,
@@ -131,12 +177,18 @@ impl Postgres {
         let updated = PostgresQuery::user_update(&conn, &req).await?;
 
         if updated {
+            if let Some(false) = req.enable {
+                PostgresQuery::session_delete(&conn, req.id, None).await?;
+            }
             if let Some(password) = req.password {
                 self.user_password_update2(req.id, password).await?;
             }
             if let Some(access) = req.access {
                 self.user_access_update(client, req.id, access).await?;
             }
+            if let Some(true) = req.factor_reset {
+                self.user_factor_delete(req.id, None).await?;
+            }
 
             let mut user = self
                 .user_read(
@@ -153,6 +205,18 @@ impl Postgres {
         }
     }
 
+    pub async fn user_password_require_update_set(&self, user_id: Uuid) -> Result<()> {
+        self.user_password_update2(
+            user_id,
+            RequestUserPasswordUpdate {
+                allow_reset: None,
+                require_update: Some(true),
+            },
+        )
+        .await?;
+        Ok(())
+    }
+
     pub async fn user_access_read(
         &self,
         client: &Client,
@@ -162,6 +226,18 @@ impl Postgres {
         PostgresQuery::user_access_read(&conn, &client.client_id, &req).await
     }
 
+    /// Returns access of user to all clients
+    pub async fn user_access_read_many(&self, user_id: Uuid) -> Result<Vec<ResponseAccess>> {
+        let conn = self.pool.get().await?;
+
+        let statement = conn
+            .prepare(include_str!("user/access_read_many.sql"))
+            .await?;
+        let rows = conn.query(&statement, &[&user_id]).await?;
+
+        Ok(rows.into_iter().map(|x| x.into()).collect())
+    }
+
     pub async fn user_access_insert(
         &self,
         client: &Client,
@@ -204,29 +280,153 @@ impl Postgres {
         Ok(user)
     }
 
+    /// Create or update user on registration, if approval is required the user is created
+    /// disabled and pending approval, and registration fails if the user already exists
+    async fn user_register_upsert(&self, approval: bool, name: &str, email: &str) -> Result<Uuid> {
+        if !approval {
+            return self.user_upsert_email(name, email).await;
+        }
+        let conn = self.pool.get().await?;
+        PostgresQuery::user_insert_approval(&conn, name, email)
+            .await?
+            .ok_or_else(|| Error::from("user email already exists"))
+    }
+
+    /// Enable user pending registration approval, returns name and email of user or none
+    /// if user is not pending approval
+    pub async fn user_register_approve(&self, id: Uuid) -> Result<Option<(String, String)>> {
+        let conn = self.pool.get().await?;
+
+        let statement = conn
+            .prepare(include_str!("user/register_approve.sql"))
+            .await?;
+
+        let row = conn.query_opt(&statement, &[&id]).await?;
+        Ok(row.map(|x| (x.get("name"), x.get("email"))))
+    }
+
+    /// Delete user pending registration approval, returns name and email of user or none
+    /// if user is not pending approval
+    pub async fn user_register_deny(&self, id: Uuid) -> Result<Option<(String, String)>> {
+        let conn = self.pool.get().await?;
+
+        let statement = conn.prepare(include_str!("user/register_deny.sql")).await?;
+
+        let row = conn.query_opt(&statement, &[&id]).await?;
+        Ok(row.map(|x| (x.get("name"), x.get("email"))))
+    }
+
     async fn user_upsert_email(&self, name: &str, email: &str) -> Result<Uuid> {
         let conn = self.pool.get().await?;
         PostgresQuery::user_upsert_email(&conn, name, email).await
     }
 
     pub async fn user_password_reset_accept(&self, id: Uuid, password: &str) -> Result<()> {
+        let hash_new = self.password_hash(password).await?;
+
         let conn = self.pool.get().await?;
-        PostgresQuery::user_password_reset_accept(&conn, &id, password).await
+        let hash = PostgresQuery::user_password_read(&conn, &id).await?;
+
+        PostgresQuery::user_password_reset_accept(&conn, &id, &hash_new).await?;
+        PostgresQuery::session_delete(&conn, id, None).await?;
+
+        if let Some(hash) = hash {
+            self.user_password_history_insert(&conn, id, &hash).await?;
+        }
+        Ok(())
     }
 
-    pub async fn user_email_update(&self, id: Uuid, password: &str, email_new: &str) -> Result<()> {
+    /// Returns true if password matches one of the most recent passwords of user, including
+    /// the current password, up to the configured history count
+    pub async fn user_password_history_check(&self, id: Uuid, password: &str) -> Result<bool> {
+        if self.password.history_count == 0 {
+            return Ok(false);
+        }
+
         let conn = self.pool.get().await?;
 
-        let statement = conn.prepare(include_str!("user_email_update.sql")).await?;
+        let statement = conn
+            .prepare(include_str!("user/password_history_read.sql"))
+            .await?;
 
-        let rows = conn
-            .execute(&statement, &[&id, &password, &email_new])
+        let limit = self.password.history_count as i64;
+        let rows = conn.query(&statement, &[&id, &limit]).await?;
+        for row in rows {
+            let hash: String = row.get("hash");
+            if password::verify_block(&hash, password).await? {
+                return Ok(true);
+            }
+        }
+        Ok(false)
+    }
+
+    /// Insert replaced password hash into history, history count includes current password
+    async fn user_password_history_insert(
+        &self,
+        conn: &deadpool_postgres::Client,
+        id: Uuid,
+        hash: &str,
+    ) -> Result<()> {
+        let keep = self.password.history_count.saturating_sub(1) as i64;
+        if keep == 0 {
+            return Ok(());
+        }
+
+        let statement = conn
+            .prepare(include_str!("user/password_history_insert.sql"))
             .await?;
+        conn.execute(&statement, &[&id, &hash]).await?;
 
-        match rows {
-            1 => Ok(()),
-            _ => Err(Error::from("email update failed")),
+        let statement = conn
+            .prepare(include_str!("user/password_history_delete.sql"))
+            .await?;
+        conn.execute(&statement, &[&id, &keep]).await?;
+        Ok(())
+    }
+
+    /// Replace email address of user with a verified email address, returns false if current
+    /// email address of user does not match
+    pub async fn user_email_replace(&self, id: Uuid, email: &str, email_new: &str) -> Result<bool> {
+        let conn = self.pool.get().await?;
+
+        let statement = conn.prepare(include_str!("user/email_replace.sql")).await?;
+
+        let rows = conn
+            .execute(&statement, &[&id, &email, &email_new])
+            .await
+            .map_err(|_e| Error::from("email update failed"))?;
+        Ok(rows == 1)
+    }
+
+    /// Restore previous email address of user whatever the current address is, pending email
+    /// update and revert codes are deleted, and the password is replaced by a random hash so
+    /// that it must be reset using the restored address, sessions of user are revoked
+    pub async fn user_email_revert(&self, id: Uuid, email_old: &str) -> Result<bool> {
+        let hash = password::hash_dummy_block(&self.password).await?;
+        let conn = self.pool.get().await?;
+
+        let statement = conn.prepare(include_str!("user/email_revert.sql")).await?;
+
+        let rows = conn
+            .query(&statement, &[&id, &email_old, &hash])
+            .await
+            .map_err(|_e| Error::from("email revert failed"))?;
+        let reverted = !rows.is_empty();
+        if reverted {
+            PostgresQuery::session_delete(&conn, id, None).await?;
         }
+        Ok(reverted)
+    }
+
+    /// Mark email address of user as verified, returns false if email address of user
+    /// does not match
+    pub async fn user_email_verify(&self, id: Uuid, email: &str) -> Result<bool> {
+        let conn = self.pool.get().await?;
+
+        let statement = conn.prepare(include_str!("user/email_verify.sql")).await?;
+
+        let rows = conn.execute(&statement, &[&id, &email]).await?;
+        Ok(rows == 1)
     }
 
     pub async fn user_password_update(
@@ -237,24 +437,35 @@ impl Postgres {
     ) -> Result<()> {
         let conn = self.pool.get().await?;
 
+        let hash = match Self::user_password_verify(&conn, id, password).await {
+            Ok(hash) => hash,
+            Err(_e) => return Err(Error::from("password update failed")),
+        };
+        let hash_new = self.password_hash(password_new).await?;
+
         let statement = conn
             .prepare(include_str!("user_password_update.sql"))
             .await?;
 
         let rows = conn
-            .execute(&statement, &[&id, &password, &password_new])
+            .execute(&statement, &[&id, &hash, &hash_new, &true])
             .await?;
 
         match rows {
-            1 => Ok(()),
+            1 => {
+                PostgresQuery::session_delete(&conn, id, None).await?;
+                self.user_password_history_insert(&conn, id, &hash).await
+            }
             _ => Err(Error::from("password update failed")),
         }
     }
 
+    #[allow(clippy::too_many_arguments)]
     pub async fn user_register_accept_password(
         &self,
         client: &Client,
         scope: &oauth2::Scope,
+        approval: bool,
         email: &str,
         name: &str,
         password: &str,
@@ -264,7 +475,7 @@ impl Postgres {
             return Err(Error::from("scope invalid"));
         }
 
-        let user_id = self.user_upsert_email(name, email).await?;
+        let user_id = self.user_register_upsert(approval, name, email).await?;
 
         self.user_password_upsert(user_id, password, password_allow_reset, false)
             .await?;
@@ -314,6 +525,7 @@ impl Postgres {
         }
     }
 
+    /// Update password flags of user, password hash is not changed so history is not checked
     async fn user_password_update2(
         &self,
         user_id: Uuid,
@@ -361,10 +573,12 @@ impl Postgres {
             .await
     }
 
+    #[allow(clippy::too_many_arguments)]
     pub async fn user_register_accept_oauth2_provider(
         &self,
         client: &Client,
         scope: &oauth2::Scope,
+        approval: bool,
         email: &str,
         name: &str,
         provider: PostgresOauth2Provider,
@@ -374,7 +588,7 @@ impl Postgres {
             return Err(Error::from("scope invalid"));
         }
 
-        let user_id = self.user_upsert_email(&name, &email).await?;
+        let user_id = self.user_register_upsert(approval, name, email).await?;
 
         let conn = self.pool.get().await?;
 
@@ -398,151 +612,696 @@ impl Postgres {
         Ok(user_id)
     }
 
-    pub async fn user_delete(&self, user_id: Uuid) -> Result<()> {
-        let client = self.pool.get().await?;
-
-        let statement = client.prepare(include_str!("user_delete.sql")).await?;
-
-        client.query(&statement, &[&user_id]).await?;
-        Ok(())
-    }
-
-    pub async fn user_oauth2_provider_check(
+    /// Create user with provider and access on first login using provider, user is disabled
+    /// pending approval if approval is true, fails if a user already exists with email address
+    #[allow(clippy::too_many_arguments)]
+    pub async fn user_provision_oauth2_provider(
         &self,
+        client: &Client,
+        scope: &oauth2::Scope,
+        approval: bool,
+        email: &str,
+        name: &str,
         provider: PostgresOauth2Provider,
         sub: &str,
     ) -> Result<Uuid> {
-        let client = self.pool.get().await?;
+        if !client.user_scope.contains(&scope) {
+            return Err(Error::from("scope invalid"));
+        }
 
-        let statement = client
-            .prepare(include_str!("user_oauth2_provider_check.sql"))
-            .await?;
+        let conn = self.pool.get().await?;
 
-        let rows = client.query(&statement, &[&provider, &sub]).await?;
-        if !rows.is_empty() {
-            let user_id: Uuid = rows[0].get("user_id");
-            Ok(user_id)
+        let user_id = if approval {
+            PostgresQuery::user_insert_approval(&conn, name, email).await?
         } else {
-            Err("access not found".into())
+            PostgresQuery::user_insert_email(&conn, name, email).await?
         }
-    }
-
-    pub async fn csrf_insert(&self, client: &Client) -> Result<ResponseCsrf> {
-        let conn = self.pool.get().await?;
+        .ok_or_else(|| Error::from("user email already exists"))?;
 
-        let statement = conn.prepare(include_str!("csrf_insert.sql")).await?;
+        let statement = conn
+            .prepare(include_str!("user_oauth2_provider_insert.sql"))
+            .await?;
 
-        let rows = conn
-            .query(&statement, &[&client.client_id, &client.ttl.csrf_s])
+        conn.execute(&statement, &[&user_id, &provider, &sub, &false])
             .await?;
 
-        Ok(ResponseCsrf {
-            client_id: rows[0].get("client_id"),
-            token: rows[0].get("token"),
-            created_at: rows[0].get("created_at"),
-            ttl: rows[0].get("ttl"),
-        })
+        self.access_upsert(
+            client,
+            RequestAccessUpdate {
+                user_id,
+                enable: true,
+                scope: scope.to_string(),
+            },
+        )
+        .await?;
+
+        Ok(user_id)
     }
 
-    pub async fn csrf_verify(&self, client: &Client, request: RequestCsrf) -> Result<()> {
+    pub async fn user_upsert_ldap(
+        &self,
+        client: &Client,
+        email: &str,
+        name: &str,
+        scope: Option<oauth2::Scope>,
+    ) -> Result<Uuid> {
         let conn = self.pool.get().await?;
 
-        let statement = conn.prepare(include_str!("csrf_verify.sql")).await?;
+        let statement = conn.prepare(include_str!("user/upsert_ldap.sql")).await?;
 
-        let rows = conn
-            .query(&statement, &[&client.client_id, &request.token])
-            .await?;
-        if !rows.is_empty() {
-            Ok(())
-        } else {
-            Err("csrf token not found or expired".into())
+        let id = Uuid::new_v4();
+        let row = conn.query_one(&statement, &[&id, &name, &email]).await?;
+        let user_id: Uuid = row.get("id");
+        let enable: bool = row.get("enable");
+        if !enable {
+            return Err("user is disabled".into());
         }
-    }
 
-    pub async fn code_insert_auth(
-        &self,
-        client: &Client,
-        ttl_s: i64,
-        user_id: Uuid,
-        state: &str,
-        scope: &str,
-    ) -> Result<String> {
-        let scope = oauth2::Scope::from_string(scope);
-        if !client.user_scope.contains(&scope) {
-            return Err(Error::from("scope invalid"));
+        match scope {
+            Some(scope) => {
+                self.access_upsert(
+                    client,
+                    RequestAccessUpdate {
+                        user_id,
+                        enable: true,
+                        scope: scope.to_string(),
+                    },
+                )
+                .await?;
+            }
+            None => {
+                // Without mapped scope user is given access with register scope of client if
+                // they do not have access, existing access is not changed
+                if !client.user_scope.contains(&client.register_scope) {
+                    return Err(Error::from("scope invalid"));
+                }
+                let statement = conn.prepare(include_str!("access/insert.sql")).await?;
+                conn.execute(
+                    &statement,
+                    &[
+                        &client.client_id,
+                        &user_id,
+                        &client.register_scope.to_string(),
+                    ],
+                )
+                .await?;
+            }
         }
 
-        let conn = self.pool.get().await?;
+        Ok(user_id)
+    }
 
-        let statement = conn.prepare(include_str!("code_insert_auth.sql")).await?;
+    pub async fn user_factor_enabled(&self, user_id: Uuid) -> Result<bool> {
+        let conn = self.pool.get().await?;
 
-        let rows = conn
-            .query(
-                &statement,
-                &[
-                    &client.client_id,
-                    &ttl_s,
-                    &user_id,
-                    &state,
-                    &scope.to_string(),
-                ],
-            )
+        let statement = conn
+            .prepare(include_str!("user/factor_enabled.sql"))
             .await?;
-        let value: &str = rows[0].get(0);
 
-        Ok(value.to_string())
+        let row = conn.query_one(&statement, &[&user_id]).await?;
+        let count: i64 = row.get("count");
+        Ok(count > 0)
     }
 
-    pub async fn code_insert_password_reset(
+    pub async fn user_factor_delete(
         &self,
-        client_id: Uuid,
-        ttl_s: i64,
-        email: &str,
-    ) -> Result<String> {
-        let client = self.pool.get().await?;
-
-        let statement = client
-            .prepare(include_str!("code_insert_password_reset.sql"))
-            .await?;
+        user_id: Uuid,
+        factor: Option<PostgresUserFactor>,
+    ) -> Result<()> {
+        let conn = self.pool.get().await?;
 
-        let rows = client
-            .query(&statement, &[&client_id, &ttl_s, &email])
-            .await?;
-        if !rows.is_empty() {
-            let value: String = rows[0].get("value");
+        let statement = conn.prepare(include_str!("user/factor_delete.sql")).await?;
 
-            Ok(value)
-        } else {
-            Err("email not found".into())
-        }
+        conn.execute(&statement, &[&user_id, &factor]).await?;
+        Ok(())
     }
 
-    pub async fn code_insert_register(
+    pub async fn user_totp_read(
         &self,
-        client_id: Uuid,
-        ttl_s: i64,
-        email: &str,
-    ) -> Result<String> {
-        let client = self.pool.get().await?;
-
-        let statement = client
-            .prepare(include_str!("code_insert_register.sql"))
-            .await?;
+        key: &str,
+        user_id: Uuid,
+    ) -> Result<Option<PostgresUserTotp>> {
+        let conn = self.pool.get().await?;
 
-        let rows = client
-            .query(&statement, &[&client_id, &ttl_s, &email])
+        let statement = conn
+            .prepare(include_str!("user/factor_totp_read.sql"))
             .await?;
-        if !rows.is_empty() {
-            let value: String = rows[0].get("value");
 
-            Ok(value)
-        } else {
-            Err("email not found".into())
+        let row = conn.query_opt(&statement, &[&user_id, &key]).await?;
+        match row {
+            Some(row) => {
+                let check: bool = row.get("check");
+                if !check {
+                    return Err(Error::from("totp secret check failed"));
+                }
+                Ok(Some(PostgresUserTotp {
+                    enable: row.get("enable"),
+                    step: row.get("step"),
+                    secret: row.get("value"),
+                }))
+            }
+            None => Ok(None),
         }
     }
 
-    pub async fn code_insert_delete(
-        &self,
+    pub async fn user_totp_insert(&self, key: &str, user_id: Uuid, secret: &str) -> Result<()> {
+        let conn = self.pool.get().await?;
+
+        let statement = conn
+            .prepare(include_str!("user/factor_totp_insert.sql"))
+            .await?;
+
+        conn.execute(&statement, &[&user_id, &key, &secret]).await?;
+        Ok(())
+    }
+
+    pub async fn user_totp_update(&self, user_id: Uuid, step: i64) -> Result<()> {
+        let conn = self.pool.get().await?;
+
+        let statement = conn
+            .prepare(include_str!("user/factor_totp_update.sql"))
+            .await?;
+
+        let rows = conn.execute(&statement, &[&user_id, &step]).await?;
+        match rows {
+            1 => Ok(()),
+            _ => Err(Error::from("totp code already used")),
+        }
+    }
+
+    pub async fn user_recovery_insert(
+        &self,
+        key: &str,
+        user_id: Uuid,
+        codes: &[String],
+    ) -> Result<()> {
+        let conn = self.pool.get().await?;
+
+        let statement = conn
+            .prepare(include_str!("user/recovery_insert.sql"))
+            .await?;
+
+        conn.execute(&statement, &[&user_id, &key, &codes]).await?;
+        Ok(())
+    }
+
+    pub async fn user_recovery_count(&self, user_id: Uuid) -> Result<i64> {
+        let conn = self.pool.get().await?;
+
+        let statement = conn
+            .prepare(include_str!("user/recovery_count.sql"))
+            .await?;
+
+        let row = conn.query_one(&statement, &[&user_id]).await?;
+        Ok(row.get("count"))
+    }
+
+    pub async fn user_recovery_verify(&self, key: &str, user_id: Uuid, code: &str) -> Result<()> {
+        let conn = self.pool.get().await?;
+
+        let statement = conn
+            .prepare(include_str!("user/recovery_verify.sql"))
+            .await?;
+
+        let rows = conn.query(&statement, &[&user_id, &key, &code]).await?;
+        if !rows.is_empty() {
+            Ok(())
+        } else {
+            Err(Error::from("recovery code is incorrect"))
+        }
+    }
+
+    pub async fn user_recovery_delete(&self, user_id: Uuid) -> Result<()> {
+        let conn = self.pool.get().await?;
+
+        let statement = conn
+            .prepare(include_str!("user/recovery_delete.sql"))
+            .await?;
+
+        conn.execute(&statement, &[&user_id]).await?;
+        Ok(())
+    }
+
+    pub async fn user_webauthn_read(
+        &self,
+        user_id: Option<Uuid>,
+        credential_id: Option<&str>,
+    ) -> Result<Vec<PostgresUserWebauthn>> {
+        let conn = self.pool.get().await?;
+
+        let statement = conn.prepare(include_str!("user/webauthn_read.sql")).await?;
+
+        let rows = conn.query(&statement, &[&user_id, &credential_id]).await?;
+        Ok(rows
+            .into_iter()
+            .map(|row| PostgresUserWebauthn {
+                created_at: row.get("created_at"),
+                credential_id: row.get("credential_id"),
+                user_id: row.get("user_id"),
+                public_key: row.get("public_key"),
+                sign_count: row.get("sign_count"),
+                name: row.get("name"),
+            })
+            .collect())
+    }
+
+    pub async fn user_webauthn_insert(
+        &self,
+        user_id: Uuid,
+        credential: &webauthn::Credential,
+        name: &str,
+    ) -> Result<()> {
+        let conn = self.pool.get().await?;
+
+        let statement = conn
+            .prepare(include_str!("user/webauthn_insert.sql"))
+            .await?;
+
+        conn.execute(
+            &statement,
+            &[
+                &credential.id,
+                &user_id,
+                &credential.public_key,
+                &i64::from(credential.sign_count),
+                &name,
+            ],
+        )
+        .await?;
+        Ok(())
+    }
+
+    pub async fn user_webauthn_update(
+        &self,
+        user_id: Uuid,
+        credential_id: &str,
+        sign_count: i64,
+        sign_count_previous: i64,
+    ) -> Result<()> {
+        let conn = self.pool.get().await?;
+
+        let statement = conn
+            .prepare(include_str!("user/webauthn_update.sql"))
+            .await?;
+
+        let rows = conn
+            .execute(
+                &statement,
+                &[&credential_id, &user_id, &sign_count, &sign_count_previous],
+            )
+            .await?;
+        match rows {
+            1 => Ok(()),
+            _ => Err(Error::from("webauthn assertion already used")),
+        }
+    }
+
+    pub async fn user_webauthn_delete(&self, user_id: Uuid, credential_id: &str) -> Result<()> {
+        let conn = self.pool.get().await?;
+
+        let statement = conn
+            .prepare(include_str!("user/webauthn_delete.sql"))
+            .await?;
+
+        let rows = conn
+            .execute(&statement, &[&credential_id, &user_id])
+            .await?;
+        match rows {
+            1 => Ok(()),
+            _ => Err(Error::from("webauthn credential not found")),
+        }
+    }
+
+    pub async fn user_password_require_update(&self, user_id: Uuid) -> Result<bool> {
+        let conn = self.pool.get().await?;
+
+        let statement = conn
+            .prepare(include_str!("user/password_require_update.sql"))
+            .await?;
+
+        let row = conn.query_opt(&statement, &[&user_id]).await?;
+        Ok(row.map(|x| x.get("require_update")).unwrap_or(false))
+    }
+
+    /// Returns time password of user was last changed, static user passwords do not expire
+    pub async fn user_password_hash_updated_at(
+        &self,
+        user_id: Uuid,
+    ) -> Result<Option<DateTime<Utc>>> {
+        let conn = self.pool.get().await?;
+
+        let statement = conn
+            .prepare(include_str!("user/password_expiry_read.sql"))
+            .await?;
+
+        let row = conn.query_opt(&statement, &[&user_id]).await?;
+        Ok(row.and_then(|x| {
+            let static_: bool = x.get("static");
+            if static_ {
+                None
+            } else {
+                Some(x.get("hash_updated_at"))
+            }
+        }))
+    }
+
+    pub async fn user_password_expire_warned_set(&self, user_id: Uuid) -> Result<()> {
+        let conn = self.pool.get().await?;
+
+        let statement = conn
+            .prepare(include_str!("user/password_expire_warned.sql"))
+            .await?;
+
+        conn.execute(&statement, &[&user_id]).await?;
+        Ok(())
+    }
+
+    pub async fn user_delete(&self, user_id: Uuid) -> Result<()> {
+        let client = self.pool.get().await?;
+
+        let statement = client.prepare(include_str!("user_delete.sql")).await?;
+
+        client.query(&statement, &[&user_id]).await?;
+        Ok(())
+    }
+
+    pub async fn user_oauth2_provider_check(
+        &self,
+        provider: PostgresOauth2Provider,
+        sub: &str,
+    ) -> Result<Uuid> {
+        let client = self.pool.get().await?;
+
+        let statement = client
+            .prepare(include_str!("user_oauth2_provider_check.sql"))
+            .await?;
+
+        let rows = client.query(&statement, &[&provider, &sub]).await?;
+        if !rows.is_empty() {
+            let user_id: Uuid = rows[0].get("user_id");
+            Ok(user_id)
+        } else {
+            Err("access not found".into())
+        }
+    }
+
+    pub async fn user_oauth2_provider_read(
+        &self,
+        user_id: Uuid,
+    ) -> Result<Vec<ResponseUserOauth2Provider>> {
+        let conn = self.pool.get().await?;
+
+        let statement = conn
+            .prepare(include_str!("user_oauth2_provider_read.sql"))
+            .await?;
+
+        let rows = conn.query(&statement, &[&user_id]).await?;
+        Ok(rows
+            .into_iter()
+            .map(|row| {
+                let provider: PostgresOauth2Provider = row.get("oauth2_provider");
+                ResponseUserOauth2Provider {
+                    created_at: row.get("created_at"),
+                    user_id: row.get("user_id"),
+                    oauth2_provider: provider.as_str().to_string(),
+                    sub: row.get("sub"),
+                    static_: row.get("static"),
+                }
+            })
+            .collect())
+    }
+
+    pub async fn user_oauth2_provider_link(
+        &self,
+        user_id: Uuid,
+        provider: PostgresOauth2Provider,
+        sub: &str,
+    ) -> Result<()> {
+        if let Ok(linked_user_id) = self.user_oauth2_provider_check(provider, sub).await {
+            if linked_user_id == user_id {
+                return Err(Error::from("oauth2 provider is already linked"));
+            }
+            return Err(Error::from("oauth2 provider is linked to another user"));
+        }
+
+        let conn = self.pool.get().await?;
+
+        let statement = conn
+            .prepare(include_str!("user_oauth2_provider_insert.sql"))
+            .await?;
+
+        conn.execute(&statement, &[&user_id, &provider, &sub, &false])
+            .await?;
+        Ok(())
+    }
+
+    /// Unlink provider from user, fails if provider is the last credential
+    /// of user (user has no password and no other providers)
+    pub async fn user_oauth2_provider_unlink(
+        &self,
+        user_id: Uuid,
+        provider: PostgresOauth2Provider,
+    ) -> Result<Vec<String>> {
+        let linked = self.user_oauth2_provider_read(user_id).await?;
+        if !linked
+            .iter()
+            .any(|x| x.oauth2_provider == provider.as_str() && !x.static_)
+        {
+            return Err(Error::from("oauth2 provider is not linked"));
+        }
+
+        let conn = self.pool.get().await?;
+
+        let statement = conn
+            .prepare(include_str!("user_oauth2_provider_delete.sql"))
+            .await?;
+
+        let rows = conn.query(&statement, &[&user_id, &provider]).await?;
+        if !rows.is_empty() {
+            Ok(rows.into_iter().map(|row| row.get("sub")).collect())
+        } else {
+            Err(Error::from(
+                "oauth2 provider is the last credential and cannot be unlinked",
+            ))
+        }
+    }
+
+    pub async fn csrf_insert(&self, client: &Client) -> Result<ResponseCsrf> {
+        let conn = self.pool.get().await?;
+
+        let statement = conn.prepare(include_str!("csrf_insert.sql")).await?;
+
+        let rows = conn
+            .query(&statement, &[&client.client_id, &client.ttl.csrf_s])
+            .await?;
+
+        Ok(ResponseCsrf {
+            client_id: rows[0].get("client_id"),
+            token: rows[0].get("token"),
+            created_at: rows[0].get("created_at"),
+            ttl: rows[0].get("ttl"),
+        })
+    }
+
+    pub async fn csrf_verify(&self, client: &Client, request: RequestCsrf) -> Result<()> {
+        let conn = self.pool.get().await?;
+
+        let statement = conn.prepare(include_str!("csrf_verify.sql")).await?;
+
+        let rows = conn
+            .query(&statement, &[&client.client_id, &request.token])
+            .await?;
+        if !rows.is_empty() {
+            Ok(())
+        } else {
+            Err("csrf token not found or expired".into())
+        }
+    }
+
+    pub async fn webauthn_challenge_insert(
+        &self,
+        client: &Client,
+        user_id: Option<Uuid>,
+        target: PostgresWebauthnTarget,
+    ) -> Result<String> {
+        let conn = self.pool.get().await?;
+
+        let statement = conn
+            .prepare(include_str!("webauthn_challenge_insert.sql"))
+            .await?;
+
+        let challenge = webauthn::challenge_generate()?;
+        conn.execute(
+            &statement,
+            &[
+                &client.client_id,
+                &user_id,
+                &challenge,
+                &target,
+                &client.ttl.csrf_s,
+            ],
+        )
+        .await?;
+        Ok(challenge)
+    }
+
+    pub async fn webauthn_challenge_verify(
+        &self,
+        client: &Client,
+        challenge: &str,
+        target: PostgresWebauthnTarget,
+    ) -> Result<Option<Uuid>> {
+        let conn = self.pool.get().await?;
+
+        let statement = conn
+            .prepare(include_str!("webauthn_challenge_verify.sql"))
+            .await?;
+
+        let row = conn
+            .query_opt(&statement, &[&client.client_id, &challenge, &target])
+            .await?;
+        match row {
+            Some(row) => Ok(row.get("user_id")),
+            None => Err("webauthn challenge not found or expired".into()),
+        }
+    }
+
+    pub async fn code_insert_auth(
+        &self,
+        client: &Client,
+        ttl_s: i64,
+        user_id: Uuid,
+        state: &str,
+        scope: &str,
+    ) -> Result<String> {
+        let scope = oauth2::Scope::from_string(scope);
+        if !client.user_scope.contains(&scope) {
+            return Err(Error::from("scope invalid"));
+        }
+
+        let conn = self.pool.get().await?;
+
+        let statement = conn.prepare(include_str!("code_insert_auth.sql")).await?;
+
+        let rows = conn
+            .query(
+                &statement,
+                &[
+                    &client.client_id,
+                    &ttl_s,
+                    &user_id,
+                    &state,
+                    &scope.to_string(),
+                ],
+            )
+            .await?;
+        let value: &str = rows[0].get(0);
+
+        Ok(value.to_string())
+    }
+
+    /// Returns none if email is not found
+    pub async fn code_insert_password_reset(
+        &self,
+        client_id: Uuid,
+        ttl_s: i64,
+        email: &str,
+    ) -> Result<Option<String>> {
+        let client = self.pool.get().await?;
+
+        let statement = client
+            .prepare(include_str!("code_insert_password_reset.sql"))
+            .await?;
+
+        let rows = client
+            .query(&statement, &[&client_id, &ttl_s, &email])
+            .await?;
+        Ok(rows.first().map(|x| x.get("value")))
+    }
+
+    /// Returns none if email is not found or user does not have access to client
+    pub async fn code_insert_login(
+        &self,
+        client: &Client,
+        ttl_s: i64,
+        email: &str,
+        state: &str,
+        scope: &oauth2::Scope,
+    ) -> Result<Option<String>> {
+        if !client.user_scope.contains(scope) {
+            return Err(Error::from("scope invalid"));
+        }
+
+        let conn = self.pool.get().await?;
+
+        let statement = conn.prepare(include_str!("code_insert_login.sql")).await?;
+
+        let rows = conn
+            .query(
+                &statement,
+                &[
+                    &client.client_id,
+                    &ttl_s,
+                    &email,
+                    &state,
+                    &scope.to_string(),
+                ],
+            )
+            .await?;
+        Ok(rows.first().map(|x| x.get("value")))
+    }
+
+    pub async fn code_insert_register(
+        &self,
+        client_id: Uuid,
+        ttl_s: i64,
+        email: &str,
+    ) -> Result<String> {
+        let client = self.pool.get().await?;
+
+        let statement = client
+            .prepare(include_str!("code_insert_register.sql"))
+            .await?;
+
+        let rows = client
+            .query(&statement, &[&client_id, &ttl_s, &email])
+            .await?;
+        if !rows.is_empty() {
+            let value: String = rows[0].get("value");
+
+            Ok(value)
+        } else {
+            Err("email not found".into())
+        }
+    }
+
+    pub async fn code_insert_invite(
+        &self,
+        client_id: Uuid,
+        ttl_s: i64,
+        email: &str,
+        name: &str,
+        scope: &oauth2::Scope,
+    ) -> Result<String> {
+        let client = self.pool.get().await?;
+
+        let statement = client
+            .prepare(include_str!("code_insert_invite.sql"))
+            .await?;
+
+        let rows = client
+            .query(
+                &statement,
+                &[&client_id, &ttl_s, &email, &name, &scope.to_string()],
+            )
+            .await?;
+        let value: String = rows[0].get("value");
+
+        Ok(value)
+    }
+
+    pub async fn code_insert_delete(
+        &self,
         client_id: Uuid,
         ttl_s: i64,
         user_id: Uuid,
@@ -550,12 +1309,14 @@ impl Postgres {
     ) -> Result<(String, String)> {
         let client = self.pool.get().await?;
 
+        let hash = Self::user_password_verify(&client, user_id, password).await?;
+
         let statement = client
             .prepare(include_str!("code_insert_delete.sql"))
             .await?;
 
         let rows = client
-            .query(&statement, &[&client_id, &ttl_s, &user_id, &password])
+            .query(&statement, &[&client_id, &ttl_s, &user_id, &hash])
             .await?;
         if !rows.is_empty() {
             let value: String = rows[0].get(0);
@@ -567,6 +1328,105 @@ impl Postgres {
         }
     }
 
+    /// Insert email verification code, email address of user is stored with code so that
+    /// it does not verify an address changed after the code was sent, returns none if user
+    /// is disabled or email address is already verified
+    pub async fn code_insert_email_verify(
+        &self,
+        client_id: Uuid,
+        ttl_s: i64,
+        user_id: Uuid,
+    ) -> Result<Option<(String, String)>> {
+        let client = self.pool.get().await?;
+
+        let statement = client
+            .prepare(include_str!("code_insert_email_verify.sql"))
+            .await?;
+
+        let rows = client
+            .query(&statement, &[&client_id, &ttl_s, &user_id])
+            .await?;
+        Ok(rows.first().map(|x| (x.get("value"), x.get("email"))))
+    }
+
+    /// Insert email update code, new email address is stored with code and current email
+    /// address is stored in state, returns code and current email address
+    pub async fn code_insert_email_update(
+        &self,
+        client_id: Uuid,
+        ttl_s: i64,
+        user_id: Uuid,
+        password: &str,
+        email_new: &str,
+    ) -> Result<(String, String)> {
+        let client = self.pool.get().await?;
+
+        let hash = Self::user_password_verify(&client, user_id, password).await?;
+
+        let statement = client
+            .prepare(include_str!("code_insert_email_update.sql"))
+            .await?;
+
+        let rows = client
+            .query(
+                &statement,
+                &[&client_id, &ttl_s, &user_id, &hash, &email_new],
+            )
+            .await?;
+        if !rows.is_empty() {
+            Ok((rows[0].get("value"), rows[0].get("state")))
+        } else {
+            Err("user not found or password incorrect".into())
+        }
+    }
+
+    /// Insert email revert code, previous email address is stored with code and new email
+    /// address is stored in state
+    pub async fn code_insert_email_revert(
+        &self,
+        client_id: Uuid,
+        ttl_s: i64,
+        user_id: Uuid,
+        email_old: &str,
+        email_new: &str,
+    ) -> Result<String> {
+        let client = self.pool.get().await?;
+
+        let statement = client
+            .prepare(include_str!("code_insert_email_revert.sql"))
+            .await?;
+
+        let rows = client
+            .query(
+                &statement,
+                &[&client_id, &ttl_s, &user_id, &email_new, &email_old],
+            )
+            .await?;
+        let value: String = rows[0].get("value");
+
+        Ok(value)
+    }
+
+    /// Insert MFA code, state of code is authentication method of first factor
+    pub async fn code_insert_mfa(
+        &self,
+        client_id: Uuid,
+        ttl_s: i64,
+        user_id: Uuid,
+        method: &str,
+    ) -> Result<String> {
+        let client = self.pool.get().await?;
+
+        let statement = client.prepare(include_str!("code_insert_mfa.sql")).await?;
+
+        let rows = client
+            .query(&statement, &[&client_id, &ttl_s, &user_id, &method])
+            .await?;
+        let value: String = rows[0].get("value");
+
+        Ok(value)
+    }
+
     pub async fn code_verify(
         &self,
         client_id: Uuid,
@@ -577,23 +1437,32 @@ impl Postgres {
 
         let statement = client.prepare(include_str!("code_verify.sql")).await?;
 
-        let rows = client
+        let mut rows = client
             .query(&statement, &[&client_id, &value, &target])
             .await?;
         if !rows.is_empty() {
-            let client_id: Uuid = rows[0].get("client_id");
-            let user_id: Option<Uuid> = rows[0].get("user_id");
-            let state: String = rows[0].get("state");
-            let scope: String = rows[0].get("scope");
-            let email: String = rows[0].get("email");
+            Ok(rows.remove(0).into())
+        } else {
+            Err("code not found or expired".into())
+        }
+    }
 
-            Ok(PostgresCode {
-                client_id,
-                user_id,
-                state,
-                scope: scope.into(),
-                email,
-            })
+    /// Returns code without consuming it, used to check requests before verifying code
+    pub async fn code_read(
+        &self,
+        client_id: Uuid,
+        value: &str,
+        target: PostgresCodeTarget,
+    ) -> Result<PostgresCode> {
+        let client = self.pool.get().await?;
+
+        let statement = client.prepare(include_str!("code_read.sql")).await?;
+
+        let mut rows = client
+            .query(&statement, &[&client_id, &value, &target])
+            .await?;
+        if !rows.is_empty() {
+            Ok(rows.remove(0).into())
         } else {
             Err("code not found or expired".into())
         }
@@ -613,19 +1482,95 @@ impl Postgres {
         }
     }
 
-    pub async fn oauth2_code_insert_auth(
+    pub async fn oauth2_code_insert_auth(
+        &self,
+        client_id: Uuid,
+        ttl_s: i64,
+        provider: PostgresOauth2Provider,
+        csrf: &str,
+        pkce: Option<&str>,
+        nonce: Option<&str>,
+        req: oauth2::AuthorizationCodeRequest,
+    ) -> Result<String> {
+        let client = self.pool.get().await?;
+
+        let statement = client
+            .prepare(include_str!("oauth2_code_insert_auth.sql"))
+            .await?;
+
+        let rows = client
+            .query(
+                &statement,
+                &[
+                    &client_id,
+                    &csrf,
+                    &provider,
+                    &PostgresOauth2Target::Auth,
+                    &ttl_s,
+                    &pkce.unwrap_or(""),
+                    &nonce.unwrap_or(""),
+                    &req.redirect_uri().to_string(),
+                    &req.state(),
+                    &req.scope().to_string(),
+                ],
+            )
+            .await?;
+        let csrf: String = rows[0].get("csrf");
+
+        Ok(csrf)
+    }
+
+    pub async fn oauth2_code_insert_register(
+        &self,
+        client_id: Uuid,
+        ttl_s: i64,
+        provider: PostgresOauth2Provider,
+        csrf: &str,
+        pkce: Option<&str>,
+        nonce: Option<&str>,
+        email: &str,
+    ) -> Result<String> {
+        let client = self.pool.get().await?;
+
+        let statement = client
+            .prepare(include_str!("oauth2_code_insert_register.sql"))
+            .await?;
+
+        let rows = client
+            .query(
+                &statement,
+                &[
+                    &client_id,
+                    &csrf,
+                    &provider,
+                    &PostgresOauth2Target::Register,
+                    &ttl_s,
+                    &pkce.unwrap_or(""),
+                    &nonce.unwrap_or(""),
+                    &email,
+                ],
+            )
+            .await?;
+        let csrf: String = rows[0].get("csrf");
+
+        Ok(csrf)
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    pub async fn oauth2_code_insert_invite(
         &self,
         client_id: Uuid,
         ttl_s: i64,
         provider: PostgresOauth2Provider,
         csrf: &str,
         pkce: Option<&str>,
-        req: oauth2::AuthorizationCodeRequest,
+        nonce: Option<&str>,
+        invite_code: &str,
     ) -> Result<String> {
         let client = self.pool.get().await?;
 
         let statement = client
-            .prepare(include_str!("oauth2_code_insert_auth.sql"))
+            .prepare(include_str!("oauth2_code_insert_invite.sql"))
             .await?;
 
         let rows = client
@@ -635,12 +1580,10 @@ impl Postgres {
                     &client_id,
                     &csrf,
                     &provider,
-                    &PostgresOauth2Target::Auth,
                     &ttl_s,
                     &pkce.unwrap_or(""),
-                    &req.redirect_uri().to_string(),
-                    &req.state(),
-                    &req.scope().to_string(),
+                    &nonce.unwrap_or(""),
+                    &invite_code,
                 ],
             )
             .await?;
@@ -649,32 +1592,34 @@ impl Postgres {
         Ok(csrf)
     }
 
-    pub async fn oauth2_code_insert_register(
+    pub async fn oauth2_code_insert_link(
         &self,
-        client_id: Uuid,
-        ttl_s: i64,
+        client: &Client,
+        user_id: Uuid,
         provider: PostgresOauth2Provider,
         csrf: &str,
         pkce: Option<&str>,
-        email: &str,
+        nonce: Option<&str>,
     ) -> Result<String> {
-        let client = self.pool.get().await?;
+        let conn = self.pool.get().await?;
 
-        let statement = client
-            .prepare(include_str!("oauth2_code_insert_register.sql"))
+        let statement = conn
+            .prepare(include_str!("oauth2_code_insert_link.sql"))
             .await?;
 
-        let rows = client
+        let rows = conn
             .query(
                 &statement,
                 &[
-                    &client_id,
+                    &client.client_id,
                     &csrf,
                     &provider,
-                    &PostgresOauth2Target::Register,
-                    &ttl_s,
+                    &PostgresOauth2Target::Link,
+                    &client.ttl.oauth2_code_s,
                     &pkce.unwrap_or(""),
-                    &email,
+                    &nonce.unwrap_or(""),
+                    &client.redirect_uri.to_string(),
+                    &user_id,
                 ],
             )
             .await?;
@@ -723,10 +1668,12 @@ impl Postgres {
                 provider: rows[0].get("provider"),
                 target: rows[0].get("target"),
                 pkce: rows[0].get("pkce"),
+                nonce: rows[0].get("nonce"),
                 redirect_uri,
                 state: rows[0].get("state"),
                 scope: rows[0].get("scope"),
                 email: rows[0].get("email"),
+                user_id: rows[0].get("user_id"),
             })
         } else {
             Err("code not found or expired".into())
@@ -836,8 +1783,12 @@ impl Postgres {
             let scope: String = rows[0].get("scope");
             let username: &str = rows[0].get("username");
             let sub: &str = rows[0].get("sub");
+            let email_verified: bool = rows[0].get("email_verified");
             Ok(Some(oauth2::IntrospectionResponseArgs::new(
-                scope, username, sub,
+                scope,
+                username,
+                sub,
+                email_verified,
             )))
         } else {
             Ok(None)
@@ -1029,6 +1980,229 @@ impl Postgres {
         }
     }
 
+    /// Returns time until which login attempt target is locked, none if not locked
+    pub async fn login_attempt_locked(
+        &self,
+        target: PostgresLoginAttemptTarget,
+        value: &str,
+    ) -> Result<Option<DateTime<Utc>>> {
+        let conn = self.pool.get().await?;
+
+        let statement = conn
+            .prepare(include_str!("login_attempt/locked.sql"))
+            .await?;
+
+        let row = conn.query_opt(&statement, &[&target, &value]).await?;
+        Ok(row.map(|x| x.get("locked_until")))
+    }
+
+    /// Increment and return number of failed login attempts for target, failures are reset
+    /// if there has not been a failure for reset_s seconds
+    pub async fn login_attempt_failure(
+        &self,
+        target: PostgresLoginAttemptTarget,
+        value: &str,
+        reset_s: i64,
+    ) -> Result<i32> {
+        let conn = self.pool.get().await?;
+
+        let statement = conn
+            .prepare(include_str!("login_attempt/failure.sql"))
+            .await?;
+
+        let row = conn
+            .query_one(&statement, &[&target, &value, &reset_s])
+            .await?;
+        Ok(row.get("failures"))
+    }
+
+    pub async fn login_attempt_lock(
+        &self,
+        target: PostgresLoginAttemptTarget,
+        value: &str,
+        lockout_s: i64,
+    ) -> Result<()> {
+        let conn = self.pool.get().await?;
+
+        let statement = conn.prepare(include_str!("login_attempt/lock.sql")).await?;
+
+        conn.execute(&statement, &[&target, &value, &lockout_s])
+            .await?;
+        Ok(())
+    }
+
+    /// Delete failed login attempts and any lockout for target, returns true if target had
+    /// failed login attempts
+    pub async fn login_attempt_delete(
+        &self,
+        target: PostgresLoginAttemptTarget,
+        value: &str,
+    ) -> Result<bool> {
+        let conn = self.pool.get().await?;
+
+        let statement = conn
+            .prepare(include_str!("login_attempt/delete.sql"))
+            .await?;
+
+        let rows = conn.execute(&statement, &[&target, &value]).await?;
+        Ok(rows > 0)
+    }
+
+    /// Remember device of user login, returns true if device is not known and user has
+    /// logged in from other devices, first device of user is not considered new
+    pub async fn user_device_login(&self, user_id: Uuid, fingerprint: &str) -> Result<bool> {
+        let conn = self.pool.get().await?;
+
+        // Expired devices of user are deleted on login, see `user_device_retention` for others
+        PostgresQuery::user_device_retention(&conn, &self.session, Some(user_id)).await?;
+
+        let statement = conn.prepare(include_str!("user/device_upsert.sql")).await?;
+
+        let row = conn
+            .query_one(&statement, &[&user_id, &fingerprint])
+            .await?;
+        let inserted: bool = row.get("inserted");
+        let count: i64 = row.get("count");
+        Ok(inserted && count > 0)
+    }
+
+    /// Delete devices which have not been seen for longer than configured, returns number
+    /// of deleted devices
+    pub async fn user_device_retention(&self) -> Result<u64> {
+        let conn = self.pool.get().await?;
+        PostgresQuery::user_device_retention(&conn, &self.session, None).await
+    }
+
+    /// Create session for user, returns session value stored in identity cookie
+    pub async fn session_create(
+        &self,
+        audit: &Audit,
+        user_id: Uuid,
+        methods: &[String],
+    ) -> Result<String> {
+        let id = Uuid::new_v4();
+        let secret = self.secret_generate().await?;
+        let secret_hash = self.secret_hash(&secret, &id.to_string()).await?;
+        // Values are sent by client and truncated to length of column constraints
+        let user_agent: String = audit
+            .user_agent()
+            .unwrap_or_default()
+            .chars()
+            .take(1000)
+            .collect();
+        let remote_addr: String = audit
+            .realip_remote_addr()
+            .unwrap_or_default()
+            .chars()
+            .take(500)
+            .collect();
+
+        let conn = self.pool.get().await?;
+
+        // Expired sessions of user are deleted on login, see `session_retention` for others
+        PostgresQuery::session_retention(&conn, &self.session, Some(user_id)).await?;
+
+        let statement = conn.prepare(include_str!("session/insert.sql")).await?;
+        conn.execute(
+            &statement,
+            &[
+                &id,
+                &user_id,
+                &secret_hash,
+                &user_agent,
+                &remote_addr,
+                &methods.join(" "),
+            ],
+        )
+        .await?;
+
+        Ok(format!("{}.{}", id, secret))
+    }
+
+    /// Returns session of value if session exists and user is enabled, last seen time
+    /// of session is updated
+    pub async fn session_verify(&self, value: &str) -> Result<Option<PostgresSession>> {
+        let (id, secret) = match self.key_secret_extract(value) {
+            Ok(x) => x,
+            Err(_e) => return Ok(None),
+        };
+
+        let conn = self.pool.get().await?;
+
+        let statement = conn.prepare(include_str!("session/verify.sql")).await?;
+        let row = conn
+            .query_opt(
+                &statement,
+                &[&id, &secret, &self.session.idle_s, &self.session.lifetime_s],
+            )
+            .await?;
+
+        Ok(row.map(|x| {
+            let methods: String = x.get("methods");
+            PostgresSession {
+                id: x.get("id"),
+                user_id: x.get("user_id"),
+                methods: methods.split_whitespace().map(String::from).collect(),
+            }
+        }))
+    }
+
+    pub async fn session_read(
+        &self,
+        req: RequestUserSessionRead,
+    ) -> Result<ResponseUserSessionMany> {
+        let conn = self.pool.get().await?;
+
+        let statement = conn.prepare(include_str!("session/read.sql")).await?;
+        let rows = conn.query(&statement, &[&req.user_id]).await?;
+
+        Ok(ResponseUserSessionMany {
+            data: rows.into_iter().map(|x| x.into()).collect(),
+        })
+    }
+
+    /// Delete sessions which have been idle or have existed for longer than configured,
+    /// returns number of deleted sessions
+    pub async fn session_retention(&self) -> Result<u64> {
+        let conn = self.pool.get().await?;
+        PostgresQuery::session_retention(&conn, &self.session, None).await
+    }
+
+    /// Delete session of user, or all sessions of user if id is none
+    pub async fn session_delete(&self, user_id: Uuid, id: Option<Uuid>) -> Result<u64> {
+        let conn = self.pool.get().await?;
+        PostgresQuery::session_delete(&conn, user_id, id).await
+    }
+
+    /// Revoke session of user, or all sessions of user if id is none, revoke is audited
+    pub async fn session_revoke(
+        &self,
+        mut audit: Audit,
+        req: RequestUserSessionDelete,
+    ) -> Result<()> {
+        let count = self.session_delete(req.user_id, req.id).await?;
+
+        audit.set_user_id(req.user_id);
+        audit.set_data("session_id", json!(req.id));
+        audit.set_data("session_count", json!(count));
+        audit.set_status_ok();
+        self.audit_insert(audit).await?;
+        Ok(())
+    }
+
+    /// Unlock email address locked by failed logins, unlock is audited
+    pub async fn user_unlock(&self, mut audit: Audit, email: &str) -> Result<()> {
+        let unlocked = self
+            .login_attempt_delete(PostgresLoginAttemptTarget::Email, &lockout::email(email))
+            .await?;
+
+        audit.subject = Some(email.to_string());
+        audit.set_data("unlocked", json!(unlocked));
+        audit.set_status_ok();
+        self.audit_insert(audit).await?;
+        Ok(())
+    }
+
     pub async fn audit_insert(&self, audit: Audit) -> Result<ResponseAudit> {
         let conn = self.pool.get().await?;
 
@@ -1059,6 +2233,16 @@ impl Postgres {
         PostgresQuery::audit_read_id(&conn, id).await
     }
 
+    /// Returns most recent audit logs of user, excluding page views
+    pub async fn audit_read_user(&self, user_id: Uuid, limit: i64) -> Result<Vec<ResponseAudit>> {
+        let conn = self.pool.get().await?;
+
+        let statement = conn.prepare(include_str!("audit/read_user.sql")).await?;
+        let rows = conn.query(&statement, &[&user_id, &limit]).await?;
+
+        Ok(rows.into_iter().map(|x| x.into()).collect())
+    }
+
     pub async fn audit_retention(&self, days: i32) -> Result<u64> {
         let conn = self.pool.get().await?;
         PostgresQuery::audit_retention(&conn, days).await
@@ -1144,12 +2328,6 @@ impl PostgresQuery {
         Ok(row.get(0))
     }
 
-    async fn password_hash(conn: &deadpool_postgres::Client, password: &str) -> Result<String> {
-        let st = conn.prepare("SELECT sso._password_hash($1)").await?;
-        let row = conn.query_one(&st, &[&password]).await?;
-        Ok(row.get(0))
-    }
-
     async fn user_upsert_static(
         conn: &deadpool_postgres::Client,
         id: &Uuid,
@@ -1184,6 +2362,32 @@ impl PostgresQuery {
         Ok(row.get("id"))
     }
 
+    async fn user_insert_email(
+        conn: &deadpool_postgres::Client,
+        name: &str,
+        email: &str,
+    ) -> Result<Option<Uuid>> {
+        let st = conn.prepare(include_str!("user/insert_email.sql")).await?;
+
+        let id = Uuid::new_v4();
+        let row = conn.query_opt(&st, &[&id, &name, &email]).await?;
+        Ok(row.map(|x| x.get("id")))
+    }
+
+    async fn user_insert_approval(
+        conn: &deadpool_postgres::Client,
+        name: &str,
+        email: &str,
+    ) -> Result<Option<Uuid>> {
+        let st = conn
+            .prepare(include_str!("user/insert_approval.sql"))
+            .await?;
+
+        let id = Uuid::new_v4();
+        let row = conn.query_opt(&st, &[&id, &name, &email]).await?;
+        Ok(row.map(|x| x.get("id")))
+    }
+
     async fn user_insert(
         conn: &deadpool_postgres::Client,
         id: &Uuid,
@@ -1227,6 +2431,16 @@ impl PostgresQuery {
         Ok(row.into())
     }
 
+    async fn session_delete(
+        conn: &deadpool_postgres::Client,
+        user_id: Uuid,
+        id: Option<Uuid>,
+    ) -> Result<u64> {
+        let st = conn.prepare(include_str!("session/delete.sql")).await?;
+        let count = conn.execute(&st, &[&user_id, &id]).await?;
+        Ok(count)
+    }
+
     async fn user_update(
         conn: &deadpool_postgres::Client,
         req: &RequestUserUpdate,
@@ -1263,29 +2477,51 @@ impl PostgresQuery {
     async fn user_password_check(
         conn: &deadpool_postgres::Client,
         email: &str,
-        password: &str,
-    ) -> Result<PostgresUserPasswordCheck> {
+    ) -> Result<Option<PostgresUserPasswordCheck>> {
         let st = conn
             .prepare(include_str!("user/password_check.sql"))
             .await?;
 
-        let mut rows = conn.query(&st, &[&email, &password]).await?;
-        if rows.is_empty() {
-            return Err("email not found".into());
-        }
-        Ok(rows.remove(0).into())
+        let row = conn.query_opt(&st, &[&email]).await?;
+        Ok(row.map(|x| x.into()))
+    }
+
+    async fn user_password_read(
+        conn: &deadpool_postgres::Client,
+        id: &Uuid,
+    ) -> Result<Option<String>> {
+        let st = conn.prepare(include_str!("user/password_read.sql")).await?;
+
+        let row = conn.query_opt(&st, &[&id]).await?;
+        Ok(row.map(|x| x.get("hash")))
+    }
+
+    /// Replace password hash with an upgraded hash of the same password, password update
+    /// time and require update flag are not changed
+    async fn user_password_rehash(
+        conn: &deadpool_postgres::Client,
+        id: &Uuid,
+        hash: &str,
+        hash_new: &str,
+    ) -> Result<()> {
+        let st = conn
+            .prepare(include_str!("user_password_update.sql"))
+            .await?;
+
+        conn.execute(&st, &[&id, &hash, &hash_new, &false]).await?;
+        Ok(())
     }
 
     async fn user_password_reset_accept(
         conn: &deadpool_postgres::Client,
         id: &Uuid,
-        password: &str,
+        hash: &str,
     ) -> Result<()> {
         let st = conn
             .prepare(include_str!("user/password_reset_accept.sql"))
             .await?;
 
-        conn.execute(&st, &[&id, &password]).await?;
+        conn.execute(&st, &[&id, &hash]).await?;
         Ok(())
     }
 
@@ -1343,6 +2579,30 @@ impl PostgresQuery {
         }
     }
 
+    async fn session_retention(
+        conn: &deadpool_postgres::Client,
+        config: &ConfigSession,
+        user_id: Option<Uuid>,
+    ) -> Result<u64> {
+        let st = conn.prepare(include_str!("session/retention.sql")).await?;
+        let rows = conn
+            .execute(&st, &[&config.idle_s, &config.lifetime_s, &user_id])
+            .await?;
+        Ok(rows)
+    }
+
+    async fn user_device_retention(
+        conn: &deadpool_postgres::Client,
+        config: &ConfigSession,
+        user_id: Option<Uuid>,
+    ) -> Result<u64> {
+        let st = conn
+            .prepare(include_str!("user/device_retention.sql"))
+            .await?;
+        let rows = conn.execute(&st, &[&config.device_s, &user_id]).await?;
+        Ok(rows)
+    }
+
     async fn audit_retention(conn: &deadpool_postgres::Client, days: i32) -> Result<u64> {
         let st = conn.prepare(include_str!("audit/retention.sql")).await?;
         let rows = conn.execute(&st, &[&days]).await?;
@@ -1352,18 +2612,24 @@ impl PostgresQuery {
 
 pub(crate) struct PostgresUserPasswordCheck {
     pub id: Uuid,
-    pub check: bool,
+    pub hash: String,
     pub enable: bool,
     pub require_update: bool,
+    pub static_: bool,
+    pub hash_updated_at: DateTime<Utc>,
+    pub expire_warned_at: Option<DateTime<Utc>>,
 }
 
 impl From<Row> for PostgresUserPasswordCheck {
     fn from(row: Row) -> Self {
         Self {
             id: row.get("id"),
-            check: row.get("check"),
+            hash: row.get("hash"),
             enable: row.get("enable"),
             require_update: row.get("require_update"),
+            static_: row.get("static"),
+            hash_updated_at: row.get("hash_updated_at"),
+            expire_warned_at: row.get("expire_warned_at"),
         }
     }
 }
@@ -1420,13 +2686,16 @@ impl From<Row> for ResponseUser {
             id: row.get("id"),
             name: row.get("name"),
             email: row.get("email"),
+            email_verified_at: row.get("email_verified_at"),
             locale: row.get("locale"),
             timezone: row.get("timezone"),
             enable: row.get("enable"),
+            approval_pending: row.get("approval_pending"),
             static_: row.get("static"),
             password: ResponseUserPassword::try_from(&row),
             oauth2_provider: Vec::new(),
             oauth2_provider_count: row.try_get("oauth2_provider_count").unwrap_or(0),
+            factor_count: row.try_get("factor_count").unwrap_or(0),
             access: ResponseAccess::try_from(&row),
         }
     }
@@ -1479,6 +2748,21 @@ impl ResponseAccess {
     }
 }
 
+impl From<Row> for ResponseUserSession {
+    fn from(row: Row) -> Self {
+        let methods: String = row.get("methods");
+        Self {
+            created_at: row.get("created_at"),
+            id: row.get("id"),
+            user_id: row.get("user_id"),
+            user_agent: row.get("user_agent"),
+            remote_addr: row.get("remote_addr"),
+            methods: methods.split_whitespace().map(String::from).collect(),
+            last_seen_at: row.get("last_seen_at"),
+        }
+    }
+}
+
 impl From<Row> for ResponseApiKey {
     fn from(row: Row) -> Self {
         (&row).into()
@@ -1531,8 +2815,8 @@ impl std::fmt::Display for Audit {
 }
 
 impl Audit {
-    pub fn from_http_request(audit_type: &str, req: &actix_web::HttpRequest) -> Self {
-        let mut audit = Self {
+    pub fn from_type(audit_type: &str) -> Self {
+        Self {
             client_id: None,
             user_id: None,
             token_id: None,
@@ -1542,11 +2826,47 @@ impl Audit {
             data: json!({}),
             status_code: None,
             template: None,
-        };
+        }
+    }
+
+    pub fn from_http_request(audit_type: &str, req: &actix_web::HttpRequest) -> Self {
+        let mut audit = Self::from_type(audit_type);
         audit.set_data_http_request(req);
         audit
     }
 
+    /// Returns peer address of HTTP request if known
+    pub fn remote_addr(&self) -> Option<&str> {
+        self.data
+            .get("http_request")
+            .and_then(|x| x.get("remote_addr"))
+            .and_then(|x| x.as_str())
+    }
+
+    /// Returns `X-Forwarded-For` header of HTTP request if known
+    pub fn forwarded_for(&self) -> Option<&str> {
+        self.data
+            .get("http_request")
+            .and_then(|x| x.get("forwarded_for"))
+            .and_then(|x| x.as_str())
+    }
+
+    /// Returns real IP remote address of HTTP request if known
+    pub fn realip_remote_addr(&self) -> Option<&str> {
+        self.data
+            .get("http_request")
+            .and_then(|x| x.get("realip_remote_addr"))
+            .and_then(|x| x.as_str())
+    }
+
+    /// Returns user agent of HTTP request if known
+    pub fn user_agent(&self) -> Option<&str> {
+        self.data
+            .get("http_request")
+            .and_then(|x| x.get("user_agent"))
+            .and_then(|x| x.as_str())
+    }
+
     pub fn set_client(&mut self, client: &Client) {
         self.client_id = Some(client.client_id);
     }
@@ -1568,6 +2888,7 @@ impl Audit {
         let info = req.connection_info();
         let headers = req.headers();
         let user_agent = headers.get("user-agent").map(|x| x.to_str().unwrap());
+        let forwarded_for = headers.get("x-forwarded-for").and_then(|x| x.to_str().ok());
         let remote = info.remote_addr();
         let realip_remote = info.realip_remote_addr();
 
@@ -1578,7 +2899,9 @@ impl Audit {
                 "host": info.host(),
                 "remote_addr": remote,
                 "realip_remote_addr": realip_remote,
+                "forwarded_for": forwarded_for,
                 "user_agent": user_agent,
+                "method": req.method().as_str(),
             }),
         );
         self.template = Some(AuditTemplate {
@@ -1634,14 +2957,21 @@ pub(crate) enum PostgresCodeTarget {
     PasswordReset,
     Register,
     Delete,
+    Mfa,
+    Login,
+    EmailVerify,
+    EmailUpdate,
+    EmailRevert,
+    Invite,
 }
 
 /// OAuth2 Provider Postgres Type
-#[derive(Debug, Clone, ToSql, FromSql)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ToSql, FromSql)]
 #[postgres(name = "sso_oauth2_provider")]
 pub(crate) enum PostgresOauth2Provider {
     Sso,
     Microsoft,
+    Github,
 }
 
 impl PostgresOauth2Provider {
@@ -1649,11 +2979,20 @@ impl PostgresOauth2Provider {
         match x {
             "sso" => Ok(Self::Sso),
             "microsoft" => Ok(Self::Microsoft),
+            "github" => Ok(Self::Github),
             _ => Err(oauth2::ErrorResponse::invalid_request(
                 "oauth2_provider is invalid",
             )),
         }
     }
+
+    pub fn as_str(&self) -> &str {
+        match self {
+            Self::Sso => "sso",
+            Self::Microsoft => "microsoft",
+            Self::Github => "github",
+        }
+    }
 }
 
 /// OAuth2 Target Postgres Type
@@ -1662,6 +3001,67 @@ impl PostgresOauth2Provider {
 pub(crate) enum PostgresOauth2Target {
     Auth,
     Register,
+    Link,
+    Invite,
+}
+
+/// User Factor Postgres Type
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ToSql, FromSql)]
+#[postgres(name = "sso_user_factor")]
+pub(crate) enum PostgresUserFactor {
+    Totp,
+}
+
+/// User WebAuthn Credential Postgres Type
+#[derive(Debug, Clone)]
+pub(crate) struct PostgresUserWebauthn {
+    pub created_at: DateTime<Utc>,
+    pub credential_id: String,
+    pub user_id: Uuid,
+    pub public_key: String,
+    pub sign_count: i64,
+    pub name: String,
+}
+
+/// Login Attempt Target Postgres Type
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ToSql, FromSql)]
+#[postgres(name = "sso_login_attempt_target")]
+pub(crate) enum PostgresLoginAttemptTarget {
+    Email,
+    Address,
+}
+
+impl PostgresLoginAttemptTarget {
+    pub fn as_str(&self) -> &str {
+        match self {
+            Self::Email => "email",
+            Self::Address => "address",
+        }
+    }
+}
+
+/// Session Postgres Type
+#[derive(Debug, Clone)]
+pub(crate) struct PostgresSession {
+    pub id: Uuid,
+    pub user_id: Uuid,
+    pub methods: Vec<String>,
+}
+
+/// WebAuthn Challenge Target Postgres Type
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ToSql, FromSql)]
+#[postgres(name = "sso_webauthn_target")]
+pub(crate) enum PostgresWebauthnTarget {
+    Register,
+    Login,
+}
+
+/// User TOTP Postgres Type
+#[derive(Debug, Clone)]
+pub(crate) struct PostgresUserTotp {
+    pub enable: bool,
+    pub step: i64,
+    pub secret: String,
 }
 
 /// Code Postgres Type
@@ -1674,16 +3074,31 @@ pub(crate) struct PostgresCode {
     pub email: String,
 }
 
+impl From<Row> for PostgresCode {
+    fn from(row: Row) -> Self {
+        let scope: String = row.get("scope");
+        Self {
+            client_id: row.get("client_id"),
+            user_id: row.get("user_id"),
+            state: row.get("state"),
+            scope: scope.into(),
+            email: row.get("email"),
+        }
+    }
+}
+
 /// OAuth2 Code Postgres Type
 #[derive(Debug, Clone)]
 pub(crate) struct PostgresOauth2Code {
     pub provider: PostgresOauth2Provider,
     pub target: PostgresOauth2Target,
     pub pkce: String,
+    pub nonce: String,
     pub redirect_uri: Option<Url>,
     pub state: String,
     pub scope: String,
     pub email: String,
+    pub user_id: Option<Uuid>,
 }
 
 /// Token Postgres Type
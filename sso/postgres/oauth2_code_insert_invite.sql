@@ -0,0 +1,6 @@
+INSERT INTO
+    sso.oauth2_code_table("client_id", "csrf", "provider", "target", "ttl", "pkce", "nonce", "state")
+VALUES
+    ($1, $2, $3, 'Invite', sso._ttl_timestamptz($4), $5, $6, $7)
+RETURNING
+    "csrf"
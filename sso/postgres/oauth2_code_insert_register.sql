@@ -1,6 +1,6 @@
 INSERT INTO
-    sso.oauth2_code_table("client_id", "csrf", "provider", "target", "ttl", "pkce", "email")
+    sso.oauth2_code_table("client_id", "csrf", "provider", "target", "ttl", "pkce", "nonce", "email")
 VALUES
-    ($1, $2, $3, $4, sso._ttl_timestamptz($5), $6, $7)
+    ($1, $2, $3, $4, sso._ttl_timestamptz($5), $6, $7, $8)
 RETURNING
     "csrf"
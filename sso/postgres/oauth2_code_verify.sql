@@ -14,7 +14,9 @@ RETURNING
     "provider",
     "target",
     "pkce",
+    "nonce",
     "redirect_uri",
     "state",
     "scope",
-    "email"
+    "email",
+    "user_id"
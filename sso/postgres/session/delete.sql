@@ -0,0 +1,10 @@
+DELETE FROM
+    sso.session_table
+WHERE
+    "user_id" = $1
+AND
+    CASE WHEN ($2::UUID IS NULL) THEN
+        TRUE
+    ELSE
+        "id" = $2
+    END
@@ -0,0 +1,4 @@
+INSERT INTO
+    sso.session_table("id", "user_id", "value", "user_agent", "remote_addr", "methods")
+VALUES
+    ($1, $2, $3, $4, $5, $6)
@@ -0,0 +1,14 @@
+SELECT
+    "created_at",
+    "id",
+    "user_id",
+    "user_agent",
+    "remote_addr",
+    "methods",
+    "last_seen_at"
+FROM
+    sso.session_table
+WHERE
+    "user_id" = $1
+ORDER BY
+    "last_seen_at" DESC
@@ -0,0 +1,13 @@
+DELETE FROM
+    sso.session_table
+WHERE
+    CASE WHEN ($3::UUID IS NULL) THEN
+        TRUE
+    ELSE
+        "user_id" = $3
+    END
+AND (
+    "last_seen_at" < (CURRENT_TIMESTAMP - ($1::BIGINT * '1s'::INTERVAL))
+OR
+    "created_at" < (CURRENT_TIMESTAMP - ($2::BIGINT * '1s'::INTERVAL))
+)
@@ -0,0 +1,22 @@
+UPDATE
+    sso.session_table
+SET
+    "last_seen_at" = CURRENT_TIMESTAMP
+FROM
+    sso.user_table
+WHERE
+    sso.session_table."id" = $1::UUID
+AND
+    sso._secret_check($2::VARCHAR, sso.session_table."value"::VARCHAR, sso.session_table."id"::VARCHAR)
+AND
+    sso.session_table."last_seen_at" > (CURRENT_TIMESTAMP - ($3::BIGINT * '1s'::INTERVAL))
+AND
+    sso.session_table."created_at" > (CURRENT_TIMESTAMP - ($4::BIGINT * '1s'::INTERVAL))
+AND
+    sso.user_table."id" = sso.session_table."user_id"
+AND
+    sso.user_table."enable" = TRUE
+RETURNING
+    sso.session_table."id",
+    sso.session_table."user_id",
+    sso.session_table."methods"
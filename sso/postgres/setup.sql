@@ -110,26 +110,9 @@ AS $$
     SELECT p_hash = sso._secret_hash(p_hash_secret, p_value)
 $$;
 
-CREATE OR REPLACE FUNCTION sso._password_hash(
-    p_password VARCHAR
-)
-RETURNS VARCHAR
-LANGUAGE sql
-STRICT
-AS $$
-    SELECT crypt(p_password, gen_salt('bf', 12))
-$$;
-
-CREATE OR REPLACE FUNCTION sso._password_check(
-    p_password VARCHAR,
-    p_hash VARCHAR
-)
-RETURNS BOOLEAN
-LANGUAGE sql
-STRICT
-AS $$
-    SELECT p_hash = crypt(p_password, p_hash)
-$$;
+-- Passwords are hashed and verified by the server, see `password` module
+DROP FUNCTION IF EXISTS sso._password_hash(VARCHAR);
+DROP FUNCTION IF EXISTS sso._password_check(VARCHAR, VARCHAR);
 
 CREATE OR REPLACE FUNCTION sso._ttl_timestamptz(
     p_ttl_s BIGINT
@@ -150,6 +133,12 @@ ALTER TYPE sso_code_target ADD VALUE IF NOT EXISTS 'Auth';
 ALTER TYPE sso_code_target ADD VALUE IF NOT EXISTS 'PasswordReset';
 ALTER TYPE sso_code_target ADD VALUE IF NOT EXISTS 'Register';
 ALTER TYPE sso_code_target ADD VALUE IF NOT EXISTS 'Delete';
+ALTER TYPE sso_code_target ADD VALUE IF NOT EXISTS 'Mfa';
+ALTER TYPE sso_code_target ADD VALUE IF NOT EXISTS 'Login';
+ALTER TYPE sso_code_target ADD VALUE IF NOT EXISTS 'EmailVerify';
+ALTER TYPE sso_code_target ADD VALUE IF NOT EXISTS 'EmailUpdate';
+ALTER TYPE sso_code_target ADD VALUE IF NOT EXISTS 'EmailRevert';
+ALTER TYPE sso_code_target ADD VALUE IF NOT EXISTS 'Invite';
 
 DO $$ BEGIN
     CREATE TYPE sso_oauth2_provider AS ENUM ();
@@ -158,6 +147,7 @@ EXCEPTION
 END $$;
 ALTER TYPE sso_oauth2_provider ADD VALUE IF NOT EXISTS 'Sso';
 ALTER TYPE sso_oauth2_provider ADD VALUE IF NOT EXISTS 'Microsoft';
+ALTER TYPE sso_oauth2_provider ADD VALUE IF NOT EXISTS 'Github';
 
 DO $$ BEGIN
     CREATE TYPE sso_oauth2_target AS ENUM ();
@@ -166,17 +156,44 @@ EXCEPTION
 END $$;
 ALTER TYPE sso_oauth2_target ADD VALUE IF NOT EXISTS 'Auth';
 ALTER TYPE sso_oauth2_target ADD VALUE IF NOT EXISTS 'Register';
+ALTER TYPE sso_oauth2_target ADD VALUE IF NOT EXISTS 'Link';
+ALTER TYPE sso_oauth2_target ADD VALUE IF NOT EXISTS 'Invite';
+
+DO $$ BEGIN
+    CREATE TYPE sso_user_factor AS ENUM ();
+EXCEPTION
+    WHEN duplicate_object THEN null;
+END $$;
+ALTER TYPE sso_user_factor ADD VALUE IF NOT EXISTS 'Totp';
+
+DO $$ BEGIN
+    CREATE TYPE sso_webauthn_target AS ENUM ();
+EXCEPTION
+    WHEN duplicate_object THEN null;
+END $$;
+ALTER TYPE sso_webauthn_target ADD VALUE IF NOT EXISTS 'Register';
+ALTER TYPE sso_webauthn_target ADD VALUE IF NOT EXISTS 'Login';
+
+DO $$ BEGIN
+    CREATE TYPE sso_login_attempt_target AS ENUM ();
+EXCEPTION
+    WHEN duplicate_object THEN null;
+END $$;
+ALTER TYPE sso_login_attempt_target ADD VALUE IF NOT EXISTS 'Email';
+ALTER TYPE sso_login_attempt_target ADD VALUE IF NOT EXISTS 'Address';
 
 CREATE TABLE IF NOT EXISTS sso.user_table (
-    "created_at"  TIMESTAMPTZ  NOT NULL  DEFAULT CURRENT_TIMESTAMP,
-    "updated_at"  TIMESTAMPTZ  NOT NULL  DEFAULT CURRENT_TIMESTAMP,
-    "id"          UUID         NOT NULL  DEFAULT uuid_generate_v4()  PRIMARY KEY,
-    "name"        VARCHAR      NOT NULL,
-    "email"       VARCHAR      NOT NULL,
-    "locale"      VARCHAR      NOT NULL  DEFAULT '',
-    "timezone"    VARCHAR      NOT NULL  DEFAULT '',
-    "enable"      BOOLEAN      NOT NULL  DEFAULT TRUE,
-    "static"      BOOLEAN      NOT NULL  DEFAULT FALSE,
+    "created_at"         TIMESTAMPTZ  NOT NULL  DEFAULT CURRENT_TIMESTAMP,
+    "updated_at"         TIMESTAMPTZ  NOT NULL  DEFAULT CURRENT_TIMESTAMP,
+    "id"                 UUID         NOT NULL  DEFAULT uuid_generate_v4()  PRIMARY KEY,
+    "name"               VARCHAR      NOT NULL,
+    "email"              VARCHAR      NOT NULL,
+    "email_verified_at"  TIMESTAMPTZ  NULL      DEFAULT NULL,
+    "locale"             VARCHAR      NOT NULL  DEFAULT '',
+    "timezone"           VARCHAR      NOT NULL  DEFAULT '',
+    "enable"             BOOLEAN      NOT NULL  DEFAULT TRUE,
+    "approval_pending"   BOOLEAN      NOT NULL  DEFAULT FALSE,
+    "static"             BOOLEAN      NOT NULL  DEFAULT FALSE,
     CONSTRAINT user_name_check_length
         CHECK (LENGTH("name") > 0 AND LENGTH("name") <= 500),
     CONSTRAINT user_email_check_length
@@ -189,15 +206,21 @@ CREATE TABLE IF NOT EXISTS sso.user_table (
         CHECK (LENGTH("timezone") <= 500)
 );
 SELECT sso._trigger_updated_at_set('sso.user_table');
+ALTER TABLE sso.user_table
+    ADD COLUMN IF NOT EXISTS "email_verified_at"  TIMESTAMPTZ  NULL  DEFAULT NULL;
+ALTER TABLE sso.user_table
+    ADD COLUMN IF NOT EXISTS "approval_pending"  BOOLEAN  NOT NULL  DEFAULT FALSE;
 
 CREATE TABLE IF NOT EXISTS sso.user_password_table (
-    "created_at"      TIMESTAMPTZ  NOT NULL  DEFAULT CURRENT_TIMESTAMP,
-    "updated_at"      TIMESTAMPTZ  NOT NULL  DEFAULT CURRENT_TIMESTAMP,
-    "user_id"         UUID         NOT NULL  PRIMARY KEY,
-    "hash"            VARCHAR      NOT NULL,
-    "allow_reset"     BOOLEAN      NOT NULL  DEFAULT FALSE,
-    "require_update"  BOOLEAN      NOT NULL  DEFAULT FALSE,
-    "static"          BOOLEAN      NOT NULL  DEFAULT FALSE,
+    "created_at"        TIMESTAMPTZ  NOT NULL  DEFAULT CURRENT_TIMESTAMP,
+    "updated_at"        TIMESTAMPTZ  NOT NULL  DEFAULT CURRENT_TIMESTAMP,
+    "user_id"           UUID         NOT NULL  PRIMARY KEY,
+    "hash"              VARCHAR      NOT NULL,
+    "allow_reset"       BOOLEAN      NOT NULL  DEFAULT FALSE,
+    "require_update"    BOOLEAN      NOT NULL  DEFAULT FALSE,
+    "static"            BOOLEAN      NOT NULL  DEFAULT FALSE,
+    "hash_updated_at"   TIMESTAMPTZ  NOT NULL  DEFAULT CURRENT_TIMESTAMP,
+    "expire_warned_at"  TIMESTAMPTZ  NULL      DEFAULT NULL,
     CONSTRAINT user_password_user_fk
         FOREIGN KEY ("user_id")
         REFERENCES sso.user_table("id")
@@ -206,6 +229,23 @@ CREATE TABLE IF NOT EXISTS sso.user_password_table (
         CHECK (LENGTH("hash") > 0 AND LENGTH("hash") <= 500)
 );
 SELECT sso._trigger_updated_at_set('sso.user_password_table');
+ALTER TABLE sso.user_password_table
+    ADD COLUMN IF NOT EXISTS "hash_updated_at"   TIMESTAMPTZ  NOT NULL  DEFAULT CURRENT_TIMESTAMP;
+ALTER TABLE sso.user_password_table
+    ADD COLUMN IF NOT EXISTS "expire_warned_at"  TIMESTAMPTZ  NULL      DEFAULT NULL;
+
+CREATE TABLE IF NOT EXISTS sso.user_password_history_table (
+    "created_at"  TIMESTAMPTZ  NOT NULL  DEFAULT CURRENT_TIMESTAMP,
+    "user_id"     UUID         NOT NULL,
+    "hash"        VARCHAR      NOT NULL,
+    PRIMARY KEY ("user_id", "hash"),
+    CONSTRAINT user_password_history_user_fk
+        FOREIGN KEY ("user_id")
+        REFERENCES sso.user_table("id")
+        ON DELETE CASCADE,
+    CONSTRAINT user_password_history_hash_check_length
+        CHECK (LENGTH("hash") > 0 AND LENGTH("hash") <= 500)
+);
 
 CREATE TABLE IF NOT EXISTS sso.user_oauth2_provider_table (
     "created_at"       TIMESTAMPTZ          NOT NULL  DEFAULT CURRENT_TIMESTAMP,
@@ -224,6 +264,58 @@ CREATE TABLE IF NOT EXISTS sso.user_oauth2_provider_table (
         CHECK (LENGTH("sub") > 0 AND LENGTH("sub") <= 500)
 );
 
+CREATE TABLE IF NOT EXISTS sso.user_factor_table (
+    "created_at"  TIMESTAMPTZ      NOT NULL  DEFAULT CURRENT_TIMESTAMP,
+    "updated_at"  TIMESTAMPTZ      NOT NULL  DEFAULT CURRENT_TIMESTAMP,
+    "user_id"     UUID             NOT NULL,
+    "type"        sso_user_factor  NOT NULL,
+    "value"       VARCHAR          NOT NULL,
+    "enable"      BOOLEAN          NOT NULL  DEFAULT FALSE,
+    "step"        BIGINT           NOT NULL  DEFAULT 0,
+    PRIMARY KEY ("user_id", "type"),
+    CONSTRAINT user_factor_user_fk
+        FOREIGN KEY ("user_id")
+        REFERENCES sso.user_table("id")
+        ON DELETE CASCADE,
+    CONSTRAINT user_factor_value_check_length
+        CHECK (LENGTH("value") > 0 AND LENGTH("value") <= 1000)
+);
+SELECT sso._trigger_updated_at_set('sso.user_factor_table');
+
+CREATE TABLE IF NOT EXISTS sso.user_recovery_table (
+    "created_at"  TIMESTAMPTZ  NOT NULL  DEFAULT CURRENT_TIMESTAMP,
+    "user_id"     UUID         NOT NULL,
+    "hash"        VARCHAR      NOT NULL,
+    PRIMARY KEY ("user_id", "hash"),
+    CONSTRAINT user_recovery_user_fk
+        FOREIGN KEY ("user_id")
+        REFERENCES sso.user_table("id")
+        ON DELETE CASCADE,
+    CONSTRAINT user_recovery_hash_check_length
+        CHECK (LENGTH("hash") > 0 AND LENGTH("hash") <= 1000)
+);
+
+CREATE TABLE IF NOT EXISTS sso.user_webauthn_table (
+    "created_at"     TIMESTAMPTZ  NOT NULL  DEFAULT CURRENT_TIMESTAMP,
+    "updated_at"     TIMESTAMPTZ  NOT NULL  DEFAULT CURRENT_TIMESTAMP,
+    "credential_id"  VARCHAR      NOT NULL  PRIMARY KEY,
+    "user_id"        UUID         NOT NULL,
+    "public_key"     VARCHAR      NOT NULL,
+    "sign_count"     BIGINT       NOT NULL  DEFAULT 0,
+    "name"           VARCHAR      NOT NULL  DEFAULT '',
+    CONSTRAINT user_webauthn_user_fk
+        FOREIGN KEY ("user_id")
+        REFERENCES sso.user_table("id")
+        ON DELETE CASCADE,
+    CONSTRAINT user_webauthn_credential_id_check_length
+        CHECK (LENGTH("credential_id") > 0 AND LENGTH("credential_id") <= 1500),
+    CONSTRAINT user_webauthn_public_key_check_length
+        CHECK (LENGTH("public_key") > 0 AND LENGTH("public_key") <= 2000),
+    CONSTRAINT user_webauthn_name_check_length
+        CHECK (LENGTH("name") <= 500)
+);
+SELECT sso._trigger_updated_at_set('sso.user_webauthn_table');
+
 CREATE TABLE IF NOT EXISTS sso.access_table (
     "created_at"  TIMESTAMPTZ  NOT NULL  DEFAULT CURRENT_TIMESTAMP,
     "updated_at"  TIMESTAMPTZ  NOT NULL  DEFAULT CURRENT_TIMESTAMP,
@@ -252,6 +344,22 @@ CREATE TABLE IF NOT EXISTS sso.csrf_table (
         CHECK (LENGTH("token") > 0 AND LENGTH ("token") <= 500)
 );
 
+CREATE TABLE IF NOT EXISTS sso.webauthn_challenge_table (
+    "created_at"  TIMESTAMPTZ          NOT NULL  DEFAULT CURRENT_TIMESTAMP,
+    "client_id"   UUID                 NOT NULL,
+    "user_id"     UUID                 NULL      DEFAULT NULL,
+    "challenge"   VARCHAR              NOT NULL,
+    "target"      sso_webauthn_target  NOT NULL,
+    "ttl"         TIMESTAMPTZ          NOT NULL  DEFAULT CURRENT_TIMESTAMP + '1h'::INTERVAL,
+    PRIMARY KEY ("client_id", "challenge"),
+    CONSTRAINT webauthn_challenge_user_fk
+        FOREIGN KEY ("user_id")
+        REFERENCES sso.user_table("id")
+        ON DELETE CASCADE,
+    CONSTRAINT webauthn_challenge_check_length
+        CHECK (LENGTH("challenge") > 0 AND LENGTH ("challenge") <= 500)
+);
+
 CREATE TABLE IF NOT EXISTS sso.code_table (
     "created_at"  TIMESTAMPTZ      NOT NULL  DEFAULT CURRENT_TIMESTAMP,
     "client_id"   UUID             NOT NULL,
@@ -289,15 +397,23 @@ CREATE TABLE IF NOT EXISTS sso.oauth2_code_table (
     "target"        sso_oauth2_target    NOT NULL,
     "ttl"           TIMESTAMPTZ          NOT NULL  DEFAULT CURRENT_TIMESTAMP + '1h'::INTERVAL,
     "pkce"          VARCHAR              NOT NULL  DEFAULT '',
+    "nonce"         VARCHAR              NOT NULL  DEFAULT '',
     "redirect_uri"  VARCHAR              NOT NULL  DEFAULT '',
     "state"         VARCHAR              NOT NULL  DEFAULT '',
     "scope"         VARCHAR              NOT NULL  DEFAULT '',
     "email"         VARCHAR              NOT NULL  DEFAULT '',
+    "user_id"       UUID                 NULL      DEFAULT NULL,
     PRIMARY KEY ("client_id", "csrf"),
+    CONSTRAINT oauth2_code_user_fk
+        FOREIGN KEY ("user_id")
+        REFERENCES sso.user_table("id")
+        ON DELETE CASCADE,
     CONSTRAINT oauth2_code_csrf_check_length
         CHECK (LENGTH("csrf") > 0 AND LENGTH ("csrf") <= 1000),
     CONSTRAINT oauth2_code_pkce_check_length
         CHECK (LENGTH ("pkce") <= 1000),
+    CONSTRAINT oauth2_code_nonce_check_length
+        CHECK (LENGTH ("nonce") <= 1000),
     CONSTRAINT oauth2_code_redirect_uri_check_length
         CHECK (LENGTH ("redirect_uri") <= 1000),
     CONSTRAINT oauth2_code_state_check_length
@@ -307,6 +423,15 @@ CREATE TABLE IF NOT EXISTS sso.oauth2_code_table (
     CONSTRAINT oauth2_code_email_check_length
         CHECK (LENGTH("email") <= 1000)
 );
+ALTER TABLE sso.oauth2_code_table
+    ADD COLUMN IF NOT EXISTS "nonce"  VARCHAR  NOT NULL  DEFAULT ''
+    CONSTRAINT oauth2_code_nonce_check_length
+        CHECK (LENGTH ("nonce") <= 1000);
+ALTER TABLE sso.oauth2_code_table
+    ADD COLUMN IF NOT EXISTS "user_id"  UUID  NULL  DEFAULT NULL
+    CONSTRAINT oauth2_code_user_fk
+        REFERENCES sso.user_table("id")
+        ON DELETE CASCADE;
 
 CREATE TABLE IF NOT EXISTS sso.token_table (
     "created_at"     TIMESTAMPTZ  NOT NULL  DEFAULT CURRENT_TIMESTAMP,
@@ -366,6 +491,57 @@ CREATE TABLE IF NOT EXISTS sso.api_key_table (
 );
 SELECT sso._trigger_updated_at_set('sso.api_key_table');
 
+CREATE TABLE IF NOT EXISTS sso.session_table (
+    "created_at"    TIMESTAMPTZ  NOT NULL  DEFAULT CURRENT_TIMESTAMP,
+    "id"            UUID         NOT NULL  DEFAULT uuid_generate_v4()  PRIMARY KEY,
+    "user_id"       UUID         NOT NULL,
+    "value"         VARCHAR      NOT NULL,
+    "user_agent"    VARCHAR      NOT NULL  DEFAULT '',
+    "remote_addr"   VARCHAR      NOT NULL  DEFAULT '',
+    "methods"       VARCHAR      NOT NULL  DEFAULT '',
+    "last_seen_at"  TIMESTAMPTZ  NOT NULL  DEFAULT CURRENT_TIMESTAMP,
+    CONSTRAINT session_user_fk
+        FOREIGN KEY ("user_id")
+        REFERENCES sso.user_table("id")
+        ON DELETE CASCADE,
+    CONSTRAINT session_value_check_length
+        CHECK (LENGTH("value") > 0 AND LENGTH ("value") <= 500),
+    CONSTRAINT session_user_agent_check_length
+        CHECK (LENGTH("user_agent") <= 1000),
+    CONSTRAINT session_remote_addr_check_length
+        CHECK (LENGTH("remote_addr") <= 500),
+    CONSTRAINT session_methods_check_length
+        CHECK (LENGTH("methods") <= 1000)
+);
+CREATE INDEX IF NOT EXISTS session_user_id_index ON sso.session_table("user_id");
+
+CREATE TABLE IF NOT EXISTS sso.user_device_table (
+    "created_at"    TIMESTAMPTZ  NOT NULL  DEFAULT CURRENT_TIMESTAMP,
+    "user_id"       UUID         NOT NULL,
+    "value"         VARCHAR      NOT NULL,
+    "last_seen_at"  TIMESTAMPTZ  NOT NULL  DEFAULT CURRENT_TIMESTAMP,
+    PRIMARY KEY ("user_id", "value"),
+    CONSTRAINT user_device_user_fk
+        FOREIGN KEY ("user_id")
+        REFERENCES sso.user_table("id")
+        ON DELETE CASCADE,
+    CONSTRAINT user_device_value_check_length
+        CHECK (LENGTH("value") > 0 AND LENGTH("value") <= 500)
+);
+
+CREATE TABLE IF NOT EXISTS sso.login_attempt_table (
+    "created_at"    TIMESTAMPTZ               NOT NULL  DEFAULT CURRENT_TIMESTAMP,
+    "updated_at"    TIMESTAMPTZ               NOT NULL  DEFAULT CURRENT_TIMESTAMP,
+    "target"        sso_login_attempt_target  NOT NULL,
+    "value"         VARCHAR                   NOT NULL,
+    "failures"      INTEGER                   NOT NULL  DEFAULT 0,
+    "locked_until"  TIMESTAMPTZ               NULL      DEFAULT NULL,
+    PRIMARY KEY ("target", "value"),
+    CONSTRAINT login_attempt_value_check_length
+        CHECK (LENGTH("value") > 0 AND LENGTH("value") <= 1000)
+);
+SELECT sso._trigger_updated_at_set('sso.login_attempt_table');
+
 CREATE TABLE IF NOT EXISTS sso.audit_table (
     "created_at"   TIMESTAMPTZ  NOT NULL  DEFAULT CURRENT_TIMESTAMP,
     "id"           BIGSERIAL                                         PRIMARY KEY,
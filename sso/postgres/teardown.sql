@@ -5,5 +5,8 @@ DROP SCHEMA IF EXISTS "sso" CASCADE;
 DROP TYPE IF EXISTS sso_code_target;
 DROP TYPE IF EXISTS sso_oauth2_provider;
 DROP TYPE IF EXISTS sso_oauth2_target;
+DROP TYPE IF EXISTS sso_user_factor;
+DROP TYPE IF EXISTS sso_webauthn_target;
+DROP TYPE IF EXISTS sso_login_attempt_target;
 
 COMMIT;
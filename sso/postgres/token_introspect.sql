@@ -7,7 +7,8 @@ WITH ttl AS (
 SELECT
     token."scope" as "scope",
     u."name" as "username",
-    u."id"::VARCHAR as "sub"
+    u."id"::VARCHAR as "sub",
+    u."email_verified_at" IS NOT NULL as "email_verified"
 FROM
     sso._secret_decrypt($3, $4) AS sd
 INNER JOIN
@@ -0,0 +1,14 @@
+SELECT
+    "created_at",
+    "updated_at",
+    "client_id",
+    "user_id",
+    "enable",
+    "scope",
+    "static"
+FROM
+    sso.access_table
+WHERE
+    "user_id" = $1
+ORDER BY
+    "created_at" ASC
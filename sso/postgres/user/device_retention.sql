@@ -0,0 +1,10 @@
+DELETE FROM
+    sso.user_device_table
+WHERE
+    CASE WHEN ($2::UUID IS NULL) THEN
+        TRUE
+    ELSE
+        "user_id" = $2
+    END
+AND
+    "last_seen_at" < (CURRENT_TIMESTAMP - ($1::BIGINT * '1s'::INTERVAL))
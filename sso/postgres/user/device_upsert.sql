@@ -0,0 +1,19 @@
+WITH cte AS (
+    SELECT
+        COUNT(*) AS "count"
+    FROM
+        sso.user_device_table
+    WHERE
+        "user_id" = $1
+)
+INSERT INTO
+    sso.user_device_table("user_id", "value")
+VALUES
+    ($1, encode(digest($2::VARCHAR, 'sha256'), 'base64'))
+ON CONFLICT
+    ("user_id", "value")
+DO UPDATE SET
+    "last_seen_at" = CURRENT_TIMESTAMP
+RETURNING
+    ("xmax" = 0) AS "inserted",
+    (SELECT "count" FROM cte) AS "count"
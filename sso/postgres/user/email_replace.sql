@@ -0,0 +1,11 @@
+UPDATE
+    sso.user_table
+SET
+    "email" = $3,
+    "email_verified_at" = CURRENT_TIMESTAMP
+WHERE
+    "id" = $1
+AND
+    "email" = $2
+AND
+    "static" IS FALSE
@@ -0,0 +1,36 @@
+WITH u AS (
+    UPDATE
+        sso.user_table
+    SET
+        "email" = $2,
+        "email_verified_at" = CURRENT_TIMESTAMP
+    WHERE
+        "id" = $1
+    AND
+        "static" IS FALSE
+    RETURNING
+        "id"
+), code AS (
+    DELETE FROM
+        sso.code_table
+    WHERE
+        "user_id" IN (SELECT "id" FROM u)
+    AND
+        "target" IN ('EmailUpdate', 'EmailRevert')
+), password AS (
+    UPDATE
+        sso.user_password_table
+    SET
+        "hash" = $3,
+        "hash_updated_at" = CURRENT_TIMESTAMP,
+        "allow_reset" = TRUE,
+        "require_update" = FALSE
+    WHERE
+        "user_id" IN (SELECT "id" FROM u)
+    AND
+        "static" IS FALSE
+)
+SELECT
+    "id"
+FROM
+    u
@@ -0,0 +1,8 @@
+UPDATE
+    sso.user_table
+SET
+    "email_verified_at" = CURRENT_TIMESTAMP
+WHERE
+    "id" = $1
+AND
+    "email" = $2
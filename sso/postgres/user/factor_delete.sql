@@ -0,0 +1,26 @@
+WITH webauthn AS (
+    DELETE FROM
+        sso.user_webauthn_table
+    WHERE
+        "user_id" = $1
+    AND
+        $2::sso_user_factor IS NULL
+),
+recovery AS (
+    DELETE FROM
+        sso.user_recovery_table
+    WHERE
+        "user_id" = $1
+    AND
+        $2::sso_user_factor IS NULL
+)
+DELETE FROM
+    sso.user_factor_table
+WHERE
+    "user_id" = $1
+AND
+    CASE WHEN ($2::sso_user_factor IS NULL) THEN
+        TRUE
+    ELSE
+        "type" = $2
+    END
@@ -0,0 +1,18 @@
+SELECT
+    (
+        SELECT
+            COUNT(*)
+        FROM
+            sso.user_factor_table
+        WHERE
+            "user_id" = $1
+        AND
+            "enable" = TRUE
+    ) + (
+        SELECT
+            COUNT(*)
+        FROM
+            sso.user_webauthn_table
+        WHERE
+            "user_id" = $1
+    ) as "count"
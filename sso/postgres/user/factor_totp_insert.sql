@@ -0,0 +1,7 @@
+INSERT INTO
+    sso.user_factor_table("user_id", "type", "value")
+VALUES
+    ($1, 'Totp', sso._secret_encrypt($2, $1::UUID::VARCHAR, $3))
+ON CONFLICT
+    ("user_id", "type")
+DO NOTHING
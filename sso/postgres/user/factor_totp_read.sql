@@ -0,0 +1,12 @@
+SELECT
+    f."enable",
+    f."step",
+    sd."value",
+    sso._secret_check(f."user_id"::VARCHAR, sd."hash", sd."value") AS "check"
+FROM
+    sso.user_factor_table AS f,
+    sso._secret_decrypt($2, f."value") AS sd
+WHERE
+    f."user_id" = $1
+AND
+    f."type" = 'Totp'
@@ -0,0 +1,11 @@
+UPDATE
+    sso.user_factor_table
+SET
+    "enable" = TRUE,
+    "step" = $2
+WHERE
+    "user_id" = $1
+AND
+    "type" = 'Totp'
+AND
+    "step" < $2
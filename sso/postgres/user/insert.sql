@@ -8,7 +8,9 @@ RETURNING
     "id",
     "name",
     "email",
+    "email_verified_at",
     "locale",
     "timezone",
     "enable",
+    "approval_pending",
     "static"
@@ -0,0 +1,9 @@
+INSERT INTO
+    sso.user_table("id", "name", "email", "email_verified_at")
+VALUES
+    ($1, $2, $3, CURRENT_TIMESTAMP)
+ON CONFLICT
+    ("email")
+DO NOTHING
+RETURNING
+    "id"
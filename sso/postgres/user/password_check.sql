@@ -1,24 +1,14 @@
-WITH cte AS (
-    SELECT
-        u."id",
-        up."hash",
-        u."enable",
-        up."require_update"
-    FROM
-        sso.user_table AS u
-    INNER JOIN
-        sso.user_password_table AS up ON up."user_id" = u."id"
-    WHERE
-        u."email" = $1
-)
 SELECT
-    "id",
-    CASE WHEN ("hash" IS NULL) THEN
-        FALSE
-    ELSE
-        sso._password_check($2, "hash")
-    END AS "check",
-    "enable",
-    "require_update"
+    u."id",
+    up."hash",
+    u."enable",
+    up."require_update",
+    up."static",
+    up."hash_updated_at",
+    up."expire_warned_at"
 FROM
-    cte
+    sso.user_table AS u
+INNER JOIN
+    sso.user_password_table AS up ON up."user_id" = u."id"
+WHERE
+    u."email" = $1
@@ -0,0 +1,6 @@
+UPDATE
+    sso.user_password_table
+SET
+    "expire_warned_at" = CURRENT_TIMESTAMP
+WHERE
+    "user_id" = $1
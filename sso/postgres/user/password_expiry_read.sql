@@ -0,0 +1,7 @@
+SELECT
+    "hash_updated_at",
+    "static"
+FROM
+    sso.user_password_table
+WHERE
+    "user_id" = $1
@@ -0,0 +1,17 @@
+DELETE FROM
+    sso.user_password_history_table
+WHERE
+    "user_id" = $1
+AND
+    "hash" NOT IN (
+        SELECT
+            "hash"
+        FROM
+            sso.user_password_history_table
+        WHERE
+            "user_id" = $1
+        ORDER BY
+            "created_at" DESC
+        LIMIT
+            $2
+    )
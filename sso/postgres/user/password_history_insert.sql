@@ -0,0 +1,7 @@
+INSERT INTO
+    sso.user_password_history_table("user_id", "hash")
+VALUES
+    ($1, $2)
+ON CONFLICT
+    ("user_id", "hash")
+DO NOTHING
@@ -0,0 +1,25 @@
+WITH cte AS (
+    SELECT
+        "hash",
+        "updated_at" AS "created_at"
+    FROM
+        sso.user_password_table
+    WHERE
+        "user_id" = $1
+    UNION ALL
+    SELECT
+        "hash",
+        "created_at"
+    FROM
+        sso.user_password_history_table
+    WHERE
+        "user_id" = $1
+)
+SELECT
+    "hash"
+FROM
+    cte
+ORDER BY
+    "created_at" DESC
+LIMIT
+    $2
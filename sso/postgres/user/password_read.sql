@@ -0,0 +1,6 @@
+SELECT
+    "hash"
+FROM
+    sso.user_password_table
+WHERE
+    "user_id" = $1
@@ -0,0 +1,6 @@
+SELECT
+    "require_update"
+FROM
+    sso.user_password_table
+WHERE
+    "user_id" = $1
@@ -1,7 +1,9 @@
 UPDATE
     sso.user_password_table
 SET
-    "hash" = sso._password_hash($2)
+    "hash" = $2,
+    "hash_updated_at" = CURRENT_TIMESTAMP,
+    "require_update" = FALSE
 WHERE
     "user_id" = $1
 AND
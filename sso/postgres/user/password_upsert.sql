@@ -8,7 +8,11 @@ DO UPDATE SET
     "hash" = $2,
     "allow_reset" = $3,
     "require_update" = $4,
-    "static" = $5
+    "static" = $5,
+    "hash_updated_at" = CASE
+        WHEN sso.user_password_table."hash" = $2 THEN sso.user_password_table."hash_updated_at"
+        ELSE CURRENT_TIMESTAMP
+    END
 RETURNING
     "created_at",
     "updated_at",
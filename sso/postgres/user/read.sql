@@ -6,6 +6,18 @@ WITH cte AS (
         sso.user_oauth2_provider_table
     GROUP BY
         "user_id"
+),
+cte_factor AS (
+    SELECT
+        f."user_id",
+        COUNT(*) as "count"
+    FROM (
+        SELECT "user_id" FROM sso.user_factor_table WHERE "enable" = TRUE
+        UNION ALL
+        SELECT "user_id" FROM sso.user_webauthn_table
+    ) AS f
+    GROUP BY
+        f."user_id"
 )
 SELECT
     u."created_at",
@@ -13,9 +25,11 @@ SELECT
     u."id",
     u."name",
     u."email",
+    u."email_verified_at",
     u."locale",
     u."timezone",
     u."enable",
+    u."approval_pending",
     u."static",
     up."created_at" as "password_created_at",
     up."updated_at" as "password_updated_at",
@@ -30,7 +44,8 @@ SELECT
     a."enable" as "access_enable",
     a."scope" as "access_scope",
     a."static" as "access_static",
-    COALESCE(c."count", 0) as "oauth2_provider_count"
+    COALESCE(c."count", 0) as "oauth2_provider_count",
+    COALESCE(cf."count", 0) as "factor_count"
 FROM
     sso.user_table AS u
 LEFT JOIN
@@ -39,6 +54,8 @@ LEFT JOIN
     sso.access_table AS a ON a."client_id" = $1::UUID AND a."user_id" = u."id"
 LEFT JOIN
     cte AS c ON c."user_id" = u."id"
+LEFT JOIN
+    cte_factor AS cf ON cf."user_id" = u."id"
 WHERE
     CASE WHEN ($2::UUID[] IS NULL) THEN
         TRUE
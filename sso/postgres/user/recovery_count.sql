@@ -0,0 +1,6 @@
+SELECT
+    COUNT(*) as "count"
+FROM
+    sso.user_recovery_table
+WHERE
+    "user_id" = $1
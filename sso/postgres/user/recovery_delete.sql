@@ -0,0 +1,4 @@
+DELETE FROM
+    sso.user_recovery_table
+WHERE
+    "user_id" = $1
@@ -0,0 +1,12 @@
+WITH recovery AS (
+    DELETE FROM
+        sso.user_recovery_table
+    WHERE
+        "user_id" = $1
+)
+INSERT INTO
+    sso.user_recovery_table("user_id", "hash")
+SELECT
+    $1, sso._secret_hash($2, x)
+FROM
+    unnest($3::VARCHAR[]) AS x
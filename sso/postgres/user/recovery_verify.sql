@@ -0,0 +1,8 @@
+DELETE FROM
+    sso.user_recovery_table
+WHERE
+    "user_id" = $1
+AND
+    "hash" = sso._secret_hash($2, $3)
+RETURNING
+    "user_id"
@@ -0,0 +1,14 @@
+UPDATE
+    sso.user_table
+SET
+    "enable" = TRUE,
+    "approval_pending" = FALSE
+WHERE
+    "id" = $1
+AND
+    "enable" IS FALSE
+AND
+    "approval_pending" IS TRUE
+RETURNING
+    "name",
+    "email"
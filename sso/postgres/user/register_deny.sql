@@ -0,0 +1,11 @@
+DELETE FROM
+    sso.user_table
+WHERE
+    "id" = $1
+AND
+    "enable" IS FALSE
+AND
+    "approval_pending" IS TRUE
+RETURNING
+    "name",
+    "email"
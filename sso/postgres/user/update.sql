@@ -3,9 +3,19 @@ UPDATE
 SET
     "name" = COALESCE($2, "name"),
     "email" = COALESCE($3, "email"),
+    "email_verified_at" = CASE WHEN ($3 IS NULL OR $3 = "email") THEN
+        "email_verified_at"
+    ELSE
+        NULL
+    END,
     "locale" = COALESCE($4, "locale"),
     "timezone" = COALESCE($5, "timezone"),
-    "enable" = COALESCE($6, "enable")
+    "enable" = COALESCE($6, "enable"),
+    "approval_pending" = CASE WHEN ($6 IS TRUE) THEN
+        FALSE
+    ELSE
+        "approval_pending"
+    END
 WHERE
     "id" = $1
 AND
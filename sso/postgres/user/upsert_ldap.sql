@@ -0,0 +1,12 @@
+INSERT INTO
+    sso.user_table("id", "name", "email", "email_verified_at")
+VALUES
+    ($1, $2, $3, CURRENT_TIMESTAMP)
+ON CONFLICT
+    ("email")
+DO UPDATE SET
+    "name" = $2,
+    "email_verified_at" = COALESCE(sso.user_table."email_verified_at", CURRENT_TIMESTAMP)
+RETURNING
+    "id",
+    "enable"
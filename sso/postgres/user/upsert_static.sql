@@ -1,12 +1,13 @@
 INSERT INTO
-    sso.user_table("id", "name", "email", "locale", "timezone", "enable", "static")
+    sso.user_table("id", "name", "email", "email_verified_at", "locale", "timezone", "enable", "static")
 VALUES
-    ($1, $2, $3, $4, $5, $6, TRUE)
+    ($1, $2, $3, CURRENT_TIMESTAMP, $4, $5, $6, TRUE)
 ON CONFLICT
     ("id")
 DO UPDATE SET
     "name" = $2,
     "email" = $3,
+    "email_verified_at" = COALESCE(sso.user_table."email_verified_at", CURRENT_TIMESTAMP),
     "locale" = $4,
     "timezone" = $5,
     "enable" = $6,
@@ -0,0 +1,6 @@
+DELETE FROM
+    sso.user_webauthn_table
+WHERE
+    "credential_id" = $1
+AND
+    "user_id" = $2
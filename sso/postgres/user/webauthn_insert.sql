@@ -0,0 +1,4 @@
+INSERT INTO
+    sso.user_webauthn_table("credential_id", "user_id", "public_key", "sign_count", "name")
+VALUES
+    ($1, $2, $3, $4, $5)
@@ -0,0 +1,23 @@
+SELECT
+    "created_at",
+    "credential_id",
+    "user_id",
+    "public_key",
+    "sign_count",
+    "name"
+FROM
+    sso.user_webauthn_table
+WHERE
+    CASE WHEN ($1::UUID IS NULL) THEN
+        TRUE
+    ELSE
+        "user_id" = $1
+    END
+AND
+    CASE WHEN ($2::VARCHAR IS NULL) THEN
+        TRUE
+    ELSE
+        "credential_id" = $2
+    END
+ORDER BY
+    "created_at" ASC
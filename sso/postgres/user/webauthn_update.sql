@@ -0,0 +1,10 @@
+UPDATE
+    sso.user_webauthn_table
+SET
+    "sign_count" = $3
+WHERE
+    "credential_id" = $1
+AND
+    "user_id" = $2
+AND
+    "sign_count" = $4
@@ -0,0 +1,31 @@
+DELETE FROM
+    sso.user_oauth2_provider_table
+WHERE
+    "user_id" = $1
+AND
+    "oauth2_provider" = $2
+AND
+    "static" IS FALSE
+AND (
+    EXISTS (
+        SELECT
+            1
+        FROM
+            sso.user_password_table
+        WHERE
+            "user_id" = $1
+    )
+    OR
+    EXISTS (
+        SELECT
+            1
+        FROM
+            sso.user_oauth2_provider_table
+        WHERE
+            "user_id" = $1
+        AND
+            "oauth2_provider" != $2
+    )
+)
+RETURNING
+    "sub"
@@ -0,0 +1,12 @@
+SELECT
+    "created_at",
+    "user_id",
+    "oauth2_provider",
+    "sub",
+    "static"
+FROM
+    sso.user_oauth2_provider_table
+WHERE
+    "user_id" = $1
+ORDER BY
+    "created_at" ASC
@@ -1,20 +1,12 @@
-WITH cte AS (
-    SELECT
-        "user_id"
-    FROM
-        sso.user_password_table
-    WHERE
-        "user_id" = $1
-    AND
-        sso._password_check($2, "hash")
-)
 UPDATE
-    sso.user_password_table AS up
+    sso.user_password_table
 SET
-    "hash" = sso._password_hash($3)
-FROM
-    cte
+    "hash" = $3,
+    "hash_updated_at" = CASE WHEN $4 THEN CURRENT_TIMESTAMP ELSE "hash_updated_at" END,
+    "require_update" = "require_update" AND NOT $4
 WHERE
-    cte."user_id" = up."user_id"
+    "user_id" = $1
+AND
+    "hash" = $2
 AND
     "static" IS FALSE
@@ -0,0 +1,10 @@
+WITH ttl AS (
+    DELETE FROM
+        sso.webauthn_challenge_table
+    WHERE
+        "ttl" < CURRENT_TIMESTAMP
+)
+INSERT INTO
+    sso.webauthn_challenge_table("client_id", "user_id", "challenge", "target", "ttl")
+VALUES
+    ($1, $2, $3, $4, sso._ttl_timestamptz($5))
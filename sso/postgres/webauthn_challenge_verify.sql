@@ -0,0 +1,18 @@
+WITH ttl AS (
+    DELETE FROM
+        sso.webauthn_challenge_table
+    WHERE
+        "ttl" < CURRENT_TIMESTAMP
+)
+DELETE FROM
+    sso.webauthn_challenge_table
+WHERE
+    "client_id" = $1
+AND
+    "challenge" = $2
+AND
+    "target" = $3
+AND
+    "ttl" >= CURRENT_TIMESTAMP
+RETURNING
+    "user_id"
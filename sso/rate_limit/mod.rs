@@ -0,0 +1,483 @@
+//! # Rate Limit
+//!
+//! Requests to the public server are limited per route group using token buckets, keyed by
+//! client IP address or client ID. Client IP address is the peer address of the request, or is
+//! read from the `X-Forwarded-For` header for requests from trusted proxies. Client ID is only
+//! used if the client secret of the request matches. Each bucket holds up to the configured number of requests
+//! and is refilled at a constant rate over the configured interval. Rejected requests receive
+//! a `429 Too Many Requests` response with a `Retry-After` header. The number of buckets is
+//! limited, the least recently used bucket is removed when full.
+use crate::internal::*;
+use actix_web::{
+    dev::{Service, ServiceRequest, ServiceResponse, Transform},
+    http::{
+        header::{self, Header},
+        StatusCode,
+    },
+    HttpResponse,
+};
+use actix_web_httpauth::headers::authorization::{Authorization, Basic};
+use futures::future::{ok, Either, Ready};
+use ring::hmac;
+use std::{
+    collections::BTreeMap,
+    net::IpAddr,
+    sync::Mutex,
+    task::{Context, Poll},
+    time::{Duration, Instant},
+};
+
+/// Maximum number of buckets kept per group, least recently used bucket is removed when full
+pub const BUCKETS_MAX: usize = 10000;
+
+/// Rate Limit Configuration
+#[derive(Debug, Default, Clone, Serialize, Deserialize)]
+pub struct Config {
+    /// OAuth2 token endpoint limit, `/v2/oauth2/token`
+    pub token: Option<ConfigLimit>,
+    /// Authentication interface limit, `/v2/auth/*`
+    pub auth: Option<ConfigLimit>,
+    /// User API limit, `/v2/user/*`
+    pub user: Option<ConfigLimit>,
+}
+
+/// Rate Limit Group Configuration
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ConfigLimit {
+    /// Number of requests allowed in a burst
+    pub requests: u32,
+    /// Number of seconds over which requests are refilled
+    #[serde(default = "default_as_60")]
+    pub interval_s: i64,
+    /// Request key, client IP address or client ID from HTTP basic authentication
+    /// with a valid client secret
+    #[serde(default)]
+    pub key: ConfigKey,
+}
+
+/// Rate Limit Key Configuration
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ConfigKey {
+    /// Client IP address of request
+    Address,
+    /// Client ID of request, client IP address if request has no client ID or the
+    /// client secret does not match
+    ClientId,
+}
+
+impl Default for ConfigKey {
+    fn default() -> Self {
+        Self::Address
+    }
+}
+
+/// Route group
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Group {
+    /// OAuth2 token endpoint
+    Token,
+    /// Browser authentication endpoints
+    Auth,
+    /// User API endpoints
+    User,
+}
+
+impl Group {
+    /// Returns group of request path, none if path is not limited
+    pub fn from_path(path: &str) -> Option<Self> {
+        if path == "/v2/oauth2/token" {
+            Some(Self::Token)
+        } else if path == "/v2/oauth2/authorize" || path.starts_with("/v2/auth/") {
+            Some(Self::Auth)
+        } else if path.starts_with("/v2/user/") {
+            Some(Self::User)
+        } else {
+            None
+        }
+    }
+
+    /// Returns group name used in metrics
+    pub fn as_str(&self) -> &str {
+        match self {
+            Self::Token => "token",
+            Self::Auth => "auth",
+            Self::User => "user",
+        }
+    }
+}
+
+/// Token bucket
+#[derive(Debug)]
+struct Bucket {
+    tokens: f64,
+    updated_at: Instant,
+    used: u64,
+}
+
+/// Token buckets by key, with keys ordered by last use
+#[derive(Debug, Default)]
+struct Buckets {
+    buckets: HashMap<String, Bucket>,
+    used: BTreeMap<u64, String>,
+    count: u64,
+}
+
+/// Limiter
+#[derive(Debug)]
+pub struct Limiter {
+    capacity: f64,
+    rate: f64,
+    buckets_max: usize,
+    buckets: Mutex<Buckets>,
+}
+
+impl Limiter {
+    /// Create limiter from group configuration
+    pub fn from_config(config: &ConfigLimit) -> Self {
+        let capacity = f64::from(config.requests.max(1));
+        let interval_s = config.interval_s.max(1) as f64;
+        Self {
+            capacity,
+            rate: capacity / interval_s,
+            buckets_max: BUCKETS_MAX,
+            buckets: Mutex::new(Buckets::default()),
+        }
+    }
+
+    /// Take a request from bucket of key, returns duration to wait before retrying if
+    /// bucket is empty
+    pub fn check(&self, key: &str, now: Instant) -> Option<Duration> {
+        let mut buckets = self.buckets.lock().unwrap();
+        let Buckets {
+            buckets,
+            used,
+            count,
+        } = &mut *buckets;
+        *count += 1;
+
+        match buckets.get(key) {
+            Some(bucket) => {
+                used.remove(&bucket.used);
+            }
+            None => {
+                if buckets.len() >= self.buckets_max {
+                    if let Some(oldest) = used.keys().next().copied() {
+                        if let Some(oldest_key) = used.remove(&oldest) {
+                            buckets.remove(&oldest_key);
+                        }
+                    }
+                }
+            }
+        }
+        used.insert(*count, key.to_string());
+
+        let capacity = self.capacity;
+        let bucket = buckets.entry(key.to_string()).or_insert(Bucket {
+            tokens: capacity,
+            updated_at: now,
+            used: *count,
+        });
+        bucket.used = *count;
+
+        let elapsed = now.saturating_duration_since(bucket.updated_at);
+        bucket.tokens = (bucket.tokens + elapsed.as_secs_f64() * self.rate).min(capacity);
+        bucket.updated_at = now;
+
+        if bucket.tokens >= 1.0 {
+            bucket.tokens -= 1.0;
+            None
+        } else {
+            Some(Duration::from_secs_f64((1.0 - bucket.tokens) / self.rate))
+        }
+    }
+}
+
+/// Rate Limit Group
+struct RateLimitGroup {
+    group: Group,
+    key: ConfigKey,
+    limiter: Limiter,
+    reject_count: opentelemetry::metrics::Counter<u64>,
+}
+
+/// Rate Limit
+#[derive(Clone)]
+pub struct RateLimit {
+    groups: Arc<Vec<RateLimitGroup>>,
+    trusted_proxies: Arc<Vec<IpAddr>>,
+    client_secrets: Arc<HashMap<Uuid, Vec<u8>>>,
+}
+
+/// Create rate limit from configuration
+pub fn from_config(
+    metrics: &metrics::Metrics,
+    config: &Config,
+    trusted_proxies: &[IpAddr],
+    clients: &HashMap<Uuid, ConfigOauth2Client>,
+) -> RateLimit {
+    RateLimit::from_config(metrics, config, trusted_proxies, clients)
+}
+
+impl RateLimit {
+    /// Create rate limit from configuration, trusted proxies and clients are used to
+    /// find the key of requests
+    pub fn from_config(
+        metrics: &metrics::Metrics,
+        config: &Config,
+        trusted_proxies: &[IpAddr],
+        clients: &HashMap<Uuid, ConfigOauth2Client>,
+    ) -> Self {
+        let counter = metrics
+            .meter()
+            .u64_counter("rate_limit_reject_count")
+            .with_description("Total number of requests rejected by rate limit.")
+            .init();
+
+        let groups = vec![
+            (Group::Token, &config.token),
+            (Group::Auth, &config.auth),
+            (Group::User, &config.user),
+        ]
+        .into_iter()
+        .filter_map(|(group, limit)| {
+            limit.as_ref().map(|limit| RateLimitGroup {
+                group,
+                key: limit.key,
+                limiter: Limiter::from_config(limit),
+                reject_count: counter.clone(),
+            })
+        })
+        .collect();
+
+        let client_secrets = clients
+            .iter()
+            .filter_map(|(id, client)| {
+                base64::decode(&client.secret)
+                    .ok()
+                    .map(|secret| (*id, secret))
+            })
+            .collect();
+
+        Self {
+            groups: Arc::new(groups),
+            trusted_proxies: Arc::new(trusted_proxies.to_vec()),
+            client_secrets: Arc::new(client_secrets),
+        }
+    }
+
+    /// Returns client ID from HTTP basic authentication of request if client secret matches
+    fn client_id(&self, req: &ServiceRequest) -> Option<String> {
+        let auth = Authorization::<Basic>::parse(req).ok()?.into_scheme();
+        let id = Uuid::parse_str(auth.user_id()).ok()?;
+        let secret_hash = self.client_secrets.get(&id)?;
+        let secret = auth.password()?;
+
+        if client_secret_verify(id, secret, secret_hash) {
+            Some(id.to_string())
+        } else {
+            None
+        }
+    }
+
+    /// Returns duration to wait before retrying if request is limited
+    fn check(&self, req: &ServiceRequest) -> Option<Duration> {
+        let group = Group::from_path(req.path())?;
+        let group = self.groups.iter().find(|x| x.group == group)?;
+
+        let peer = req.peer_addr().map(|x| x.to_string());
+        let forwarded_for = req
+            .headers()
+            .get("x-forwarded-for")
+            .and_then(|x| x.to_str().ok());
+        let address =
+            lockout::client_address(peer.as_deref(), forwarded_for, &self.trusted_proxies)
+                .unwrap_or_else(|| "unknown".to_string());
+        let key = match group.key {
+            ConfigKey::Address => address,
+            ConfigKey::ClientId => self.client_id(req).unwrap_or(address),
+        };
+
+        let retry_after = group.limiter.check(&key, Instant::now());
+        if retry_after.is_some() {
+            group.reject_count.add(
+                1,
+                &[opentelemetry::KeyValue::new(
+                    "group",
+                    group.group.as_str().to_string(),
+                )],
+            );
+        }
+        retry_after
+    }
+}
+
+/// Returns true if secret matches client secret hash, client secrets are stored as SHA256
+/// HMAC of client ID like `sso._secret_hash`
+fn client_secret_verify(id: Uuid, secret: &str, secret_hash: &[u8]) -> bool {
+    let key = hmac::Key::new(hmac::HMAC_SHA256, secret.as_bytes());
+    hmac::verify(&key, id.to_string().as_bytes(), secret_hash).is_ok()
+}
+
+impl<S, B> Transform<S> for RateLimit
+where
+    S: Service<Request = ServiceRequest, Response = ServiceResponse<B>, Error = actix_web::Error>,
+    S::Future: 'static,
+    B: 'static,
+{
+    type Request = ServiceRequest;
+    type Response = ServiceResponse<B>;
+    type Error = actix_web::Error;
+    type InitError = ();
+    type Transform = RateLimitMiddleware<S>;
+    type Future = Ready<std::result::Result<Self::Transform, Self::InitError>>;
+
+    fn new_transform(&self, service: S) -> Self::Future {
+        ok(RateLimitMiddleware {
+            service,
+            rate_limit: self.clone(),
+        })
+    }
+}
+
+/// Rate Limit Middleware
+#[derive(Debug)]
+pub struct RateLimitMiddleware<S> {
+    service: S,
+    rate_limit: RateLimit,
+}
+
+impl<S, B> Service for RateLimitMiddleware<S>
+where
+    S: Service<Request = ServiceRequest, Response = ServiceResponse<B>, Error = actix_web::Error>,
+    S::Future: 'static,
+    B: 'static,
+{
+    type Request = ServiceRequest;
+    type Response = ServiceResponse<B>;
+    type Error = actix_web::Error;
+    type Future = Either<S::Future, Ready<std::result::Result<Self::Response, Self::Error>>>;
+
+    fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<std::result::Result<(), Self::Error>> {
+        self.service.poll_ready(cx)
+    }
+
+    fn call(&mut self, req: ServiceRequest) -> Self::Future {
+        match self.rate_limit.check(&req) {
+            Some(retry_after) => Either::Right(ok(req.error_response(RateLimitError {
+                retry_after_s: retry_after.as_secs() + 1,
+            }))),
+            None => Either::Left(self.service.call(req)),
+        }
+    }
+}
+
+/// Rate Limit Error
+#[derive(Debug)]
+struct RateLimitError {
+    retry_after_s: u64,
+}
+
+impl std::fmt::Display for RateLimitError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "rate limit exceeded, retry after {}s",
+            self.retry_after_s
+        )
+    }
+}
+
+impl actix_web::ResponseError for RateLimitError {
+    fn status_code(&self) -> StatusCode {
+        StatusCode::TOO_MANY_REQUESTS
+    }
+
+    fn error_response(&self) -> HttpResponse {
+        HttpResponse::build(self.status_code())
+            .header(header::RETRY_AFTER, self.retry_after_s.to_string())
+            .finish()
+    }
+}
+
+impl std::fmt::Debug for RateLimit {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "RateLimit {{ groups }}")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_group_from_path() {
+        assert_eq!(Group::from_path("/v2/oauth2/token"), Some(Group::Token));
+        assert_eq!(Group::from_path("/v2/oauth2/authorize"), Some(Group::Auth));
+        assert_eq!(
+            Group::from_path("/v2/auth/password-reset"),
+            Some(Group::Auth)
+        );
+        assert_eq!(Group::from_path("/v2/user/read"), Some(Group::User));
+        assert_eq!(Group::from_path("/v2/user-access/read"), None);
+        assert_eq!(Group::from_path("/ping"), None);
+    }
+
+    #[test]
+    fn test_limiter_check() {
+        let limiter = Limiter::from_config(&ConfigLimit {
+            requests: 2,
+            interval_s: 10,
+            key: ConfigKey::Address,
+        });
+        let now = Instant::now();
+
+        assert_eq!(limiter.check("a", now), None);
+        assert_eq!(limiter.check("a", now), None);
+        assert_eq!(limiter.check("a", now), Some(Duration::from_secs(5)));
+        // Keys have separate buckets
+        assert_eq!(limiter.check("b", now), None);
+        // Bucket is refilled at rate of 2 requests per 10 seconds
+        assert_eq!(limiter.check("a", now + Duration::from_secs(5)), None);
+        assert_eq!(
+            limiter.check("a", now + Duration::from_secs(5)),
+            Some(Duration::from_secs(5))
+        );
+        // Bucket is not refilled beyond capacity
+        assert_eq!(limiter.check("a", now + Duration::from_secs(100)), None);
+        assert_eq!(limiter.check("a", now + Duration::from_secs(100)), None);
+        assert!(limiter.check("a", now + Duration::from_secs(100)).is_some());
+    }
+
+    #[test]
+    fn test_client_secret_verify() {
+        let id = Uuid::parse_str("e5f1a3b4-9b0e-4a5e-8f57-1c2d3e4f5a6b").unwrap();
+        let secret_hash = base64::decode("m/OsA+xgIz8YGG0GtOiHxSHJjDKu6xEWdJCri5hCCrQ=").unwrap();
+
+        assert!(client_secret_verify(id, "s3cret", &secret_hash));
+        assert!(!client_secret_verify(id, "guess", &secret_hash));
+        assert!(!client_secret_verify(Uuid::nil(), "s3cret", &secret_hash));
+    }
+
+    #[test]
+    fn test_limiter_check_buckets_max() {
+        let mut limiter = Limiter::from_config(&ConfigLimit {
+            requests: 1,
+            interval_s: 10,
+            key: ConfigKey::Address,
+        });
+        limiter.buckets_max = 2;
+        let now = Instant::now();
+
+        assert_eq!(limiter.check("a", now), None);
+        assert_eq!(limiter.check("b", now), None);
+        assert!(limiter.check("a", now).is_some());
+        // Least recently used bucket is removed when full
+        assert_eq!(limiter.check("c", now), None);
+        assert_eq!(limiter.buckets.lock().unwrap().buckets.len(), 2);
+        assert_eq!(limiter.buckets.lock().unwrap().used.len(), 2);
+        assert!(limiter.check("a", now).is_some());
+        assert!(limiter.check("c", now).is_some());
+        assert_eq!(limiter.check("b", now), None);
+    }
+}
@@ -0,0 +1,227 @@
+//! # TOTP
+//!
+//! Time-based one-time passwords ([RFC 6238](https://tools.ietf.org/html/rfc6238)) using
+//! HMAC-SHA1, 6 digits and a 30 second time step, the defaults supported by most
+//! authenticator apps.
+use crate::internal::*;
+use ring::{hmac, rand::SecureRandom};
+
+/// Secret length in bytes
+pub const SECRET_LEN: usize = 20;
+
+/// Time step in seconds
+pub const STEP_S: u64 = 30;
+
+/// Number of code digits
+pub const DIGITS: usize = 6;
+
+/// Number of time steps before and after the current step accepted to allow for clock drift
+pub const SKEW: i64 = 1;
+
+/// Number of recovery codes generated for a user
+pub const RECOVERY_CODES: usize = 10;
+
+/// Recovery code length in bytes
+pub const RECOVERY_CODE_LEN: usize = 10;
+
+const BASE32_ALPHABET: &[u8; 32] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZ234567";
+
+/// Returns random base32 encoded secret
+pub fn secret_generate() -> Result<String> {
+    let mut secret = [0u8; SECRET_LEN];
+    ring::rand::SystemRandom::new()
+        .fill(&mut secret)
+        .map_err(|_e| Error::from("totp secret generate failed"))?;
+    Ok(base32_encode(&secret))
+}
+
+/// Returns random base32 encoded recovery codes, formatted in groups of four characters
+pub fn recovery_codes_generate() -> Result<Vec<String>> {
+    let rng = ring::rand::SystemRandom::new();
+    (0..RECOVERY_CODES)
+        .map(|_| {
+            let mut code = [0u8; RECOVERY_CODE_LEN];
+            rng.fill(&mut code)
+                .map_err(|_e| Error::from("recovery code generate failed"))?;
+            let code = base32_encode(&code);
+            Ok(code
+                .as_bytes()
+                .chunks(4)
+                .map(|x| String::from_utf8_lossy(x).to_string())
+                .collect::<Vec<_>>()
+                .join("-"))
+        })
+        .collect()
+}
+
+/// Returns recovery code without separators or whitespace in upper case, this value is hashed
+pub fn recovery_code_normalise(value: &str) -> String {
+    value
+        .chars()
+        .filter(|x| x.is_ascii_alphanumeric())
+        .map(|x| x.to_ascii_uppercase())
+        .collect()
+}
+
+/// Returns otpauth URI used to add secret to authenticator apps
+pub fn uri(issuer: &str, account: &str, secret: &str) -> Url {
+    let mut uri = Url::parse("otpauth://totp/").unwrap();
+    uri.set_path(&format!("{}:{}", issuer, account));
+    uri.query_pairs_mut()
+        .append_pair("secret", secret)
+        .append_pair("issuer", issuer)
+        .append_pair("algorithm", "SHA1")
+        .append_pair("digits", &DIGITS.to_string())
+        .append_pair("period", &STEP_S.to_string());
+    uri
+}
+
+/// Returns QR code SVG image of otpauth URI
+pub fn uri_qr_svg(uri: &Url) -> Result<String> {
+    use qrcode::{render::svg, QrCode};
+
+    let code = QrCode::new(uri.as_str().as_bytes()).map_err(|e| Error::from(e.to_string()))?;
+    Ok(code.render::<svg::Color>().min_dimensions(200, 200).build())
+}
+
+/// Returns time step of UNIX timestamp
+pub fn step(timestamp_s: u64) -> i64 {
+    (timestamp_s / STEP_S) as i64
+}
+
+/// Returns code for time step
+pub fn code(secret: &[u8], step: i64) -> String {
+    let key = hmac::Key::new(hmac::HMAC_SHA1_FOR_LEGACY_USE_ONLY, secret);
+    let tag = hmac::sign(&key, &step.to_be_bytes());
+    let hash = tag.as_ref();
+
+    let offset = (hash[hash.len() - 1] & 0x0f) as usize;
+    let binary = (u32::from(hash[offset]) & 0x7f) << 24
+        | u32::from(hash[offset + 1]) << 16
+        | u32::from(hash[offset + 2]) << 8
+        | u32::from(hash[offset + 3]);
+
+    format!(
+        "{:0width$}",
+        binary % 10u32.pow(DIGITS as u32),
+        width = DIGITS
+    )
+}
+
+/// Verify code against base32 encoded secret at UNIX timestamp
+///
+/// Returns matched time step if code is valid, steps at or before `step_used`
+/// are rejected to prevent codes being replayed.
+pub fn verify(secret: &str, value: &str, timestamp_s: u64, step_used: i64) -> Option<i64> {
+    let secret = base32_decode(secret)?;
+    let current = step(timestamp_s);
+
+    ((current - SKEW)..=(current + SKEW))
+        .filter(|x| *x > step_used)
+        .find(|x| constant_time_eq(code(&secret, *x).as_bytes(), value.as_bytes()))
+}
+
+/// Returns current UNIX timestamp
+pub fn timestamp() -> u64 {
+    std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map(|x| x.as_secs())
+        .unwrap_or(0)
+}
+
+fn constant_time_eq(a: &[u8], b: &[u8]) -> bool {
+    ring::constant_time::verify_slices_are_equal(a, b).is_ok()
+}
+
+fn base32_encode(data: &[u8]) -> String {
+    let mut out = String::new();
+    let mut buffer: u32 = 0;
+    let mut bits = 0;
+
+    for byte in data {
+        buffer = (buffer << 8) | u32::from(*byte);
+        bits += 8;
+        while bits >= 5 {
+            bits -= 5;
+            out.push(BASE32_ALPHABET[((buffer >> bits) & 0x1f) as usize] as char);
+        }
+    }
+    if bits > 0 {
+        out.push(BASE32_ALPHABET[((buffer << (5 - bits)) & 0x1f) as usize] as char);
+    }
+    out
+}
+
+fn base32_decode(data: &str) -> Option<Vec<u8>> {
+    let mut out = Vec::new();
+    let mut buffer: u32 = 0;
+    let mut bits = 0;
+
+    for c in data.trim_end_matches('=').bytes() {
+        let value = BASE32_ALPHABET
+            .iter()
+            .position(|x| *x == c.to_ascii_uppercase())?;
+        buffer = (buffer << 5) | value as u32;
+        bits += 5;
+        if bits >= 8 {
+            bits -= 8;
+            out.push(((buffer >> bits) & 0xff) as u8);
+        }
+    }
+    Some(out)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SECRET: &[u8] = b"12345678901234567890";
+    const SECRET_BASE32: &str = "GEZDGNBVGY3TQOJQGEZDGNBVGY3TQOJQ";
+
+    #[test]
+    fn test_base32() {
+        assert_eq!(base32_encode(SECRET), SECRET_BASE32);
+        assert_eq!(base32_decode(SECRET_BASE32).unwrap(), SECRET);
+        assert!(base32_decode("not base32!").is_none());
+    }
+
+    #[test]
+    fn test_code_rfc6238() {
+        assert_eq!(code(SECRET, step(59)), "287082");
+        assert_eq!(code(SECRET, step(1111111109)), "081804");
+        assert_eq!(code(SECRET, step(1234567890)), "005924");
+    }
+
+    #[test]
+    fn test_verify() {
+        assert_eq!(
+            verify(SECRET_BASE32, "081804", 1111111109, 0),
+            Some(37037036)
+        );
+        assert_eq!(
+            verify(SECRET_BASE32, "081804", 1111111109 + 30, 0),
+            Some(37037036)
+        );
+        assert_eq!(verify(SECRET_BASE32, "081804", 1111111109 + 90, 0), None);
+        assert_eq!(verify(SECRET_BASE32, "081804", 1111111109, 37037036), None);
+        assert_eq!(verify(SECRET_BASE32, "000000", 1111111109, 0), None);
+    }
+
+    #[test]
+    fn test_recovery_codes_generate() {
+        let codes = recovery_codes_generate().unwrap();
+        assert_eq!(codes.len(), RECOVERY_CODES);
+        for code in codes {
+            assert_eq!(code.len(), 19);
+            let code = recovery_code_normalise(&code);
+            assert_eq!(base32_decode(&code).unwrap().len(), RECOVERY_CODE_LEN);
+        }
+        assert_eq!(recovery_code_normalise(" abcd-EFGH "), "ABCDEFGH");
+    }
+
+    #[test]
+    fn test_secret_generate() {
+        let secret = secret_generate().unwrap();
+        assert_eq!(base32_decode(&secret).unwrap().len(), SECRET_LEN);
+    }
+}
@@ -151,6 +151,35 @@ pub const PASSWORD_MIN: usize = 8;
 /// Password maximum length
 pub const PASSWORD_MAX: usize = 64;
 
+/// Validates a TOTP code
+pub fn totp(value: &str) -> std::result::Result<(), ValidationError> {
+    if value.len() != totp::DIGITS || !value.chars().all(|x| x.is_ascii_digit()) {
+        return Err(ValidationError::new("totp_invalid"));
+    }
+    Ok(())
+}
+
+/// Validates a recovery code
+pub fn recovery_code(value: &str) -> std::result::Result<(), ValidationError> {
+    if value.is_empty() || value.len() > 100 {
+        return Err(ValidationError::new("recovery_code_invalid"));
+    }
+    Ok(())
+}
+
+/// Validates base64url encoded WebAuthn data
+pub fn webauthn(value: &str) -> std::result::Result<(), ValidationError> {
+    if value.is_empty()
+        || value.len() > 10000
+        || !value
+            .chars()
+            .all(|x| x.is_ascii_alphanumeric() || x == '-' || x == '_')
+    {
+        return Err(ValidationError::new("webauthn_invalid"));
+    }
+    Ok(())
+}
+
 /// Validates a password
 pub fn password(value: &str) -> std::result::Result<(), ValidationError> {
     if value.len() < PASSWORD_MIN || value.len() > PASSWORD_MAX {
@@ -159,6 +188,14 @@ pub fn password(value: &str) -> std::result::Result<(), ValidationError> {
     Ok(())
 }
 
+/// Validates a password, empty string is allowed for optional form inputs
+pub fn password_optional(value: &str) -> std::result::Result<(), ValidationError> {
+    if value.is_empty() {
+        return Ok(());
+    }
+    password(value)
+}
+
 /// Name minimum length
 pub const NAME_MIN: usize = 1;
 
@@ -0,0 +1,468 @@
+//! # WebAuthn
+//!
+//! Minimal [WebAuthn](https://www.w3.org/TR/webauthn-2/) relying party used to register and
+//! verify passkeys. The `none` attestation conveyance is requested so attestation statements
+//! are not verified, supported public key algorithms are ES256 and RS256.
+use crate::internal::*;
+use ring::{digest, rand::SecureRandom, signature};
+use serde_cbor::Value;
+use std::collections::BTreeMap;
+
+/// Challenge length in bytes
+pub const CHALLENGE_LEN: usize = 32;
+
+/// COSE algorithm identifier for ECDSA with SHA-256
+pub const ALG_ES256: i64 = -7;
+
+/// COSE algorithm identifier for RSASSA-PKCS1-v1_5 with SHA-256
+pub const ALG_RS256: i64 = -257;
+
+const FLAG_UP: u8 = 0x01;
+const FLAG_UV: u8 = 0x04;
+const FLAG_AT: u8 = 0x40;
+
+const TYPE_CREATE: &str = "webauthn.create";
+const TYPE_GET: &str = "webauthn.get";
+
+/// Relying Party
+#[derive(Debug, Clone)]
+pub struct RelyingParty {
+    id: String,
+    name: String,
+    origin: String,
+}
+
+/// Registered Credential
+#[derive(Debug, Clone)]
+pub struct Credential {
+    /// Base64url encoded credential ID
+    pub id: String,
+    /// Base64url encoded COSE public key
+    pub public_key: String,
+    /// Signature counter
+    pub sign_count: u32,
+}
+
+impl RelyingParty {
+    /// Returns relying party with ID and origin of URI
+    pub fn from_uri(uri: &Url, name: &str) -> Result<Self> {
+        let id = uri
+            .host_str()
+            .ok_or_else(|| Error::from("webauthn relying party host is required"))?;
+        Ok(Self {
+            id: id.to_string(),
+            name: name.to_string(),
+            origin: uri.origin().ascii_serialization(),
+        })
+    }
+
+    /// Returns relying party ID
+    pub fn id(&self) -> &str {
+        &self.id
+    }
+
+    /// Returns relying party name
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// Verify registration ceremony response
+    ///
+    /// Arguments are base64url encoded as returned by `navigator.credentials.create`.
+    pub fn register_verify(
+        &self,
+        challenge: &str,
+        client_data: &str,
+        attestation_object: &str,
+        user_verification: bool,
+    ) -> Result<Credential> {
+        let client_data = base64url_decode(client_data)?;
+        self.client_data_verify(&client_data, TYPE_CREATE, challenge)?;
+
+        let attestation_object = base64url_decode(attestation_object)?;
+        let attestation_object: BTreeMap<String, Value> =
+            serde_cbor::from_slice(&attestation_object)
+                .map_err(|_e| Error::from("webauthn attestation object is invalid"))?;
+        let auth_data = match attestation_object.get("authData") {
+            Some(Value::Bytes(x)) => x,
+            _ => return Err(Error::from("webauthn authenticator data not found")),
+        };
+
+        let auth_data = authenticator_data_parse(auth_data)?;
+        self.authenticator_data_verify(&auth_data, user_verification)?;
+        let (id, public_key) = auth_data
+            .credential
+            .ok_or_else(|| Error::from("webauthn attested credential data not found"))?;
+
+        // Public key is parsed to check algorithm is supported before it is stored
+        PublicKey::parse(&public_key)?;
+
+        Ok(Credential {
+            id: base64url_encode(&id),
+            public_key: base64url_encode(&public_key),
+            sign_count: auth_data.sign_count,
+        })
+    }
+
+    /// Verify authentication ceremony response, returns updated signature counter
+    ///
+    /// Arguments are base64url encoded as returned by `navigator.credentials.get`.
+    pub fn login_verify(
+        &self,
+        challenge: &str,
+        credential: &Credential,
+        client_data: &str,
+        authenticator_data: &str,
+        signature: &str,
+        user_verification: bool,
+    ) -> Result<u32> {
+        let client_data = base64url_decode(client_data)?;
+        self.client_data_verify(&client_data, TYPE_GET, challenge)?;
+
+        let authenticator_data = base64url_decode(authenticator_data)?;
+        let auth_data = authenticator_data_parse(&authenticator_data)?;
+        self.authenticator_data_verify(&auth_data, user_verification)?;
+
+        let public_key = PublicKey::parse(&base64url_decode(&credential.public_key)?)?;
+        let mut message = authenticator_data.clone();
+        message.extend_from_slice(digest::digest(&digest::SHA256, &client_data).as_ref());
+        public_key.verify(&message, &base64url_decode(signature)?)?;
+
+        // Counter is zero for authenticators which do not support it, otherwise it
+        // must increase or the authenticator may have been cloned
+        if (auth_data.sign_count > 0 || credential.sign_count > 0)
+            && auth_data.sign_count <= credential.sign_count
+        {
+            return Err(Error::from("webauthn signature counter is invalid"));
+        }
+        Ok(auth_data.sign_count)
+    }
+
+    fn client_data_verify(&self, client_data: &[u8], type_: &str, challenge: &str) -> Result<()> {
+        let client_data: ClientData = serde_json::from_slice(client_data)
+            .map_err(|_e| Error::from("webauthn client data is invalid"))?;
+
+        if client_data.type_ != type_ {
+            return Err(Error::from("webauthn client data type is invalid"));
+        }
+        if !constant_time_eq(client_data.challenge.as_bytes(), challenge.as_bytes()) {
+            return Err(Error::from("webauthn challenge is invalid"));
+        }
+        if client_data.origin != self.origin {
+            return Err(Error::from("webauthn origin is invalid"));
+        }
+        Ok(())
+    }
+
+    fn authenticator_data_verify(
+        &self,
+        auth_data: &AuthenticatorData,
+        user_verification: bool,
+    ) -> Result<()> {
+        let rp_id_hash = digest::digest(&digest::SHA256, self.id.as_bytes());
+        if !constant_time_eq(auth_data.rp_id_hash, rp_id_hash.as_ref()) {
+            return Err(Error::from("webauthn relying party id is invalid"));
+        }
+        if auth_data.flags & FLAG_UP == 0 {
+            return Err(Error::from("webauthn user is not present"));
+        }
+        if user_verification && auth_data.flags & FLAG_UV == 0 {
+            return Err(Error::from("webauthn user is not verified"));
+        }
+        Ok(())
+    }
+}
+
+/// Returns random base64url encoded challenge
+pub fn challenge_generate() -> Result<String> {
+    let mut challenge = [0u8; CHALLENGE_LEN];
+    ring::rand::SystemRandom::new()
+        .fill(&mut challenge)
+        .map_err(|_e| Error::from("webauthn challenge generate failed"))?;
+    Ok(base64url_encode(&challenge))
+}
+
+/// Returns base64url encoded data without padding
+pub fn base64url_encode(data: &[u8]) -> String {
+    base64::encode_config(data, base64::URL_SAFE_NO_PAD)
+}
+
+fn base64url_decode(data: &str) -> Result<Vec<u8>> {
+    base64::decode_config(data, base64::URL_SAFE_NO_PAD)
+        .map_err(|_e| Error::from("webauthn base64url data is invalid"))
+}
+
+fn constant_time_eq(a: &[u8], b: &[u8]) -> bool {
+    ring::constant_time::verify_slices_are_equal(a, b).is_ok()
+}
+
+#[derive(Debug, Deserialize)]
+struct ClientData {
+    #[serde(rename = "type")]
+    type_: String,
+    challenge: String,
+    origin: String,
+}
+
+#[derive(Debug)]
+struct AuthenticatorData<'a> {
+    rp_id_hash: &'a [u8],
+    flags: u8,
+    sign_count: u32,
+    credential: Option<(Vec<u8>, Vec<u8>)>,
+}
+
+fn authenticator_data_parse(data: &[u8]) -> Result<AuthenticatorData<'_>> {
+    if data.len() < 37 {
+        return Err(Error::from("webauthn authenticator data is invalid"));
+    }
+    let flags = data[32];
+    let sign_count = u32::from_be_bytes([data[33], data[34], data[35], data[36]]);
+
+    let credential = if flags & FLAG_AT != 0 {
+        // AAGUID is followed by credential ID length, credential ID and COSE public key,
+        // extensions may follow the public key so its length is taken from the decoder
+        let data = &data[37..];
+        if data.len() < 18 {
+            return Err(Error::from("webauthn attested credential data is invalid"));
+        }
+        let id_len = u16::from_be_bytes([data[16], data[17]]) as usize;
+        let data = &data[18..];
+        if data.len() < id_len {
+            return Err(Error::from("webauthn attested credential data is invalid"));
+        }
+        let (id, data) = data.split_at(id_len);
+
+        let mut de = serde_cbor::Deserializer::from_slice(data);
+        <Value as serde::Deserialize>::deserialize(&mut de)
+            .map_err(|_e| Error::from("webauthn public key is invalid"))?;
+        let public_key = &data[..de.byte_offset()];
+
+        Some((id.to_vec(), public_key.to_vec()))
+    } else {
+        None
+    };
+
+    Ok(AuthenticatorData {
+        rp_id_hash: &data[..32],
+        flags,
+        sign_count,
+        credential,
+    })
+}
+
+#[derive(Debug)]
+enum PublicKey {
+    Es256(Vec<u8>),
+    Rs256 { n: Vec<u8>, e: Vec<u8> },
+}
+
+impl PublicKey {
+    fn parse(cose: &[u8]) -> Result<Self> {
+        let map: BTreeMap<Value, Value> = serde_cbor::from_slice(cose)
+            .map_err(|_e| Error::from("webauthn public key is invalid"))?;
+        let integer = |key: i128| match map.get(&Value::Integer(key)) {
+            Some(Value::Integer(x)) => Some(*x),
+            _ => None,
+        };
+        let bytes = |key: i128| match map.get(&Value::Integer(key)) {
+            Some(Value::Bytes(x)) => Some(x.clone()),
+            _ => None,
+        };
+        let invalid = || Error::from("webauthn public key is invalid");
+
+        match integer(3).map(|x| x as i64) {
+            Some(ALG_ES256) => {
+                // EC2 key type and P-256 curve
+                if integer(1) != Some(2) || integer(-1) != Some(1) {
+                    return Err(invalid());
+                }
+                let x = bytes(-2).filter(|x| x.len() == 32).ok_or_else(invalid)?;
+                let y = bytes(-3).filter(|x| x.len() == 32).ok_or_else(invalid)?;
+                let mut point = vec![0x04];
+                point.extend_from_slice(&x);
+                point.extend_from_slice(&y);
+                Ok(Self::Es256(point))
+            }
+            Some(ALG_RS256) => {
+                // RSA key type
+                if integer(1) != Some(3) {
+                    return Err(invalid());
+                }
+                let n = bytes(-1).ok_or_else(invalid)?;
+                let e = bytes(-2).ok_or_else(invalid)?;
+                Ok(Self::Rs256 { n, e })
+            }
+            _ => Err(Error::from(
+                "webauthn public key algorithm is not supported",
+            )),
+        }
+    }
+
+    fn verify(&self, message: &[u8], sig: &[u8]) -> Result<()> {
+        let result = match self {
+            Self::Es256(point) => {
+                signature::UnparsedPublicKey::new(&signature::ECDSA_P256_SHA256_ASN1, point)
+                    .verify(message, sig)
+            }
+            Self::Rs256 { n, e } => signature::RsaPublicKeyComponents { n, e }.verify(
+                &signature::RSA_PKCS1_2048_8192_SHA256,
+                message,
+                sig,
+            ),
+        };
+        result.map_err(|_e| Error::from("webauthn signature is invalid"))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ring::signature::{EcdsaKeyPair, KeyPair, ECDSA_P256_SHA256_ASN1_SIGNING};
+
+    const CHALLENGE: &str = "dGVzdC1jaGFsbGVuZ2UtdmFsdWUtMzItYnl0ZXMtbG9uZw";
+
+    fn rp() -> RelyingParty {
+        RelyingParty::from_uri(&Url::parse("https://sso.example.com/").unwrap(), "sso").unwrap()
+    }
+
+    fn key_pair() -> EcdsaKeyPair {
+        let rng = ring::rand::SystemRandom::new();
+        let pkcs8 = EcdsaKeyPair::generate_pkcs8(&ECDSA_P256_SHA256_ASN1_SIGNING, &rng).unwrap();
+        EcdsaKeyPair::from_pkcs8(&ECDSA_P256_SHA256_ASN1_SIGNING, pkcs8.as_ref()).unwrap()
+    }
+
+    fn client_data(type_: &str, challenge: &str, origin: &str) -> Vec<u8> {
+        json!({ "type": type_, "challenge": challenge, "origin": origin })
+            .to_string()
+            .into_bytes()
+    }
+
+    fn auth_data(flags: u8, sign_count: u32, attested: Option<(&[u8], &[u8])>) -> Vec<u8> {
+        let mut data = digest::digest(&digest::SHA256, b"sso.example.com")
+            .as_ref()
+            .to_vec();
+        data.push(flags);
+        data.extend_from_slice(&sign_count.to_be_bytes());
+        if let Some((id, public_key)) = attested {
+            data.extend_from_slice(&[0u8; 16]);
+            data.extend_from_slice(&(id.len() as u16).to_be_bytes());
+            data.extend_from_slice(id);
+            data.extend_from_slice(public_key);
+        }
+        data
+    }
+
+    fn cose_es256(key_pair: &EcdsaKeyPair) -> Vec<u8> {
+        let point = key_pair.public_key().as_ref();
+        let mut map = BTreeMap::new();
+        map.insert(Value::Integer(1), Value::Integer(2));
+        map.insert(Value::Integer(3), Value::Integer(ALG_ES256 as i128));
+        map.insert(Value::Integer(-1), Value::Integer(1));
+        map.insert(Value::Integer(-2), Value::Bytes(point[1..33].to_vec()));
+        map.insert(Value::Integer(-3), Value::Bytes(point[33..65].to_vec()));
+        serde_cbor::to_vec(&Value::Map(map)).unwrap()
+    }
+
+    fn register(key_pair: &EcdsaKeyPair) -> Credential {
+        let public_key = cose_es256(key_pair);
+        let mut attestation_object = BTreeMap::new();
+        attestation_object.insert(
+            Value::Text("fmt".to_string()),
+            Value::Text("none".to_string()),
+        );
+        attestation_object.insert(
+            Value::Text("attStmt".to_string()),
+            Value::Map(BTreeMap::new()),
+        );
+        attestation_object.insert(
+            Value::Text("authData".to_string()),
+            Value::Bytes(auth_data(
+                FLAG_UP | FLAG_UV | FLAG_AT,
+                0,
+                Some((b"credential", &public_key)),
+            )),
+        );
+        let attestation_object = serde_cbor::to_vec(&Value::Map(attestation_object)).unwrap();
+
+        rp().register_verify(
+            CHALLENGE,
+            &base64url_encode(&client_data(
+                TYPE_CREATE,
+                CHALLENGE,
+                "https://sso.example.com",
+            )),
+            &base64url_encode(&attestation_object),
+            true,
+        )
+        .unwrap()
+    }
+
+    fn login(
+        key_pair: &EcdsaKeyPair,
+        credential: &Credential,
+        origin: &str,
+        sign_count: u32,
+    ) -> Result<u32> {
+        let client_data = client_data(TYPE_GET, CHALLENGE, origin);
+        let auth_data = auth_data(FLAG_UP | FLAG_UV, sign_count, None);
+        let mut message = auth_data.clone();
+        message.extend_from_slice(digest::digest(&digest::SHA256, &client_data).as_ref());
+        let sig = key_pair
+            .sign(&ring::rand::SystemRandom::new(), &message)
+            .unwrap();
+
+        rp().login_verify(
+            CHALLENGE,
+            credential,
+            &base64url_encode(&client_data),
+            &base64url_encode(&auth_data),
+            &base64url_encode(sig.as_ref()),
+            true,
+        )
+    }
+
+    #[test]
+    fn test_register_login() {
+        let key_pair = key_pair();
+        let credential = register(&key_pair);
+        assert_eq!(credential.id, base64url_encode(b"credential"));
+        assert_eq!(credential.sign_count, 0);
+
+        assert_eq!(
+            login(&key_pair, &credential, "https://sso.example.com", 1).unwrap(),
+            1
+        );
+    }
+
+    #[test]
+    fn test_login_invalid() {
+        let key_pair = key_pair();
+        let mut credential = register(&key_pair);
+
+        assert!(login(&key_pair, &credential, "https://example.com", 1).is_err());
+        assert!(login(&self::key_pair(), &credential, "https://sso.example.com", 1).is_err());
+
+        credential.sign_count = 5;
+        assert!(login(&key_pair, &credential, "https://sso.example.com", 5).is_err());
+        assert!(login(&key_pair, &credential, "https://sso.example.com", 6).is_ok());
+    }
+
+    #[test]
+    fn test_authenticator_data_parse() {
+        assert!(authenticator_data_parse(&[0u8; 36]).is_err());
+        assert!(authenticator_data_parse(&auth_data(FLAG_AT, 0, None)).is_err());
+
+        let data = auth_data(FLAG_UP, 7, None);
+        let parsed = authenticator_data_parse(&data).unwrap();
+        assert_eq!(parsed.flags, FLAG_UP);
+        assert_eq!(parsed.sign_count, 7);
+        assert!(parsed.credential.is_none());
+    }
+
+    #[test]
+    fn test_challenge_generate() {
+        let challenge = challenge_generate().unwrap();
+        assert_eq!(base64url_decode(&challenge).unwrap().len(), CHALLENGE_LEN);
+    }
+}
@@ -1,6 +1,8 @@
 
 pub struct MissingAllowReset;
 pub struct AllowResetExists;
+pub struct MissingApprovalPending;
+pub struct ApprovalPendingExists;
 pub struct MissingAuditType;
 pub struct AuditTypeExists;
 pub struct MissingAuthorizationEndpoint;
@@ -11,10 +13,14 @@ pub struct MissingCreatedAt;
 pub struct CreatedAtExists;
 pub struct MissingData;
 pub struct DataExists;
+pub struct MissingDeviceNotifyEnable;
+pub struct DeviceNotifyEnableExists;
 pub struct MissingEmail;
 pub struct EmailExists;
 pub struct MissingEnable;
 pub struct EnableExists;
+pub struct MissingFactorCount;
+pub struct FactorCountExists;
 pub struct MissingGrantType;
 pub struct GrantTypeExists;
 pub struct MissingId;
@@ -23,10 +29,16 @@ pub struct MissingIssuer;
 pub struct IssuerExists;
 pub struct MissingKey;
 pub struct KeyExists;
+pub struct MissingLastSeenAt;
+pub struct LastSeenAtExists;
 pub struct MissingLimit;
 pub struct LimitExists;
 pub struct MissingLocale;
 pub struct LocaleExists;
+pub struct MissingMagicLinkEnable;
+pub struct MagicLinkEnableExists;
+pub struct MissingMethods;
+pub struct MethodsExists;
 pub struct MissingName;
 pub struct NameExists;
 pub struct MissingOauth2Provider;
@@ -37,10 +49,18 @@ pub struct MissingPassword;
 pub struct PasswordExists;
 pub struct MissingRedirectUri;
 pub struct RedirectUriExists;
+pub struct MissingRegisterApproval;
+pub struct RegisterApprovalExists;
 pub struct MissingRegisterEnable;
 pub struct RegisterEnableExists;
 pub struct MissingRegisterScope;
 pub struct RegisterScopeExists;
+pub struct MissingRemoteAddr;
+pub struct RemoteAddrExists;
+pub struct MissingRequireEmailVerified;
+pub struct RequireEmailVerifiedExists;
+pub struct MissingRequireMfa;
+pub struct RequireMfaExists;
 pub struct MissingRequireUpdate;
 pub struct RequireUpdateExists;
 pub struct MissingScope;
@@ -65,6 +85,8 @@ pub struct MissingUpdatedAt;
 pub struct UpdatedAtExists;
 pub struct MissingUri;
 pub struct UriExists;
+pub struct MissingUserAgent;
+pub struct UserAgentExists;
 pub struct MissingUserId;
 pub struct UserIdExists;
 pub struct MissingUserScope;
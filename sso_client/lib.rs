@@ -69,10 +69,30 @@ pub mod request_user_delete {
     include!("./request_user_delete.rs");
 }
 
+pub mod request_user_invite {
+    include!("./request_user_invite.rs");
+}
+
 pub mod request_user_read {
     include!("./request_user_read.rs");
 }
 
+pub mod request_user_register_approval {
+    include!("./request_user_register_approval.rs");
+}
+
+pub mod request_user_session_delete {
+    include!("./request_user_session_delete.rs");
+}
+
+pub mod request_user_session_read {
+    include!("./request_user_session_read.rs");
+}
+
+pub mod request_user_unlock {
+    include!("./request_user_unlock.rs");
+}
+
 pub mod request_user_update {
     include!("./request_user_update.rs");
 }
@@ -121,6 +141,10 @@ pub mod response_user_many {
     include!("./response_user_many.rs");
 }
 
+pub mod response_user_session_many {
+    include!("./response_user_session_many.rs");
+}
+
 pub mod client {
     use futures::Stream;
     use parking_lot::Mutex;
@@ -0,0 +1,125 @@
+#[derive(Debug, Default, Clone, Serialize, Deserialize)]
+pub struct RequestUserInvite {
+    /// User email address
+    pub email: String,
+    /// User name
+    pub name: String,
+    /// User access scope
+    pub scope: String,
+}
+
+impl RequestUserInvite {
+    /// Create a builder for this object.
+    #[inline]
+    pub fn builder() -> RequestUserInviteBuilder<crate::generics::MissingEmail, crate::generics::MissingName, crate::generics::MissingScope> {
+        RequestUserInviteBuilder {
+            body: Default::default(),
+            _email: core::marker::PhantomData,
+            _name: core::marker::PhantomData,
+            _scope: core::marker::PhantomData,
+        }
+    }
+
+    #[inline]
+    pub fn post() -> RequestUserInvitePostBuilder<crate::generics::MissingEmail, crate::generics::MissingName, crate::generics::MissingScope> {
+        RequestUserInvitePostBuilder {
+            body: Default::default(),
+            _email: core::marker::PhantomData,
+            _name: core::marker::PhantomData,
+            _scope: core::marker::PhantomData,
+        }
+    }
+}
+
+impl Into<RequestUserInvite> for RequestUserInviteBuilder<crate::generics::EmailExists, crate::generics::NameExists, crate::generics::ScopeExists> {
+    fn into(self) -> RequestUserInvite {
+        self.body
+    }
+}
+
+impl Into<RequestUserInvite> for RequestUserInvitePostBuilder<crate::generics::EmailExists, crate::generics::NameExists, crate::generics::ScopeExists> {
+    fn into(self) -> RequestUserInvite {
+        self.body
+    }
+}
+
+/// Builder for [`RequestUserInvite`](./struct.RequestUserInvite.html) object.
+#[derive(Debug, Clone)]
+pub struct RequestUserInviteBuilder<Email, Name, Scope> {
+    body: self::RequestUserInvite,
+    _email: core::marker::PhantomData<Email>,
+    _name: core::marker::PhantomData<Name>,
+    _scope: core::marker::PhantomData<Scope>,
+}
+
+impl<Email, Name, Scope> RequestUserInviteBuilder<Email, Name, Scope> {
+    /// User email address
+    #[inline]
+    pub fn email(mut self, value: impl Into<String>) -> RequestUserInviteBuilder<crate::generics::EmailExists, Name, Scope> {
+        self.body.email = value.into();
+        unsafe { std::mem::transmute(self) }
+    }
+
+    /// User name
+    #[inline]
+    pub fn name(mut self, value: impl Into<String>) -> RequestUserInviteBuilder<Email, crate::generics::NameExists, Scope> {
+        self.body.name = value.into();
+        unsafe { std::mem::transmute(self) }
+    }
+
+    /// User access scope
+    #[inline]
+    pub fn scope(mut self, value: impl Into<String>) -> RequestUserInviteBuilder<Email, Name, crate::generics::ScopeExists> {
+        self.body.scope = value.into();
+        unsafe { std::mem::transmute(self) }
+    }
+}
+
+/// Builder created by [`RequestUserInvite::post`](./struct.RequestUserInvite.html#method.post) method for a `POST` operation associated with `RequestUserInvite`.
+#[derive(Debug, Clone)]
+pub struct RequestUserInvitePostBuilder<Email, Name, Scope> {
+    body: self::RequestUserInvite,
+    _email: core::marker::PhantomData<Email>,
+    _name: core::marker::PhantomData<Name>,
+    _scope: core::marker::PhantomData<Scope>,
+}
+
+impl<Email, Name, Scope> RequestUserInvitePostBuilder<Email, Name, Scope> {
+    /// User email address
+    #[inline]
+    pub fn email(mut self, value: impl Into<String>) -> RequestUserInvitePostBuilder<crate::generics::EmailExists, Name, Scope> {
+        self.body.email = value.into();
+        unsafe { std::mem::transmute(self) }
+    }
+
+    /// User name
+    #[inline]
+    pub fn name(mut self, value: impl Into<String>) -> RequestUserInvitePostBuilder<Email, crate::generics::NameExists, Scope> {
+        self.body.name = value.into();
+        unsafe { std::mem::transmute(self) }
+    }
+
+    /// User access scope
+    #[inline]
+    pub fn scope(mut self, value: impl Into<String>) -> RequestUserInvitePostBuilder<Email, Name, crate::generics::ScopeExists> {
+        self.body.scope = value.into();
+        unsafe { std::mem::transmute(self) }
+    }
+}
+
+impl<Client: crate::client::ApiClient + Sync + 'static> crate::client::Sendable<Client> for RequestUserInvitePostBuilder<crate::generics::EmailExists, crate::generics::NameExists, crate::generics::ScopeExists> {
+    type Output = Any<serde_json::Value>;
+
+    const METHOD: http::Method = http::Method::POST;
+
+    fn rel_path(&self) -> std::borrow::Cow<'static, str> {
+        "/v2/user/invite".into()
+    }
+
+    fn modify(&self, req: Client::Request) -> Result<Client::Request, crate::client::ApiError<Client::Response>> {
+        use crate::client::Request;
+        Ok(req
+        .json(&self.body)
+        .header(http::header::ACCEPT.as_str(), "application/json"))
+    }
+}
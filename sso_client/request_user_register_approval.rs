@@ -0,0 +1,128 @@
+#[derive(Debug, Default, Clone, Serialize, Deserialize)]
+pub struct RequestUserRegisterApproval {
+    pub user_id: String,
+}
+
+impl RequestUserRegisterApproval {
+    /// Create a builder for this object.
+    #[inline]
+    pub fn builder() -> RequestUserRegisterApprovalBuilder<crate::generics::MissingUserId> {
+        RequestUserRegisterApprovalBuilder {
+            body: Default::default(),
+            _user_id: core::marker::PhantomData,
+        }
+    }
+
+    #[inline]
+    pub fn post() -> RequestUserRegisterApprovalPostBuilder<crate::generics::MissingUserId> {
+        RequestUserRegisterApprovalPostBuilder {
+            body: Default::default(),
+            _user_id: core::marker::PhantomData,
+        }
+    }
+
+    #[inline]
+    pub fn post_1() -> RequestUserRegisterApprovalPostBuilder1<crate::generics::MissingUserId> {
+        RequestUserRegisterApprovalPostBuilder1 {
+            body: Default::default(),
+            _user_id: core::marker::PhantomData,
+        }
+    }
+}
+
+impl Into<RequestUserRegisterApproval> for RequestUserRegisterApprovalBuilder<crate::generics::UserIdExists> {
+    fn into(self) -> RequestUserRegisterApproval {
+        self.body
+    }
+}
+
+impl Into<RequestUserRegisterApproval> for RequestUserRegisterApprovalPostBuilder<crate::generics::UserIdExists> {
+    fn into(self) -> RequestUserRegisterApproval {
+        self.body
+    }
+}
+
+impl Into<RequestUserRegisterApproval> for RequestUserRegisterApprovalPostBuilder1<crate::generics::UserIdExists> {
+    fn into(self) -> RequestUserRegisterApproval {
+        self.body
+    }
+}
+
+/// Builder for [`RequestUserRegisterApproval`](./struct.RequestUserRegisterApproval.html) object.
+#[derive(Debug, Clone)]
+pub struct RequestUserRegisterApprovalBuilder<UserId> {
+    body: self::RequestUserRegisterApproval,
+    _user_id: core::marker::PhantomData<UserId>,
+}
+
+impl<UserId> RequestUserRegisterApprovalBuilder<UserId> {
+    #[inline]
+    pub fn user_id(mut self, value: impl Into<String>) -> RequestUserRegisterApprovalBuilder<crate::generics::UserIdExists> {
+        self.body.user_id = value.into();
+        unsafe { std::mem::transmute(self) }
+    }
+}
+
+/// Builder created by [`RequestUserRegisterApproval::post`](./struct.RequestUserRegisterApproval.html#method.post) method for a `POST` operation associated with `RequestUserRegisterApproval`.
+#[derive(Debug, Clone)]
+pub struct RequestUserRegisterApprovalPostBuilder<UserId> {
+    body: self::RequestUserRegisterApproval,
+    _user_id: core::marker::PhantomData<UserId>,
+}
+
+impl<UserId> RequestUserRegisterApprovalPostBuilder<UserId> {
+    #[inline]
+    pub fn user_id(mut self, value: impl Into<String>) -> RequestUserRegisterApprovalPostBuilder<crate::generics::UserIdExists> {
+        self.body.user_id = value.into();
+        unsafe { std::mem::transmute(self) }
+    }
+}
+
+impl<Client: crate::client::ApiClient + Sync + 'static> crate::client::Sendable<Client> for RequestUserRegisterApprovalPostBuilder<crate::generics::UserIdExists> {
+    type Output = Any<serde_json::Value>;
+
+    const METHOD: http::Method = http::Method::POST;
+
+    fn rel_path(&self) -> std::borrow::Cow<'static, str> {
+        "/v2/user/register/approve".into()
+    }
+
+    fn modify(&self, req: Client::Request) -> Result<Client::Request, crate::client::ApiError<Client::Response>> {
+        use crate::client::Request;
+        Ok(req
+        .json(&self.body)
+        .header(http::header::ACCEPT.as_str(), "application/json"))
+    }
+}
+
+/// Builder created by [`RequestUserRegisterApproval::post_1`](./struct.RequestUserRegisterApproval.html#method.post_1) method for a `POST` operation associated with `RequestUserRegisterApproval`.
+#[derive(Debug, Clone)]
+pub struct RequestUserRegisterApprovalPostBuilder1<UserId> {
+    body: self::RequestUserRegisterApproval,
+    _user_id: core::marker::PhantomData<UserId>,
+}
+
+impl<UserId> RequestUserRegisterApprovalPostBuilder1<UserId> {
+    #[inline]
+    pub fn user_id(mut self, value: impl Into<String>) -> RequestUserRegisterApprovalPostBuilder1<crate::generics::UserIdExists> {
+        self.body.user_id = value.into();
+        unsafe { std::mem::transmute(self) }
+    }
+}
+
+impl<Client: crate::client::ApiClient + Sync + 'static> crate::client::Sendable<Client> for RequestUserRegisterApprovalPostBuilder1<crate::generics::UserIdExists> {
+    type Output = Any<serde_json::Value>;
+
+    const METHOD: http::Method = http::Method::POST;
+
+    fn rel_path(&self) -> std::borrow::Cow<'static, str> {
+        "/v2/user/register/deny".into()
+    }
+
+    fn modify(&self, req: Client::Request) -> Result<Client::Request, crate::client::ApiError<Client::Response>> {
+        use crate::client::Request;
+        Ok(req
+        .json(&self.body)
+        .header(http::header::ACCEPT.as_str(), "application/json"))
+    }
+}
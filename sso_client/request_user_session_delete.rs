@@ -0,0 +1,95 @@
+#[derive(Debug, Default, Clone, Serialize, Deserialize)]
+pub struct RequestUserSessionDelete {
+    pub id: Option<String>,
+    pub user_id: String,
+}
+
+impl RequestUserSessionDelete {
+    /// Create a builder for this object.
+    #[inline]
+    pub fn builder() -> RequestUserSessionDeleteBuilder<crate::generics::MissingUserId> {
+        RequestUserSessionDeleteBuilder {
+            body: Default::default(),
+            _user_id: core::marker::PhantomData,
+        }
+    }
+
+    #[inline]
+    pub fn post() -> RequestUserSessionDeletePostBuilder<crate::generics::MissingUserId> {
+        RequestUserSessionDeletePostBuilder {
+            body: Default::default(),
+            _user_id: core::marker::PhantomData,
+        }
+    }
+}
+
+impl Into<RequestUserSessionDelete> for RequestUserSessionDeleteBuilder<crate::generics::UserIdExists> {
+    fn into(self) -> RequestUserSessionDelete {
+        self.body
+    }
+}
+
+impl Into<RequestUserSessionDelete> for RequestUserSessionDeletePostBuilder<crate::generics::UserIdExists> {
+    fn into(self) -> RequestUserSessionDelete {
+        self.body
+    }
+}
+
+/// Builder for [`RequestUserSessionDelete`](./struct.RequestUserSessionDelete.html) object.
+#[derive(Debug, Clone)]
+pub struct RequestUserSessionDeleteBuilder<UserId> {
+    body: self::RequestUserSessionDelete,
+    _user_id: core::marker::PhantomData<UserId>,
+}
+
+impl<UserId> RequestUserSessionDeleteBuilder<UserId> {
+    #[inline]
+    pub fn id(mut self, value: impl Into<String>) -> Self {
+        self.body.id = Some(value.into());
+        self
+    }
+
+    #[inline]
+    pub fn user_id(mut self, value: impl Into<String>) -> RequestUserSessionDeleteBuilder<crate::generics::UserIdExists> {
+        self.body.user_id = value.into();
+        unsafe { std::mem::transmute(self) }
+    }
+}
+
+/// Builder created by [`RequestUserSessionDelete::post`](./struct.RequestUserSessionDelete.html#method.post) method for a `POST` operation associated with `RequestUserSessionDelete`.
+#[derive(Debug, Clone)]
+pub struct RequestUserSessionDeletePostBuilder<UserId> {
+    body: self::RequestUserSessionDelete,
+    _user_id: core::marker::PhantomData<UserId>,
+}
+
+impl<UserId> RequestUserSessionDeletePostBuilder<UserId> {
+    #[inline]
+    pub fn id(mut self, value: impl Into<String>) -> Self {
+        self.body.id = Some(value.into());
+        self
+    }
+
+    #[inline]
+    pub fn user_id(mut self, value: impl Into<String>) -> RequestUserSessionDeletePostBuilder<crate::generics::UserIdExists> {
+        self.body.user_id = value.into();
+        unsafe { std::mem::transmute(self) }
+    }
+}
+
+impl<Client: crate::client::ApiClient + Sync + 'static> crate::client::Sendable<Client> for RequestUserSessionDeletePostBuilder<crate::generics::UserIdExists> {
+    type Output = Any<serde_json::Value>;
+
+    const METHOD: http::Method = http::Method::POST;
+
+    fn rel_path(&self) -> std::borrow::Cow<'static, str> {
+        "/v2/user/session/delete".into()
+    }
+
+    fn modify(&self, req: Client::Request) -> Result<Client::Request, crate::client::ApiError<Client::Response>> {
+        use crate::client::Request;
+        Ok(req
+        .json(&self.body)
+        .header(http::header::ACCEPT.as_str(), "application/json"))
+    }
+}
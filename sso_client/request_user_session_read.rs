@@ -0,0 +1,81 @@
+#[derive(Debug, Default, Clone, Serialize, Deserialize)]
+pub struct RequestUserSessionRead {
+    pub user_id: String,
+}
+
+impl RequestUserSessionRead {
+    /// Create a builder for this object.
+    #[inline]
+    pub fn builder() -> RequestUserSessionReadBuilder<crate::generics::MissingUserId> {
+        RequestUserSessionReadBuilder {
+            body: Default::default(),
+            _user_id: core::marker::PhantomData,
+        }
+    }
+
+    #[inline]
+    pub fn post() -> RequestUserSessionReadPostBuilder<crate::generics::MissingUserId> {
+        RequestUserSessionReadPostBuilder {
+            body: Default::default(),
+            _user_id: core::marker::PhantomData,
+        }
+    }
+}
+
+impl Into<RequestUserSessionRead> for RequestUserSessionReadBuilder<crate::generics::UserIdExists> {
+    fn into(self) -> RequestUserSessionRead {
+        self.body
+    }
+}
+
+impl Into<RequestUserSessionRead> for RequestUserSessionReadPostBuilder<crate::generics::UserIdExists> {
+    fn into(self) -> RequestUserSessionRead {
+        self.body
+    }
+}
+
+/// Builder for [`RequestUserSessionRead`](./struct.RequestUserSessionRead.html) object.
+#[derive(Debug, Clone)]
+pub struct RequestUserSessionReadBuilder<UserId> {
+    body: self::RequestUserSessionRead,
+    _user_id: core::marker::PhantomData<UserId>,
+}
+
+impl<UserId> RequestUserSessionReadBuilder<UserId> {
+    #[inline]
+    pub fn user_id(mut self, value: impl Into<String>) -> RequestUserSessionReadBuilder<crate::generics::UserIdExists> {
+        self.body.user_id = value.into();
+        unsafe { std::mem::transmute(self) }
+    }
+}
+
+/// Builder created by [`RequestUserSessionRead::post`](./struct.RequestUserSessionRead.html#method.post) method for a `POST` operation associated with `RequestUserSessionRead`.
+#[derive(Debug, Clone)]
+pub struct RequestUserSessionReadPostBuilder<UserId> {
+    body: self::RequestUserSessionRead,
+    _user_id: core::marker::PhantomData<UserId>,
+}
+
+impl<UserId> RequestUserSessionReadPostBuilder<UserId> {
+    #[inline]
+    pub fn user_id(mut self, value: impl Into<String>) -> RequestUserSessionReadPostBuilder<crate::generics::UserIdExists> {
+        self.body.user_id = value.into();
+        unsafe { std::mem::transmute(self) }
+    }
+}
+
+impl<Client: crate::client::ApiClient + Sync + 'static> crate::client::Sendable<Client> for RequestUserSessionReadPostBuilder<crate::generics::UserIdExists> {
+    type Output = crate::response_user_session_many::ResponseUserSessionMany;
+
+    const METHOD: http::Method = http::Method::POST;
+
+    fn rel_path(&self) -> std::borrow::Cow<'static, str> {
+        "/v2/user/session/read".into()
+    }
+
+    fn modify(&self, req: Client::Request) -> Result<Client::Request, crate::client::ApiError<Client::Response>> {
+        use crate::client::Request;
+        Ok(req
+        .json(&self.body))
+    }
+}
@@ -0,0 +1,82 @@
+#[derive(Debug, Default, Clone, Serialize, Deserialize)]
+pub struct RequestUserUnlock {
+    pub email: String,
+}
+
+impl RequestUserUnlock {
+    /// Create a builder for this object.
+    #[inline]
+    pub fn builder() -> RequestUserUnlockBuilder<crate::generics::MissingEmail> {
+        RequestUserUnlockBuilder {
+            body: Default::default(),
+            _email: core::marker::PhantomData,
+        }
+    }
+
+    #[inline]
+    pub fn post() -> RequestUserUnlockPostBuilder<crate::generics::MissingEmail> {
+        RequestUserUnlockPostBuilder {
+            body: Default::default(),
+            _email: core::marker::PhantomData,
+        }
+    }
+}
+
+impl Into<RequestUserUnlock> for RequestUserUnlockBuilder<crate::generics::EmailExists> {
+    fn into(self) -> RequestUserUnlock {
+        self.body
+    }
+}
+
+impl Into<RequestUserUnlock> for RequestUserUnlockPostBuilder<crate::generics::EmailExists> {
+    fn into(self) -> RequestUserUnlock {
+        self.body
+    }
+}
+
+/// Builder for [`RequestUserUnlock`](./struct.RequestUserUnlock.html) object.
+#[derive(Debug, Clone)]
+pub struct RequestUserUnlockBuilder<Email> {
+    body: self::RequestUserUnlock,
+    _email: core::marker::PhantomData<Email>,
+}
+
+impl<Email> RequestUserUnlockBuilder<Email> {
+    #[inline]
+    pub fn email(mut self, value: impl Into<String>) -> RequestUserUnlockBuilder<crate::generics::EmailExists> {
+        self.body.email = value.into();
+        unsafe { std::mem::transmute(self) }
+    }
+}
+
+/// Builder created by [`RequestUserUnlock::post`](./struct.RequestUserUnlock.html#method.post) method for a `POST` operation associated with `RequestUserUnlock`.
+#[derive(Debug, Clone)]
+pub struct RequestUserUnlockPostBuilder<Email> {
+    body: self::RequestUserUnlock,
+    _email: core::marker::PhantomData<Email>,
+}
+
+impl<Email> RequestUserUnlockPostBuilder<Email> {
+    #[inline]
+    pub fn email(mut self, value: impl Into<String>) -> RequestUserUnlockPostBuilder<crate::generics::EmailExists> {
+        self.body.email = value.into();
+        unsafe { std::mem::transmute(self) }
+    }
+}
+
+impl<Client: crate::client::ApiClient + Sync + 'static> crate::client::Sendable<Client> for RequestUserUnlockPostBuilder<crate::generics::EmailExists> {
+    type Output = Any<serde_json::Value>;
+
+    const METHOD: http::Method = http::Method::POST;
+
+    fn rel_path(&self) -> std::borrow::Cow<'static, str> {
+        "/v2/user/unlock".into()
+    }
+
+    fn modify(&self, req: Client::Request) -> Result<Client::Request, crate::client::ApiError<Client::Response>> {
+        use crate::client::Request;
+        Ok(req
+        .json(&self.body)
+        .header(http::header::ACCEPT.as_str(), "application/json"))
+    }
+}
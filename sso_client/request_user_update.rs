@@ -3,6 +3,7 @@ pub struct RequestUserUpdate {
     pub access: Option<crate::request_user_update::RequestUserUpdateAccess>,
     pub email: Option<String>,
     pub enable: Option<bool>,
+    pub factor_reset: Option<bool>,
     pub id: String,
     pub locale: Option<String>,
     pub name: Option<String>,
@@ -77,6 +78,12 @@ impl<Id> RequestUserUpdateBuilder<Id> {
         self
     }
 
+    #[inline]
+    pub fn factor_reset(mut self, value: impl Into<bool>) -> Self {
+        self.body.factor_reset = Some(value.into());
+        self
+    }
+
     #[inline]
     pub fn id(mut self, value: impl Into<String>) -> RequestUserUpdateBuilder<crate::generics::IdExists> {
         self.body.id = value.into();
@@ -134,6 +141,12 @@ impl<Id> RequestUserUpdatePostBuilder<Id> {
         self
     }
 
+    #[inline]
+    pub fn factor_reset(mut self, value: impl Into<bool>) -> Self {
+        self.body.factor_reset = Some(value.into());
+        self
+    }
+
     #[inline]
     pub fn id(mut self, value: impl Into<String>) -> RequestUserUpdatePostBuilder<crate::generics::IdExists> {
         self.body.id = value.into();
@@ -1,11 +1,16 @@
 #[derive(Debug, Default, Clone, Serialize, Deserialize)]
 pub struct ResponseClient {
+    pub device_notify_enable: bool,
     pub enable: bool,
     pub id: String,
+    pub magic_link_enable: bool,
     pub name: String,
     pub redirect_uri: String,
+    pub register_approval: bool,
     pub register_enable: bool,
     pub register_scope: String,
+    pub require_email_verified: bool,
+    pub require_mfa: bool,
     pub scope: String,
     pub uri: String,
     pub user_scope: String,
@@ -14,15 +19,20 @@ pub struct ResponseClient {
 impl ResponseClient {
     /// Create a builder for this object.
     #[inline]
-    pub fn builder() -> ResponseClientBuilder<crate::generics::MissingEnable, crate::generics::MissingId, crate::generics::MissingName, crate::generics::MissingRedirectUri, crate::generics::MissingRegisterEnable, crate::generics::MissingRegisterScope, crate::generics::MissingScope, crate::generics::MissingUri, crate::generics::MissingUserScope> {
+    pub fn builder() -> ResponseClientBuilder<crate::generics::MissingDeviceNotifyEnable, crate::generics::MissingEnable, crate::generics::MissingId, crate::generics::MissingMagicLinkEnable, crate::generics::MissingName, crate::generics::MissingRedirectUri, crate::generics::MissingRegisterApproval, crate::generics::MissingRegisterEnable, crate::generics::MissingRegisterScope, crate::generics::MissingRequireEmailVerified, crate::generics::MissingRequireMfa, crate::generics::MissingScope, crate::generics::MissingUri, crate::generics::MissingUserScope> {
         ResponseClientBuilder {
             body: Default::default(),
+            _device_notify_enable: core::marker::PhantomData,
             _enable: core::marker::PhantomData,
             _id: core::marker::PhantomData,
+            _magic_link_enable: core::marker::PhantomData,
             _name: core::marker::PhantomData,
             _redirect_uri: core::marker::PhantomData,
+            _register_approval: core::marker::PhantomData,
             _register_enable: core::marker::PhantomData,
             _register_scope: core::marker::PhantomData,
+            _require_email_verified: core::marker::PhantomData,
+            _require_mfa: core::marker::PhantomData,
             _scope: core::marker::PhantomData,
             _uri: core::marker::PhantomData,
             _user_scope: core::marker::PhantomData,
@@ -35,7 +45,7 @@ impl ResponseClient {
     }
 }
 
-impl Into<ResponseClient> for ResponseClientBuilder<crate::generics::EnableExists, crate::generics::IdExists, crate::generics::NameExists, crate::generics::RedirectUriExists, crate::generics::RegisterEnableExists, crate::generics::RegisterScopeExists, crate::generics::ScopeExists, crate::generics::UriExists, crate::generics::UserScopeExists> {
+impl Into<ResponseClient> for ResponseClientBuilder<crate::generics::DeviceNotifyEnableExists, crate::generics::EnableExists, crate::generics::IdExists, crate::generics::MagicLinkEnableExists, crate::generics::NameExists, crate::generics::RedirectUriExists, crate::generics::RegisterApprovalExists, crate::generics::RegisterEnableExists, crate::generics::RegisterScopeExists, crate::generics::RequireEmailVerifiedExists, crate::generics::RequireMfaExists, crate::generics::ScopeExists, crate::generics::UriExists, crate::generics::UserScopeExists> {
     fn into(self) -> ResponseClient {
         self.body
     }
@@ -43,70 +53,105 @@ impl Into<ResponseClient> for ResponseClientBuilder<crate::generics::EnableExist
 
 /// Builder for [`ResponseClient`](./struct.ResponseClient.html) object.
 #[derive(Debug, Clone)]
-pub struct ResponseClientBuilder<Enable, Id, Name, RedirectUri, RegisterEnable, RegisterScope, Scope, Uri, UserScope> {
+pub struct ResponseClientBuilder<DeviceNotifyEnable, Enable, Id, MagicLinkEnable, Name, RedirectUri, RegisterApproval, RegisterEnable, RegisterScope, RequireEmailVerified, RequireMfa, Scope, Uri, UserScope> {
     body: self::ResponseClient,
+    _device_notify_enable: core::marker::PhantomData<DeviceNotifyEnable>,
     _enable: core::marker::PhantomData<Enable>,
     _id: core::marker::PhantomData<Id>,
+    _magic_link_enable: core::marker::PhantomData<MagicLinkEnable>,
     _name: core::marker::PhantomData<Name>,
     _redirect_uri: core::marker::PhantomData<RedirectUri>,
+    _register_approval: core::marker::PhantomData<RegisterApproval>,
     _register_enable: core::marker::PhantomData<RegisterEnable>,
     _register_scope: core::marker::PhantomData<RegisterScope>,
+    _require_email_verified: core::marker::PhantomData<RequireEmailVerified>,
+    _require_mfa: core::marker::PhantomData<RequireMfa>,
     _scope: core::marker::PhantomData<Scope>,
     _uri: core::marker::PhantomData<Uri>,
     _user_scope: core::marker::PhantomData<UserScope>,
 }
 
-impl<Enable, Id, Name, RedirectUri, RegisterEnable, RegisterScope, Scope, Uri, UserScope> ResponseClientBuilder<Enable, Id, Name, RedirectUri, RegisterEnable, RegisterScope, Scope, Uri, UserScope> {
+impl<DeviceNotifyEnable, Enable, Id, MagicLinkEnable, Name, RedirectUri, RegisterApproval, RegisterEnable, RegisterScope, RequireEmailVerified, RequireMfa, Scope, Uri, UserScope> ResponseClientBuilder<DeviceNotifyEnable, Enable, Id, MagicLinkEnable, Name, RedirectUri, RegisterApproval, RegisterEnable, RegisterScope, RequireEmailVerified, RequireMfa, Scope, Uri, UserScope> {
     #[inline]
-    pub fn enable(mut self, value: impl Into<bool>) -> ResponseClientBuilder<crate::generics::EnableExists, Id, Name, RedirectUri, RegisterEnable, RegisterScope, Scope, Uri, UserScope> {
+    pub fn device_notify_enable(mut self, value: impl Into<bool>) -> ResponseClientBuilder<crate::generics::DeviceNotifyEnableExists, Enable, Id, MagicLinkEnable, Name, RedirectUri, RegisterApproval, RegisterEnable, RegisterScope, RequireEmailVerified, RequireMfa, Scope, Uri, UserScope> {
+        self.body.device_notify_enable = value.into();
+        unsafe { std::mem::transmute(self) }
+    }
+
+    #[inline]
+    pub fn enable(mut self, value: impl Into<bool>) -> ResponseClientBuilder<DeviceNotifyEnable, crate::generics::EnableExists, Id, MagicLinkEnable, Name, RedirectUri, RegisterApproval, RegisterEnable, RegisterScope, RequireEmailVerified, RequireMfa, Scope, Uri, UserScope> {
         self.body.enable = value.into();
         unsafe { std::mem::transmute(self) }
     }
 
     #[inline]
-    pub fn id(mut self, value: impl Into<String>) -> ResponseClientBuilder<Enable, crate::generics::IdExists, Name, RedirectUri, RegisterEnable, RegisterScope, Scope, Uri, UserScope> {
+    pub fn id(mut self, value: impl Into<String>) -> ResponseClientBuilder<DeviceNotifyEnable, Enable, crate::generics::IdExists, MagicLinkEnable, Name, RedirectUri, RegisterApproval, RegisterEnable, RegisterScope, RequireEmailVerified, RequireMfa, Scope, Uri, UserScope> {
         self.body.id = value.into();
         unsafe { std::mem::transmute(self) }
     }
 
     #[inline]
-    pub fn name(mut self, value: impl Into<String>) -> ResponseClientBuilder<Enable, Id, crate::generics::NameExists, RedirectUri, RegisterEnable, RegisterScope, Scope, Uri, UserScope> {
+    pub fn magic_link_enable(mut self, value: impl Into<bool>) -> ResponseClientBuilder<DeviceNotifyEnable, Enable, Id, crate::generics::MagicLinkEnableExists, Name, RedirectUri, RegisterApproval, RegisterEnable, RegisterScope, RequireEmailVerified, RequireMfa, Scope, Uri, UserScope> {
+        self.body.magic_link_enable = value.into();
+        unsafe { std::mem::transmute(self) }
+    }
+
+    #[inline]
+    pub fn name(mut self, value: impl Into<String>) -> ResponseClientBuilder<DeviceNotifyEnable, Enable, Id, MagicLinkEnable, crate::generics::NameExists, RedirectUri, RegisterApproval, RegisterEnable, RegisterScope, RequireEmailVerified, RequireMfa, Scope, Uri, UserScope> {
         self.body.name = value.into();
         unsafe { std::mem::transmute(self) }
     }
 
     #[inline]
-    pub fn redirect_uri(mut self, value: impl Into<String>) -> ResponseClientBuilder<Enable, Id, Name, crate::generics::RedirectUriExists, RegisterEnable, RegisterScope, Scope, Uri, UserScope> {
+    pub fn redirect_uri(mut self, value: impl Into<String>) -> ResponseClientBuilder<DeviceNotifyEnable, Enable, Id, MagicLinkEnable, Name, crate::generics::RedirectUriExists, RegisterApproval, RegisterEnable, RegisterScope, RequireEmailVerified, RequireMfa, Scope, Uri, UserScope> {
         self.body.redirect_uri = value.into();
         unsafe { std::mem::transmute(self) }
     }
 
     #[inline]
-    pub fn register_enable(mut self, value: impl Into<bool>) -> ResponseClientBuilder<Enable, Id, Name, RedirectUri, crate::generics::RegisterEnableExists, RegisterScope, Scope, Uri, UserScope> {
+    pub fn register_approval(mut self, value: impl Into<bool>) -> ResponseClientBuilder<DeviceNotifyEnable, Enable, Id, MagicLinkEnable, Name, RedirectUri, crate::generics::RegisterApprovalExists, RegisterEnable, RegisterScope, RequireEmailVerified, RequireMfa, Scope, Uri, UserScope> {
+        self.body.register_approval = value.into();
+        unsafe { std::mem::transmute(self) }
+    }
+
+    #[inline]
+    pub fn register_enable(mut self, value: impl Into<bool>) -> ResponseClientBuilder<DeviceNotifyEnable, Enable, Id, MagicLinkEnable, Name, RedirectUri, RegisterApproval, crate::generics::RegisterEnableExists, RegisterScope, RequireEmailVerified, RequireMfa, Scope, Uri, UserScope> {
         self.body.register_enable = value.into();
         unsafe { std::mem::transmute(self) }
     }
 
     #[inline]
-    pub fn register_scope(mut self, value: impl Into<String>) -> ResponseClientBuilder<Enable, Id, Name, RedirectUri, RegisterEnable, crate::generics::RegisterScopeExists, Scope, Uri, UserScope> {
+    pub fn register_scope(mut self, value: impl Into<String>) -> ResponseClientBuilder<DeviceNotifyEnable, Enable, Id, MagicLinkEnable, Name, RedirectUri, RegisterApproval, RegisterEnable, crate::generics::RegisterScopeExists, RequireEmailVerified, RequireMfa, Scope, Uri, UserScope> {
         self.body.register_scope = value.into();
         unsafe { std::mem::transmute(self) }
     }
 
     #[inline]
-    pub fn scope(mut self, value: impl Into<String>) -> ResponseClientBuilder<Enable, Id, Name, RedirectUri, RegisterEnable, RegisterScope, crate::generics::ScopeExists, Uri, UserScope> {
+    pub fn require_email_verified(mut self, value: impl Into<bool>) -> ResponseClientBuilder<DeviceNotifyEnable, Enable, Id, MagicLinkEnable, Name, RedirectUri, RegisterApproval, RegisterEnable, RegisterScope, crate::generics::RequireEmailVerifiedExists, RequireMfa, Scope, Uri, UserScope> {
+        self.body.require_email_verified = value.into();
+        unsafe { std::mem::transmute(self) }
+    }
+
+    #[inline]
+    pub fn require_mfa(mut self, value: impl Into<bool>) -> ResponseClientBuilder<DeviceNotifyEnable, Enable, Id, MagicLinkEnable, Name, RedirectUri, RegisterApproval, RegisterEnable, RegisterScope, RequireEmailVerified, crate::generics::RequireMfaExists, Scope, Uri, UserScope> {
+        self.body.require_mfa = value.into();
+        unsafe { std::mem::transmute(self) }
+    }
+
+    #[inline]
+    pub fn scope(mut self, value: impl Into<String>) -> ResponseClientBuilder<DeviceNotifyEnable, Enable, Id, MagicLinkEnable, Name, RedirectUri, RegisterApproval, RegisterEnable, RegisterScope, RequireEmailVerified, RequireMfa, crate::generics::ScopeExists, Uri, UserScope> {
         self.body.scope = value.into();
         unsafe { std::mem::transmute(self) }
     }
 
     #[inline]
-    pub fn uri(mut self, value: impl Into<String>) -> ResponseClientBuilder<Enable, Id, Name, RedirectUri, RegisterEnable, RegisterScope, Scope, crate::generics::UriExists, UserScope> {
+    pub fn uri(mut self, value: impl Into<String>) -> ResponseClientBuilder<DeviceNotifyEnable, Enable, Id, MagicLinkEnable, Name, RedirectUri, RegisterApproval, RegisterEnable, RegisterScope, RequireEmailVerified, RequireMfa, Scope, crate::generics::UriExists, UserScope> {
         self.body.uri = value.into();
         unsafe { std::mem::transmute(self) }
     }
 
     #[inline]
-    pub fn user_scope(mut self, value: impl Into<String>) -> ResponseClientBuilder<Enable, Id, Name, RedirectUri, RegisterEnable, RegisterScope, Scope, Uri, crate::generics::UserScopeExists> {
+    pub fn user_scope(mut self, value: impl Into<String>) -> ResponseClientBuilder<DeviceNotifyEnable, Enable, Id, MagicLinkEnable, Name, RedirectUri, RegisterApproval, RegisterEnable, RegisterScope, RequireEmailVerified, RequireMfa, Scope, Uri, crate::generics::UserScopeExists> {
         self.body.user_scope = value.into();
         unsafe { std::mem::transmute(self) }
     }
@@ -1,9 +1,12 @@
 #[derive(Debug, Default, Clone, Serialize, Deserialize)]
 pub struct ResponseUser {
     pub access: Option<crate::response_user::ResponseUserAccess>,
+    pub approval_pending: bool,
     pub created_at: String,
     pub email: String,
+    pub email_verified_at: Option<String>,
     pub enable: bool,
+    pub factor_count: i64,
     pub id: String,
     pub locale: String,
     pub name: String,
@@ -49,12 +52,14 @@ pub struct ResponseUserPassword {
 impl ResponseUser {
     /// Create a builder for this object.
     #[inline]
-    pub fn builder() -> ResponseUserBuilder<crate::generics::MissingCreatedAt, crate::generics::MissingEmail, crate::generics::MissingEnable, crate::generics::MissingId, crate::generics::MissingLocale, crate::generics::MissingName, crate::generics::MissingOauth2Provider, crate::generics::MissingOauth2ProviderCount, crate::generics::MissingStatic, crate::generics::MissingTimezone, crate::generics::MissingUpdatedAt> {
+    pub fn builder() -> ResponseUserBuilder<crate::generics::MissingApprovalPending, crate::generics::MissingCreatedAt, crate::generics::MissingEmail, crate::generics::MissingEnable, crate::generics::MissingFactorCount, crate::generics::MissingId, crate::generics::MissingLocale, crate::generics::MissingName, crate::generics::MissingOauth2Provider, crate::generics::MissingOauth2ProviderCount, crate::generics::MissingStatic, crate::generics::MissingTimezone, crate::generics::MissingUpdatedAt> {
         ResponseUserBuilder {
             body: Default::default(),
+            _approval_pending: core::marker::PhantomData,
             _created_at: core::marker::PhantomData,
             _email: core::marker::PhantomData,
             _enable: core::marker::PhantomData,
+            _factor_count: core::marker::PhantomData,
             _id: core::marker::PhantomData,
             _locale: core::marker::PhantomData,
             _name: core::marker::PhantomData,
@@ -67,7 +72,7 @@ impl ResponseUser {
     }
 }
 
-impl Into<ResponseUser> for ResponseUserBuilder<crate::generics::CreatedAtExists, crate::generics::EmailExists, crate::generics::EnableExists, crate::generics::IdExists, crate::generics::LocaleExists, crate::generics::NameExists, crate::generics::Oauth2ProviderExists, crate::generics::Oauth2ProviderCountExists, crate::generics::StaticExists, crate::generics::TimezoneExists, crate::generics::UpdatedAtExists> {
+impl Into<ResponseUser> for ResponseUserBuilder<crate::generics::ApprovalPendingExists, crate::generics::CreatedAtExists, crate::generics::EmailExists, crate::generics::EnableExists, crate::generics::FactorCountExists, crate::generics::IdExists, crate::generics::LocaleExists, crate::generics::NameExists, crate::generics::Oauth2ProviderExists, crate::generics::Oauth2ProviderCountExists, crate::generics::StaticExists, crate::generics::TimezoneExists, crate::generics::UpdatedAtExists> {
     fn into(self) -> ResponseUser {
         self.body
     }
@@ -75,11 +80,13 @@ impl Into<ResponseUser> for ResponseUserBuilder<crate::generics::CreatedAtExists
 
 /// Builder for [`ResponseUser`](./struct.ResponseUser.html) object.
 #[derive(Debug, Clone)]
-pub struct ResponseUserBuilder<CreatedAt, Email, Enable, Id, Locale, Name, Oauth2Provider, Oauth2ProviderCount, Static, Timezone, UpdatedAt> {
+pub struct ResponseUserBuilder<ApprovalPending, CreatedAt, Email, Enable, FactorCount, Id, Locale, Name, Oauth2Provider, Oauth2ProviderCount, Static, Timezone, UpdatedAt> {
     body: self::ResponseUser,
+    _approval_pending: core::marker::PhantomData<ApprovalPending>,
     _created_at: core::marker::PhantomData<CreatedAt>,
     _email: core::marker::PhantomData<Email>,
     _enable: core::marker::PhantomData<Enable>,
+    _factor_count: core::marker::PhantomData<FactorCount>,
     _id: core::marker::PhantomData<Id>,
     _locale: core::marker::PhantomData<Locale>,
     _name: core::marker::PhantomData<Name>,
@@ -90,7 +97,7 @@ pub struct ResponseUserBuilder<CreatedAt, Email, Enable, Id, Locale, Name, Oauth
     _updated_at: core::marker::PhantomData<UpdatedAt>,
 }
 
-impl<CreatedAt, Email, Enable, Id, Locale, Name, Oauth2Provider, Oauth2ProviderCount, Static, Timezone, UpdatedAt> ResponseUserBuilder<CreatedAt, Email, Enable, Id, Locale, Name, Oauth2Provider, Oauth2ProviderCount, Static, Timezone, UpdatedAt> {
+impl<ApprovalPending, CreatedAt, Email, Enable, FactorCount, Id, Locale, Name, Oauth2Provider, Oauth2ProviderCount, Static, Timezone, UpdatedAt> ResponseUserBuilder<ApprovalPending, CreatedAt, Email, Enable, FactorCount, Id, Locale, Name, Oauth2Provider, Oauth2ProviderCount, Static, Timezone, UpdatedAt> {
     #[inline]
     pub fn access(mut self, value: crate::response_user::ResponseUserAccessBuilder<crate::generics::ClientIdExists, crate::generics::CreatedAtExists, crate::generics::EnableExists, crate::generics::ScopeExists, crate::generics::StaticExists, crate::generics::UpdatedAtExists, crate::generics::UserIdExists>) -> Self {
         self.body.access = Some(value.into());
@@ -98,49 +105,67 @@ impl<CreatedAt, Email, Enable, Id, Locale, Name, Oauth2Provider, Oauth2ProviderC
     }
 
     #[inline]
-    pub fn created_at(mut self, value: impl Into<String>) -> ResponseUserBuilder<crate::generics::CreatedAtExists, Email, Enable, Id, Locale, Name, Oauth2Provider, Oauth2ProviderCount, Static, Timezone, UpdatedAt> {
+    pub fn approval_pending(mut self, value: impl Into<bool>) -> ResponseUserBuilder<crate::generics::ApprovalPendingExists, CreatedAt, Email, Enable, FactorCount, Id, Locale, Name, Oauth2Provider, Oauth2ProviderCount, Static, Timezone, UpdatedAt> {
+        self.body.approval_pending = value.into();
+        unsafe { std::mem::transmute(self) }
+    }
+
+    #[inline]
+    pub fn created_at(mut self, value: impl Into<String>) -> ResponseUserBuilder<ApprovalPending, crate::generics::CreatedAtExists, Email, Enable, FactorCount, Id, Locale, Name, Oauth2Provider, Oauth2ProviderCount, Static, Timezone, UpdatedAt> {
         self.body.created_at = value.into();
         unsafe { std::mem::transmute(self) }
     }
 
     #[inline]
-    pub fn email(mut self, value: impl Into<String>) -> ResponseUserBuilder<CreatedAt, crate::generics::EmailExists, Enable, Id, Locale, Name, Oauth2Provider, Oauth2ProviderCount, Static, Timezone, UpdatedAt> {
+    pub fn email(mut self, value: impl Into<String>) -> ResponseUserBuilder<ApprovalPending, CreatedAt, crate::generics::EmailExists, Enable, FactorCount, Id, Locale, Name, Oauth2Provider, Oauth2ProviderCount, Static, Timezone, UpdatedAt> {
         self.body.email = value.into();
         unsafe { std::mem::transmute(self) }
     }
 
     #[inline]
-    pub fn enable(mut self, value: impl Into<bool>) -> ResponseUserBuilder<CreatedAt, Email, crate::generics::EnableExists, Id, Locale, Name, Oauth2Provider, Oauth2ProviderCount, Static, Timezone, UpdatedAt> {
+    pub fn email_verified_at(mut self, value: impl Into<String>) -> Self {
+        self.body.email_verified_at = Some(value.into());
+        self
+    }
+
+    #[inline]
+    pub fn enable(mut self, value: impl Into<bool>) -> ResponseUserBuilder<ApprovalPending, CreatedAt, Email, crate::generics::EnableExists, FactorCount, Id, Locale, Name, Oauth2Provider, Oauth2ProviderCount, Static, Timezone, UpdatedAt> {
         self.body.enable = value.into();
         unsafe { std::mem::transmute(self) }
     }
 
     #[inline]
-    pub fn id(mut self, value: impl Into<String>) -> ResponseUserBuilder<CreatedAt, Email, Enable, crate::generics::IdExists, Locale, Name, Oauth2Provider, Oauth2ProviderCount, Static, Timezone, UpdatedAt> {
+    pub fn factor_count(mut self, value: impl Into<i64>) -> ResponseUserBuilder<ApprovalPending, CreatedAt, Email, Enable, crate::generics::FactorCountExists, Id, Locale, Name, Oauth2Provider, Oauth2ProviderCount, Static, Timezone, UpdatedAt> {
+        self.body.factor_count = value.into();
+        unsafe { std::mem::transmute(self) }
+    }
+
+    #[inline]
+    pub fn id(mut self, value: impl Into<String>) -> ResponseUserBuilder<ApprovalPending, CreatedAt, Email, Enable, FactorCount, crate::generics::IdExists, Locale, Name, Oauth2Provider, Oauth2ProviderCount, Static, Timezone, UpdatedAt> {
         self.body.id = value.into();
         unsafe { std::mem::transmute(self) }
     }
 
     #[inline]
-    pub fn locale(mut self, value: impl Into<String>) -> ResponseUserBuilder<CreatedAt, Email, Enable, Id, crate::generics::LocaleExists, Name, Oauth2Provider, Oauth2ProviderCount, Static, Timezone, UpdatedAt> {
+    pub fn locale(mut self, value: impl Into<String>) -> ResponseUserBuilder<ApprovalPending, CreatedAt, Email, Enable, FactorCount, Id, crate::generics::LocaleExists, Name, Oauth2Provider, Oauth2ProviderCount, Static, Timezone, UpdatedAt> {
         self.body.locale = value.into();
         unsafe { std::mem::transmute(self) }
     }
 
     #[inline]
-    pub fn name(mut self, value: impl Into<String>) -> ResponseUserBuilder<CreatedAt, Email, Enable, Id, Locale, crate::generics::NameExists, Oauth2Provider, Oauth2ProviderCount, Static, Timezone, UpdatedAt> {
+    pub fn name(mut self, value: impl Into<String>) -> ResponseUserBuilder<ApprovalPending, CreatedAt, Email, Enable, FactorCount, Id, Locale, crate::generics::NameExists, Oauth2Provider, Oauth2ProviderCount, Static, Timezone, UpdatedAt> {
         self.body.name = value.into();
         unsafe { std::mem::transmute(self) }
     }
 
     #[inline]
-    pub fn oauth2_provider(mut self, value: impl Iterator<Item = crate::response_user::ResponseUserOauth2ProviderItemBuilder<crate::generics::CreatedAtExists, crate::generics::Oauth2ProviderExists, crate::generics::StaticExists, crate::generics::SubExists, crate::generics::UserIdExists>>) -> ResponseUserBuilder<CreatedAt, Email, Enable, Id, Locale, Name, crate::generics::Oauth2ProviderExists, Oauth2ProviderCount, Static, Timezone, UpdatedAt> {
+    pub fn oauth2_provider(mut self, value: impl Iterator<Item = crate::response_user::ResponseUserOauth2ProviderItemBuilder<crate::generics::CreatedAtExists, crate::generics::Oauth2ProviderExists, crate::generics::StaticExists, crate::generics::SubExists, crate::generics::UserIdExists>>) -> ResponseUserBuilder<ApprovalPending, CreatedAt, Email, Enable, FactorCount, Id, Locale, Name, crate::generics::Oauth2ProviderExists, Oauth2ProviderCount, Static, Timezone, UpdatedAt> {
         self.body.oauth2_provider = value.map(|value| value.into()).collect::<Vec<_>>().into();
         unsafe { std::mem::transmute(self) }
     }
 
     #[inline]
-    pub fn oauth2_provider_count(mut self, value: impl Into<i64>) -> ResponseUserBuilder<CreatedAt, Email, Enable, Id, Locale, Name, Oauth2Provider, crate::generics::Oauth2ProviderCountExists, Static, Timezone, UpdatedAt> {
+    pub fn oauth2_provider_count(mut self, value: impl Into<i64>) -> ResponseUserBuilder<ApprovalPending, CreatedAt, Email, Enable, FactorCount, Id, Locale, Name, Oauth2Provider, crate::generics::Oauth2ProviderCountExists, Static, Timezone, UpdatedAt> {
         self.body.oauth2_provider_count = value.into();
         unsafe { std::mem::transmute(self) }
     }
@@ -152,19 +177,19 @@ impl<CreatedAt, Email, Enable, Id, Locale, Name, Oauth2Provider, Oauth2ProviderC
     }
 
     #[inline]
-    pub fn static_(mut self, value: impl Into<bool>) -> ResponseUserBuilder<CreatedAt, Email, Enable, Id, Locale, Name, Oauth2Provider, Oauth2ProviderCount, crate::generics::StaticExists, Timezone, UpdatedAt> {
+    pub fn static_(mut self, value: impl Into<bool>) -> ResponseUserBuilder<ApprovalPending, CreatedAt, Email, Enable, FactorCount, Id, Locale, Name, Oauth2Provider, Oauth2ProviderCount, crate::generics::StaticExists, Timezone, UpdatedAt> {
         self.body.static_ = value.into();
         unsafe { std::mem::transmute(self) }
     }
 
     #[inline]
-    pub fn timezone(mut self, value: impl Into<String>) -> ResponseUserBuilder<CreatedAt, Email, Enable, Id, Locale, Name, Oauth2Provider, Oauth2ProviderCount, Static, crate::generics::TimezoneExists, UpdatedAt> {
+    pub fn timezone(mut self, value: impl Into<String>) -> ResponseUserBuilder<ApprovalPending, CreatedAt, Email, Enable, FactorCount, Id, Locale, Name, Oauth2Provider, Oauth2ProviderCount, Static, crate::generics::TimezoneExists, UpdatedAt> {
         self.body.timezone = value.into();
         unsafe { std::mem::transmute(self) }
     }
 
     #[inline]
-    pub fn updated_at(mut self, value: impl Into<String>) -> ResponseUserBuilder<CreatedAt, Email, Enable, Id, Locale, Name, Oauth2Provider, Oauth2ProviderCount, Static, Timezone, crate::generics::UpdatedAtExists> {
+    pub fn updated_at(mut self, value: impl Into<String>) -> ResponseUserBuilder<ApprovalPending, CreatedAt, Email, Enable, FactorCount, Id, Locale, Name, Oauth2Provider, Oauth2ProviderCount, Static, Timezone, crate::generics::UpdatedAtExists> {
         self.body.updated_at = value.into();
         unsafe { std::mem::transmute(self) }
     }
@@ -5,9 +5,12 @@ pub struct ResponseUserMany {
 #[derive(Debug, Default, Clone, Serialize, Deserialize)]
 pub struct ResponseUserManyDataItem {
     pub access: Option<crate::response_user_many::ResponseUserManyDataItemAccess>,
+    pub approval_pending: bool,
     pub created_at: String,
     pub email: String,
+    pub email_verified_at: Option<String>,
     pub enable: bool,
+    pub factor_count: i64,
     pub id: String,
     pub locale: String,
     pub name: String,
@@ -76,7 +79,7 @@ pub struct ResponseUserManyBuilder<Data> {
 
 impl<Data> ResponseUserManyBuilder<Data> {
     #[inline]
-    pub fn data(mut self, value: impl Iterator<Item = crate::response_user_many::ResponseUserManyDataItemBuilder<crate::generics::CreatedAtExists, crate::generics::EmailExists, crate::generics::EnableExists, crate::generics::IdExists, crate::generics::LocaleExists, crate::generics::NameExists, crate::generics::Oauth2ProviderExists, crate::generics::Oauth2ProviderCountExists, crate::generics::StaticExists, crate::generics::TimezoneExists, crate::generics::UpdatedAtExists>>) -> ResponseUserManyBuilder<crate::generics::DataExists> {
+    pub fn data(mut self, value: impl Iterator<Item = crate::response_user_many::ResponseUserManyDataItemBuilder<crate::generics::ApprovalPendingExists, crate::generics::CreatedAtExists, crate::generics::EmailExists, crate::generics::EnableExists, crate::generics::FactorCountExists, crate::generics::IdExists, crate::generics::LocaleExists, crate::generics::NameExists, crate::generics::Oauth2ProviderExists, crate::generics::Oauth2ProviderCountExists, crate::generics::StaticExists, crate::generics::TimezoneExists, crate::generics::UpdatedAtExists>>) -> ResponseUserManyBuilder<crate::generics::DataExists> {
         self.body.data = value.map(|value| value.into()).collect::<Vec<_>>().into();
         unsafe { std::mem::transmute(self) }
     }
@@ -85,12 +88,14 @@ impl<Data> ResponseUserManyBuilder<Data> {
 impl ResponseUserManyDataItem {
     /// Create a builder for this object.
     #[inline]
-    pub fn builder() -> ResponseUserManyDataItemBuilder<crate::generics::MissingCreatedAt, crate::generics::MissingEmail, crate::generics::MissingEnable, crate::generics::MissingId, crate::generics::MissingLocale, crate::generics::MissingName, crate::generics::MissingOauth2Provider, crate::generics::MissingOauth2ProviderCount, crate::generics::MissingStatic, crate::generics::MissingTimezone, crate::generics::MissingUpdatedAt> {
+    pub fn builder() -> ResponseUserManyDataItemBuilder<crate::generics::MissingApprovalPending, crate::generics::MissingCreatedAt, crate::generics::MissingEmail, crate::generics::MissingEnable, crate::generics::MissingFactorCount, crate::generics::MissingId, crate::generics::MissingLocale, crate::generics::MissingName, crate::generics::MissingOauth2Provider, crate::generics::MissingOauth2ProviderCount, crate::generics::MissingStatic, crate::generics::MissingTimezone, crate::generics::MissingUpdatedAt> {
         ResponseUserManyDataItemBuilder {
             body: Default::default(),
+            _approval_pending: core::marker::PhantomData,
             _created_at: core::marker::PhantomData,
             _email: core::marker::PhantomData,
             _enable: core::marker::PhantomData,
+            _factor_count: core::marker::PhantomData,
             _id: core::marker::PhantomData,
             _locale: core::marker::PhantomData,
             _name: core::marker::PhantomData,
@@ -103,7 +108,7 @@ impl ResponseUserManyDataItem {
     }
 }
 
-impl Into<ResponseUserManyDataItem> for ResponseUserManyDataItemBuilder<crate::generics::CreatedAtExists, crate::generics::EmailExists, crate::generics::EnableExists, crate::generics::IdExists, crate::generics::LocaleExists, crate::generics::NameExists, crate::generics::Oauth2ProviderExists, crate::generics::Oauth2ProviderCountExists, crate::generics::StaticExists, crate::generics::TimezoneExists, crate::generics::UpdatedAtExists> {
+impl Into<ResponseUserManyDataItem> for ResponseUserManyDataItemBuilder<crate::generics::ApprovalPendingExists, crate::generics::CreatedAtExists, crate::generics::EmailExists, crate::generics::EnableExists, crate::generics::FactorCountExists, crate::generics::IdExists, crate::generics::LocaleExists, crate::generics::NameExists, crate::generics::Oauth2ProviderExists, crate::generics::Oauth2ProviderCountExists, crate::generics::StaticExists, crate::generics::TimezoneExists, crate::generics::UpdatedAtExists> {
     fn into(self) -> ResponseUserManyDataItem {
         self.body
     }
@@ -111,11 +116,13 @@ impl Into<ResponseUserManyDataItem> for ResponseUserManyDataItemBuilder<crate::g
 
 /// Builder for [`ResponseUserManyDataItem`](./struct.ResponseUserManyDataItem.html) object.
 #[derive(Debug, Clone)]
-pub struct ResponseUserManyDataItemBuilder<CreatedAt, Email, Enable, Id, Locale, Name, Oauth2Provider, Oauth2ProviderCount, Static, Timezone, UpdatedAt> {
+pub struct ResponseUserManyDataItemBuilder<ApprovalPending, CreatedAt, Email, Enable, FactorCount, Id, Locale, Name, Oauth2Provider, Oauth2ProviderCount, Static, Timezone, UpdatedAt> {
     body: self::ResponseUserManyDataItem,
+    _approval_pending: core::marker::PhantomData<ApprovalPending>,
     _created_at: core::marker::PhantomData<CreatedAt>,
     _email: core::marker::PhantomData<Email>,
     _enable: core::marker::PhantomData<Enable>,
+    _factor_count: core::marker::PhantomData<FactorCount>,
     _id: core::marker::PhantomData<Id>,
     _locale: core::marker::PhantomData<Locale>,
     _name: core::marker::PhantomData<Name>,
@@ -126,7 +133,7 @@ pub struct ResponseUserManyDataItemBuilder<CreatedAt, Email, Enable, Id, Locale,
     _updated_at: core::marker::PhantomData<UpdatedAt>,
 }
 
-impl<CreatedAt, Email, Enable, Id, Locale, Name, Oauth2Provider, Oauth2ProviderCount, Static, Timezone, UpdatedAt> ResponseUserManyDataItemBuilder<CreatedAt, Email, Enable, Id, Locale, Name, Oauth2Provider, Oauth2ProviderCount, Static, Timezone, UpdatedAt> {
+impl<ApprovalPending, CreatedAt, Email, Enable, FactorCount, Id, Locale, Name, Oauth2Provider, Oauth2ProviderCount, Static, Timezone, UpdatedAt> ResponseUserManyDataItemBuilder<ApprovalPending, CreatedAt, Email, Enable, FactorCount, Id, Locale, Name, Oauth2Provider, Oauth2ProviderCount, Static, Timezone, UpdatedAt> {
     #[inline]
     pub fn access(mut self, value: crate::response_user_many::ResponseUserManyDataItemAccessBuilder<crate::generics::ClientIdExists, crate::generics::CreatedAtExists, crate::generics::EnableExists, crate::generics::ScopeExists, crate::generics::StaticExists, crate::generics::UpdatedAtExists, crate::generics::UserIdExists>) -> Self {
         self.body.access = Some(value.into());
@@ -134,49 +141,67 @@ impl<CreatedAt, Email, Enable, Id, Locale, Name, Oauth2Provider, Oauth2ProviderC
     }
 
     #[inline]
-    pub fn created_at(mut self, value: impl Into<String>) -> ResponseUserManyDataItemBuilder<crate::generics::CreatedAtExists, Email, Enable, Id, Locale, Name, Oauth2Provider, Oauth2ProviderCount, Static, Timezone, UpdatedAt> {
+    pub fn approval_pending(mut self, value: impl Into<bool>) -> ResponseUserManyDataItemBuilder<crate::generics::ApprovalPendingExists, CreatedAt, Email, Enable, FactorCount, Id, Locale, Name, Oauth2Provider, Oauth2ProviderCount, Static, Timezone, UpdatedAt> {
+        self.body.approval_pending = value.into();
+        unsafe { std::mem::transmute(self) }
+    }
+
+    #[inline]
+    pub fn created_at(mut self, value: impl Into<String>) -> ResponseUserManyDataItemBuilder<ApprovalPending, crate::generics::CreatedAtExists, Email, Enable, FactorCount, Id, Locale, Name, Oauth2Provider, Oauth2ProviderCount, Static, Timezone, UpdatedAt> {
         self.body.created_at = value.into();
         unsafe { std::mem::transmute(self) }
     }
 
     #[inline]
-    pub fn email(mut self, value: impl Into<String>) -> ResponseUserManyDataItemBuilder<CreatedAt, crate::generics::EmailExists, Enable, Id, Locale, Name, Oauth2Provider, Oauth2ProviderCount, Static, Timezone, UpdatedAt> {
+    pub fn email(mut self, value: impl Into<String>) -> ResponseUserManyDataItemBuilder<ApprovalPending, CreatedAt, crate::generics::EmailExists, Enable, FactorCount, Id, Locale, Name, Oauth2Provider, Oauth2ProviderCount, Static, Timezone, UpdatedAt> {
         self.body.email = value.into();
         unsafe { std::mem::transmute(self) }
     }
 
     #[inline]
-    pub fn enable(mut self, value: impl Into<bool>) -> ResponseUserManyDataItemBuilder<CreatedAt, Email, crate::generics::EnableExists, Id, Locale, Name, Oauth2Provider, Oauth2ProviderCount, Static, Timezone, UpdatedAt> {
+    pub fn email_verified_at(mut self, value: impl Into<String>) -> Self {
+        self.body.email_verified_at = Some(value.into());
+        self
+    }
+
+    #[inline]
+    pub fn enable(mut self, value: impl Into<bool>) -> ResponseUserManyDataItemBuilder<ApprovalPending, CreatedAt, Email, crate::generics::EnableExists, FactorCount, Id, Locale, Name, Oauth2Provider, Oauth2ProviderCount, Static, Timezone, UpdatedAt> {
         self.body.enable = value.into();
         unsafe { std::mem::transmute(self) }
     }
 
     #[inline]
-    pub fn id(mut self, value: impl Into<String>) -> ResponseUserManyDataItemBuilder<CreatedAt, Email, Enable, crate::generics::IdExists, Locale, Name, Oauth2Provider, Oauth2ProviderCount, Static, Timezone, UpdatedAt> {
+    pub fn factor_count(mut self, value: impl Into<i64>) -> ResponseUserManyDataItemBuilder<ApprovalPending, CreatedAt, Email, Enable, crate::generics::FactorCountExists, Id, Locale, Name, Oauth2Provider, Oauth2ProviderCount, Static, Timezone, UpdatedAt> {
+        self.body.factor_count = value.into();
+        unsafe { std::mem::transmute(self) }
+    }
+
+    #[inline]
+    pub fn id(mut self, value: impl Into<String>) -> ResponseUserManyDataItemBuilder<ApprovalPending, CreatedAt, Email, Enable, FactorCount, crate::generics::IdExists, Locale, Name, Oauth2Provider, Oauth2ProviderCount, Static, Timezone, UpdatedAt> {
         self.body.id = value.into();
         unsafe { std::mem::transmute(self) }
     }
 
     #[inline]
-    pub fn locale(mut self, value: impl Into<String>) -> ResponseUserManyDataItemBuilder<CreatedAt, Email, Enable, Id, crate::generics::LocaleExists, Name, Oauth2Provider, Oauth2ProviderCount, Static, Timezone, UpdatedAt> {
+    pub fn locale(mut self, value: impl Into<String>) -> ResponseUserManyDataItemBuilder<ApprovalPending, CreatedAt, Email, Enable, FactorCount, Id, crate::generics::LocaleExists, Name, Oauth2Provider, Oauth2ProviderCount, Static, Timezone, UpdatedAt> {
         self.body.locale = value.into();
         unsafe { std::mem::transmute(self) }
     }
 
     #[inline]
-    pub fn name(mut self, value: impl Into<String>) -> ResponseUserManyDataItemBuilder<CreatedAt, Email, Enable, Id, Locale, crate::generics::NameExists, Oauth2Provider, Oauth2ProviderCount, Static, Timezone, UpdatedAt> {
+    pub fn name(mut self, value: impl Into<String>) -> ResponseUserManyDataItemBuilder<ApprovalPending, CreatedAt, Email, Enable, FactorCount, Id, Locale, crate::generics::NameExists, Oauth2Provider, Oauth2ProviderCount, Static, Timezone, UpdatedAt> {
         self.body.name = value.into();
         unsafe { std::mem::transmute(self) }
     }
 
     #[inline]
-    pub fn oauth2_provider(mut self, value: impl Iterator<Item = crate::response_user_many::ResponseUserManyDataItemOauth2ProviderItemBuilder<crate::generics::CreatedAtExists, crate::generics::Oauth2ProviderExists, crate::generics::StaticExists, crate::generics::SubExists, crate::generics::UserIdExists>>) -> ResponseUserManyDataItemBuilder<CreatedAt, Email, Enable, Id, Locale, Name, crate::generics::Oauth2ProviderExists, Oauth2ProviderCount, Static, Timezone, UpdatedAt> {
+    pub fn oauth2_provider(mut self, value: impl Iterator<Item = crate::response_user_many::ResponseUserManyDataItemOauth2ProviderItemBuilder<crate::generics::CreatedAtExists, crate::generics::Oauth2ProviderExists, crate::generics::StaticExists, crate::generics::SubExists, crate::generics::UserIdExists>>) -> ResponseUserManyDataItemBuilder<ApprovalPending, CreatedAt, Email, Enable, FactorCount, Id, Locale, Name, crate::generics::Oauth2ProviderExists, Oauth2ProviderCount, Static, Timezone, UpdatedAt> {
         self.body.oauth2_provider = value.map(|value| value.into()).collect::<Vec<_>>().into();
         unsafe { std::mem::transmute(self) }
     }
 
     #[inline]
-    pub fn oauth2_provider_count(mut self, value: impl Into<i64>) -> ResponseUserManyDataItemBuilder<CreatedAt, Email, Enable, Id, Locale, Name, Oauth2Provider, crate::generics::Oauth2ProviderCountExists, Static, Timezone, UpdatedAt> {
+    pub fn oauth2_provider_count(mut self, value: impl Into<i64>) -> ResponseUserManyDataItemBuilder<ApprovalPending, CreatedAt, Email, Enable, FactorCount, Id, Locale, Name, Oauth2Provider, crate::generics::Oauth2ProviderCountExists, Static, Timezone, UpdatedAt> {
         self.body.oauth2_provider_count = value.into();
         unsafe { std::mem::transmute(self) }
     }
@@ -188,19 +213,19 @@ impl<CreatedAt, Email, Enable, Id, Locale, Name, Oauth2Provider, Oauth2ProviderC
     }
 
     #[inline]
-    pub fn static_(mut self, value: impl Into<bool>) -> ResponseUserManyDataItemBuilder<CreatedAt, Email, Enable, Id, Locale, Name, Oauth2Provider, Oauth2ProviderCount, crate::generics::StaticExists, Timezone, UpdatedAt> {
+    pub fn static_(mut self, value: impl Into<bool>) -> ResponseUserManyDataItemBuilder<ApprovalPending, CreatedAt, Email, Enable, FactorCount, Id, Locale, Name, Oauth2Provider, Oauth2ProviderCount, crate::generics::StaticExists, Timezone, UpdatedAt> {
         self.body.static_ = value.into();
         unsafe { std::mem::transmute(self) }
     }
 
     #[inline]
-    pub fn timezone(mut self, value: impl Into<String>) -> ResponseUserManyDataItemBuilder<CreatedAt, Email, Enable, Id, Locale, Name, Oauth2Provider, Oauth2ProviderCount, Static, crate::generics::TimezoneExists, UpdatedAt> {
+    pub fn timezone(mut self, value: impl Into<String>) -> ResponseUserManyDataItemBuilder<ApprovalPending, CreatedAt, Email, Enable, FactorCount, Id, Locale, Name, Oauth2Provider, Oauth2ProviderCount, Static, crate::generics::TimezoneExists, UpdatedAt> {
         self.body.timezone = value.into();
         unsafe { std::mem::transmute(self) }
     }
 
     #[inline]
-    pub fn updated_at(mut self, value: impl Into<String>) -> ResponseUserManyDataItemBuilder<CreatedAt, Email, Enable, Id, Locale, Name, Oauth2Provider, Oauth2ProviderCount, Static, Timezone, crate::generics::UpdatedAtExists> {
+    pub fn updated_at(mut self, value: impl Into<String>) -> ResponseUserManyDataItemBuilder<ApprovalPending, CreatedAt, Email, Enable, FactorCount, Id, Locale, Name, Oauth2Provider, Oauth2ProviderCount, Static, Timezone, crate::generics::UpdatedAtExists> {
         self.body.updated_at = value.into();
         unsafe { std::mem::transmute(self) }
     }
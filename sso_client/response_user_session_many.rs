@@ -0,0 +1,126 @@
+#[derive(Debug, Default, Clone, Serialize, Deserialize)]
+pub struct ResponseUserSessionMany {
+    pub data: Vec<crate::response_user_session_many::ResponseUserSessionManyDataItem>,
+}
+#[derive(Debug, Default, Clone, Serialize, Deserialize)]
+pub struct ResponseUserSessionManyDataItem {
+    pub created_at: String,
+    pub id: String,
+    pub last_seen_at: String,
+    pub methods: Vec<String>,
+    pub remote_addr: String,
+    pub user_agent: String,
+    pub user_id: String,
+}
+
+impl ResponseUserSessionMany {
+    /// Create a builder for this object.
+    #[inline]
+    pub fn builder() -> ResponseUserSessionManyBuilder<crate::generics::MissingData> {
+        ResponseUserSessionManyBuilder {
+            body: Default::default(),
+            _data: core::marker::PhantomData,
+        }
+    }
+}
+
+impl Into<ResponseUserSessionMany> for ResponseUserSessionManyBuilder<crate::generics::DataExists> {
+    fn into(self) -> ResponseUserSessionMany {
+        self.body
+    }
+}
+
+/// Builder for [`ResponseUserSessionMany`](./struct.ResponseUserSessionMany.html) object.
+#[derive(Debug, Clone)]
+pub struct ResponseUserSessionManyBuilder<Data> {
+    body: self::ResponseUserSessionMany,
+    _data: core::marker::PhantomData<Data>,
+}
+
+impl<Data> ResponseUserSessionManyBuilder<Data> {
+    #[inline]
+    pub fn data(mut self, value: impl Iterator<Item = crate::response_user_session_many::ResponseUserSessionManyDataItemBuilder<crate::generics::CreatedAtExists, crate::generics::IdExists, crate::generics::LastSeenAtExists, crate::generics::MethodsExists, crate::generics::RemoteAddrExists, crate::generics::UserAgentExists, crate::generics::UserIdExists>>) -> ResponseUserSessionManyBuilder<crate::generics::DataExists> {
+        self.body.data = value.map(|value| value.into()).collect::<Vec<_>>().into();
+        unsafe { std::mem::transmute(self) }
+    }
+}
+
+impl ResponseUserSessionManyDataItem {
+    /// Create a builder for this object.
+    #[inline]
+    pub fn builder() -> ResponseUserSessionManyDataItemBuilder<crate::generics::MissingCreatedAt, crate::generics::MissingId, crate::generics::MissingLastSeenAt, crate::generics::MissingMethods, crate::generics::MissingRemoteAddr, crate::generics::MissingUserAgent, crate::generics::MissingUserId> {
+        ResponseUserSessionManyDataItemBuilder {
+            body: Default::default(),
+            _created_at: core::marker::PhantomData,
+            _id: core::marker::PhantomData,
+            _last_seen_at: core::marker::PhantomData,
+            _methods: core::marker::PhantomData,
+            _remote_addr: core::marker::PhantomData,
+            _user_agent: core::marker::PhantomData,
+            _user_id: core::marker::PhantomData,
+        }
+    }
+}
+
+impl Into<ResponseUserSessionManyDataItem> for ResponseUserSessionManyDataItemBuilder<crate::generics::CreatedAtExists, crate::generics::IdExists, crate::generics::LastSeenAtExists, crate::generics::MethodsExists, crate::generics::RemoteAddrExists, crate::generics::UserAgentExists, crate::generics::UserIdExists> {
+    fn into(self) -> ResponseUserSessionManyDataItem {
+        self.body
+    }
+}
+
+/// Builder for [`ResponseUserSessionManyDataItem`](./struct.ResponseUserSessionManyDataItem.html) object.
+#[derive(Debug, Clone)]
+pub struct ResponseUserSessionManyDataItemBuilder<CreatedAt, Id, LastSeenAt, Methods, RemoteAddr, UserAgent, UserId> {
+    body: self::ResponseUserSessionManyDataItem,
+    _created_at: core::marker::PhantomData<CreatedAt>,
+    _id: core::marker::PhantomData<Id>,
+    _last_seen_at: core::marker::PhantomData<LastSeenAt>,
+    _methods: core::marker::PhantomData<Methods>,
+    _remote_addr: core::marker::PhantomData<RemoteAddr>,
+    _user_agent: core::marker::PhantomData<UserAgent>,
+    _user_id: core::marker::PhantomData<UserId>,
+}
+
+impl<CreatedAt, Id, LastSeenAt, Methods, RemoteAddr, UserAgent, UserId> ResponseUserSessionManyDataItemBuilder<CreatedAt, Id, LastSeenAt, Methods, RemoteAddr, UserAgent, UserId> {
+    #[inline]
+    pub fn created_at(mut self, value: impl Into<String>) -> ResponseUserSessionManyDataItemBuilder<crate::generics::CreatedAtExists, Id, LastSeenAt, Methods, RemoteAddr, UserAgent, UserId> {
+        self.body.created_at = value.into();
+        unsafe { std::mem::transmute(self) }
+    }
+
+    #[inline]
+    pub fn id(mut self, value: impl Into<String>) -> ResponseUserSessionManyDataItemBuilder<CreatedAt, crate::generics::IdExists, LastSeenAt, Methods, RemoteAddr, UserAgent, UserId> {
+        self.body.id = value.into();
+        unsafe { std::mem::transmute(self) }
+    }
+
+    #[inline]
+    pub fn last_seen_at(mut self, value: impl Into<String>) -> ResponseUserSessionManyDataItemBuilder<CreatedAt, Id, crate::generics::LastSeenAtExists, Methods, RemoteAddr, UserAgent, UserId> {
+        self.body.last_seen_at = value.into();
+        unsafe { std::mem::transmute(self) }
+    }
+
+    #[inline]
+    pub fn methods(mut self, value: impl Iterator<Item = impl Into<String>>) -> ResponseUserSessionManyDataItemBuilder<CreatedAt, Id, LastSeenAt, crate::generics::MethodsExists, RemoteAddr, UserAgent, UserId> {
+        self.body.methods = value.map(|value| value.into()).collect::<Vec<_>>().into();
+        unsafe { std::mem::transmute(self) }
+    }
+
+    #[inline]
+    pub fn remote_addr(mut self, value: impl Into<String>) -> ResponseUserSessionManyDataItemBuilder<CreatedAt, Id, LastSeenAt, Methods, crate::generics::RemoteAddrExists, UserAgent, UserId> {
+        self.body.remote_addr = value.into();
+        unsafe { std::mem::transmute(self) }
+    }
+
+    #[inline]
+    pub fn user_agent(mut self, value: impl Into<String>) -> ResponseUserSessionManyDataItemBuilder<CreatedAt, Id, LastSeenAt, Methods, RemoteAddr, crate::generics::UserAgentExists, UserId> {
+        self.body.user_agent = value.into();
+        unsafe { std::mem::transmute(self) }
+    }
+
+    #[inline]
+    pub fn user_id(mut self, value: impl Into<String>) -> ResponseUserSessionManyDataItemBuilder<CreatedAt, Id, LastSeenAt, Methods, RemoteAddr, UserAgent, crate::generics::UserIdExists> {
+        self.body.user_id = value.into();
+        unsafe { std::mem::transmute(self) }
+    }
+}
@@ -8,6 +8,8 @@ import {
     mailAddress,
     CLIENT_URI,
     SSO_URI,
+    mailRead,
+    mailUrls,
 } from "./util";
 
 describe("sso-api", function () {
@@ -66,6 +68,11 @@ describe("sso-api", function () {
         expect(client.userScope).toEqual("admin api");
         expect(client.registerEnable).toEqual(true);
         expect(client.registerScope).toEqual("");
+        expect(client.registerApproval).toEqual(false);
+        expect(client.requireMfa).toEqual(false);
+        expect(client.requireEmailVerified).toEqual(false);
+        expect(client.magicLinkEnable).toEqual(false);
+        expect(client.deviceNotifyEnable).toEqual(false);
     });
 
     it("should read client access", async function () {
@@ -203,6 +210,9 @@ describe("sso-api", function () {
         expect(user.password.allowReset).toEqual(true);
         expect(user.password.requireUpdate).toEqual(false);
         expect(user.access).toBeDefined();
+        expect(user.emailVerifiedAt).toBeFalsy();
+        expect(user.approvalPending).toEqual(false);
+        expect(user.factorCount).toEqual(0);
     });
 
     it("should fail to create user with invalid email", async function () {
@@ -623,4 +633,140 @@ describe("sso-api", function () {
             expect(e.response.body.message).toEqual("api key verify failed");
         }
     });
+
+    it("should unlock user", async function () {
+        let user = await userCreate({
+            password: PASSWORD1,
+            allowReset: true,
+            requireUpdate: false,
+        });
+
+        let response = await api.v2UserUnlockPost({
+            email: user.email,
+        });
+        expect(response.body).toEqual(null);
+    });
+
+    it("should read user sessions", async function () {
+        let user = await userCreate({
+            password: PASSWORD1,
+            allowReset: true,
+            requireUpdate: false,
+        });
+
+        let read = (
+            await api.v2UserSessionReadPost({
+                userId: user.id,
+            })
+        ).body;
+        expect(read).toBeDefined();
+        expect(read.data).toBeDefined();
+        expect(read.data.length).toEqual(0);
+    });
+
+    it("should delete user sessions", async function () {
+        let user = await userCreate({
+            password: PASSWORD1,
+            allowReset: true,
+            requireUpdate: false,
+        });
+
+        let response = await api.v2UserSessionDeletePost({
+            userId: user.id,
+        });
+        expect(response.body).toEqual(null);
+
+        let read = (
+            await api.v2UserSessionReadPost({
+                userId: user.id,
+            })
+        ).body;
+        expect(read.data.length).toEqual(0);
+    });
+
+    it("should fail to approve user registration not pending approval", async function () {
+        let user = await userCreate({
+            password: PASSWORD1,
+            allowReset: true,
+            requireUpdate: false,
+        });
+
+        try {
+            await api.v2UserRegisterApprovePost({
+                userId: user.id,
+            });
+            fail();
+        } catch (e) {
+            expect(e.statusCode).toEqual(400);
+            expect(e.response.body.error).toEqual("BadRequest");
+            expect(e.response.body.message).toEqual(
+                "user not found or not pending approval"
+            );
+        }
+    });
+
+    it("should fail to deny user registration not pending approval", async function () {
+        let user = await userCreate({
+            password: PASSWORD1,
+            allowReset: true,
+            requireUpdate: false,
+        });
+
+        try {
+            await api.v2UserRegisterDenyPost({
+                userId: user.id,
+            });
+            fail();
+        } catch (e) {
+            expect(e.statusCode).toEqual(400);
+            expect(e.response.body.error).toEqual("BadRequest");
+            expect(e.response.body.message).toEqual(
+                "user not found or not pending approval"
+            );
+        }
+
+        let users = (
+            await api.v2UserReadPost({
+                id: [user.id],
+            })
+        ).body;
+        expect(users.data.length).toEqual(1);
+    });
+
+    it("should invite user", async function () {
+        let email = mailAddress();
+
+        let response = await api.v2UserInvitePost({
+            email,
+            name: "test",
+            scope: "api",
+        });
+        expect(response.body).toEqual(null);
+
+        let mail = mailRead(email, "Invitation");
+        expect(mail).not.toBeNull();
+        let urls = mailUrls(mail);
+        expect(
+            urls.some(
+                (x) =>
+                    x.startsWith(`${SSO_URI}/v2/auth/register`) &&
+                    x.includes("response_type=invite")
+            )
+        ).toEqual(true);
+    });
+
+    it("should fail to invite user for unknown scope", async function () {
+        try {
+            await api.v2UserInvitePost({
+                email: mailAddress(),
+                name: "test",
+                scope: "unknownscope",
+            });
+            fail();
+        } catch (e) {
+            expect(e.statusCode).toEqual(400);
+            expect(e.response.body.error).toEqual("BadRequest");
+            expect(e.response.body.message).toEqual("scope invalid");
+        }
+    });
 });
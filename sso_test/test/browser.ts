@@ -23,6 +23,7 @@ import {
     browser_get_email_update,
     form_password_update_submit,
     form_email_update_submit,
+    form_email_update_accept_submit,
     browser_get_delete,
     form_delete_accept_submit,
     form_delete_submit,
@@ -62,7 +63,10 @@ describe("sso-browser", function () {
             PASSWORD1
         );
 
-        await error_check_code_description("access_denied", "email not found");
+        await error_check_code_description(
+            "access_denied",
+            "email or password is incorrect"
+        );
     });
 
     it("should fail to login with wrong password", async function () {
@@ -75,7 +79,7 @@ describe("sso-browser", function () {
 
         await error_check_code_description(
             "access_denied",
-            "password is incorrect"
+            "email or password is incorrect"
         );
     });
 
@@ -93,7 +97,10 @@ describe("sso-browser", function () {
 
         await form_password_login_submit(user.email, PASSWORD1);
 
-        await error_check_code_description("access_denied", "user is disabled");
+        await error_check_code_description(
+            "access_denied",
+            "email or password is incorrect"
+        );
     });
 
     it("should fail to login if user access not enabled", async function () {
@@ -135,7 +142,7 @@ describe("sso-browser", function () {
         // truncated to fit
         await error_check_code_description(
             "access_denied",
-            "password is incorrect"
+            "email or password is incorrect"
         );
     });
 
@@ -215,13 +222,18 @@ describe("sso-browser", function () {
         let email = mailAddress();
         await form_email_update_submit(PASSWORD1, email, email);
 
+        await form_email_update_accept_submit(email);
+
         await browser_delete_cookies();
 
         await browser_get_authorize();
 
         await form_password_login_submit(user.email, PASSWORD1);
 
-        await error_check_code_description("access_denied", "email not found");
+        await error_check_code_description(
+            "access_denied",
+            "email or password is incorrect"
+        );
 
         await form_password_login_submit(email, PASSWORD1);
 
@@ -273,7 +285,7 @@ describe("sso-browser", function () {
 
         await error_check_code_description(
             "access_denied",
-            "password is incorrect"
+            "email or password is incorrect"
         );
 
         await form_password_login_submit(user.email, PASSWORD2);
@@ -388,7 +400,10 @@ describe("sso-browser", function () {
 
         await form_password_login_submit(user.email, PASSWORD1);
 
-        await error_check_code_description("access_denied", "email not found");
+        await error_check_code_description(
+            "access_denied",
+            "email or password is incorrect"
+        );
     });
 
     it("should introspect token", async function () {
@@ -215,6 +215,23 @@ export async function form_email_update_submit(
     await browser_sleep();
 }
 
+export async function form_email_update_accept_submit(emailNew: string) {
+    let mail = mailRead(emailNew, "Email Update Request");
+    expect(mail).toBeDefined();
+
+    let urls = mailUrls(mail);
+    let accept_url = urls[0];
+
+    browser.get(accept_url);
+    expect(await browser.getCurrentUrl()).toContain(
+        `${SSO_URI}/v2/auth/email-update`
+    );
+
+    let submit = await browser.findElement(By.id("submit"));
+    await submit.click();
+    await browser_sleep();
+}
+
 export async function form_register_submit(emailAddress: string) {
     let email = await browser.findElement(By.id("register-email"));
     await email.sendKeys(emailAddress);